@@ -0,0 +1,108 @@
+//! The stubbed toolchains are shell scripts, so the benchmark only runs on Unix
+
+#[cfg(unix)]
+use std::env;
+#[cfg(unix)]
+use std::fs::{self, File};
+
+#[cfg(unix)]
+use clap::ArgMatches;
+#[cfg(unix)]
+use criterion::{criterion_group, criterion_main, Criterion};
+#[cfg(unix)]
+use starship::context::Context;
+#[cfg(unix)]
+use starship::print;
+
+#[cfg(unix)]
+#[allow(dead_code)]
+#[path = "../tests/testsuite/common.rs"]
+mod common;
+
+/// How long each stubbed toolchain takes to print its version
+#[cfg(unix)]
+const COMMAND_DELAY: &str = "0.05";
+
+/// Files which activate each of the benchmarked modules, and the version stubs they run
+#[cfg(unix)]
+const SLOW_MODULES: &[(&str, &str, &str, &str)] = &[
+    (
+        "crystal",
+        "shard.yml",
+        "crystal",
+        "Crystal 0.31.1 (2019-10-02)",
+    ),
+    ("golang", "go.mod", "go", "go version go1.12.1 linux/amd64"),
+    ("nodejs", "package.json", "node", "v12.0.0"),
+    ("ocaml", "dune-project", "opam", "4.10.0"),
+    (
+        "ruby",
+        "Gemfile",
+        "ruby",
+        "ruby 2.6.0p0 (2018-12-25 revision 66547)",
+    ),
+];
+
+#[cfg(unix)]
+fn render_slow_modules(c: &mut Criterion) {
+    let project_dir = tempfile::tempdir().unwrap();
+    let bin_dir = tempfile::tempdir().unwrap();
+    let config_dir = tempfile::tempdir().unwrap();
+
+    for (_, file, _, _) in SLOW_MODULES {
+        File::create(project_dir.path().join(file)).unwrap();
+    }
+    // Each stub sleeps before printing its version, like a slow toolchain would
+    let mut path = String::new();
+    for (_, _, command, output) in SLOW_MODULES {
+        let script = format!("sleep {}\necho '{}'", COMMAND_DELAY, output);
+        path = common::create_stub_command(bin_dir.path(), command, &script).unwrap();
+    }
+
+    let prompt_order = SLOW_MODULES
+        .iter()
+        .map(|(module, _, _, _)| format!("\"{}\"", module))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let config_path = config_dir.path().join("starship.toml");
+    fs::write(
+        &config_path,
+        format!("add_newline = false\nprompt_order = [{}]\n", prompt_order),
+    )
+    .unwrap();
+
+    env::set_var("STARSHIP_CONFIG", &config_path);
+    env::set_var("PATH", path);
+    env::remove_var("OPAMSWITCH");
+
+    // The global pool is sized to the number of CPUs, so `RAYON_NUM_THREADS` can be set
+    // to compare against more threads on machines with few cores
+    let sequential = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("slow modules");
+    group.sample_size(10);
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            let context = Context::new_with_dir(ArgMatches::default(), project_dir.path());
+            sequential.install(|| print::get_prompt(&context, false))
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| {
+            let context = Context::new_with_dir(ArgMatches::default(), project_dir.path());
+            print::get_prompt(&context, false)
+        })
+    });
+    group.finish();
+}
+
+#[cfg(unix)]
+criterion_group!(benches, render_slow_modules);
+#[cfg(unix)]
+criterion_main!(benches);
+
+#[cfg(not(unix))]
+fn main() {}
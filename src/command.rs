@@ -0,0 +1,244 @@
+use std::io::Read;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait between checks of whether a command has finished
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// The output of a command run by `execute_full`
+#[derive(Clone, Debug)]
+pub struct CommandOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub status: ExitStatus,
+}
+
+/// Why `execute_full` didn't return the output of a command
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CommandError {
+    /// The command couldn't be started or waited for
+    Failed,
+    /// The command, or the output it left open, outlasted the timeout
+    TimedOut,
+}
+
+/// Get the stdout of a command, or `None` if it exited with a non-zero status
+///
+/// Use the full output for commands which report to stderr or whose exit status matters.
+pub fn successful_stdout(program: &str, args: &[&str], output: CommandOutput) -> Option<String> {
+    if !output.status.success() {
+        log::warn!(
+            "Non-Zero exit code '{}' when executing `{} {}`",
+            output.status,
+            program,
+            args.join(" ")
+        );
+        return None;
+    }
+
+    Some(output.stdout)
+}
+
+/// Execute a command and return its stdout, stderr and exit status
+///
+/// Returns an error if the command can't be started, or is still running after
+/// `timeout`, in which case it's killed so that a hung tool can't stall the prompt.
+/// The same deadline applies to reading its output, which a process it left running
+/// in the background may hold open.
+pub fn execute_full(
+    program: &str,
+    args: &[&str],
+    timeout: Duration,
+) -> Result<CommandOutput, CommandError> {
+    let command_string = format!("{} {}", program, args.join(" "));
+    log::trace!("Executing `{}`", command_string);
+
+    let mut child = match Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            log::debug!("Unable to run `{}`: {}", command_string, e);
+            return Err(CommandError::Failed);
+        }
+    };
+
+    // Read the output on other threads, so a command can't block on a full pipe
+    let (stdout, stderr) = match (child.stdout.take(), child.stderr.take()) {
+        (Some(stdout), Some(stderr)) => (stdout, stderr),
+        _ => return Err(CommandError::Failed),
+    };
+    let stdout_reader = read_in_background(stdout);
+    let stderr_reader = read_in_background(stderr);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+            Ok(None) => {
+                log::warn!(
+                    "`{}` took longer than {}ms, killing it",
+                    command_string,
+                    timeout.as_millis()
+                );
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(CommandError::TimedOut);
+            }
+            Err(e) => {
+                log::debug!("Unable to wait for `{}`: {}", command_string, e);
+                return Err(CommandError::Failed);
+            }
+        }
+    };
+
+    let output = receive_before(&stdout_reader, deadline).and_then(|stdout| {
+        let stderr = receive_before(&stderr_reader, deadline)?;
+        Some(CommandOutput {
+            stdout,
+            stderr,
+            status,
+        })
+    });
+
+    output.ok_or_else(|| {
+        log::warn!(
+            "The output of `{}` was still open after {}ms, ignoring it",
+            command_string,
+            timeout.as_millis()
+        );
+        CommandError::TimedOut
+    })
+}
+
+fn read_in_background<R: Read + Send + 'static>(mut reader: R) -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = reader.read_to_end(&mut output);
+        let _ = sender.send(String::from_utf8_lossy(&output).into_owned());
+    });
+    receiver
+}
+
+/// Wait for the output read by `read_in_background`, giving up at `deadline`
+fn receive_before(receiver: &Receiver<String>, deadline: Instant) -> Option<String> {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    receiver.recv_timeout(remaining).ok()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_millis(500);
+
+    fn execute(program: &str, args: &[&str], timeout: Duration) -> Option<String> {
+        let output = execute_full(program, args, timeout).ok()?;
+        successful_stdout(program, args, output)
+    }
+
+    #[test]
+    fn test_execute_returns_stdout() {
+        assert_eq!(
+            execute("echo", &["hello"], TIMEOUT),
+            Some("hello\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_execute_argument_with_space() {
+        assert_eq!(
+            execute("printf", &["%s|%s", "hello world", "again"], TIMEOUT),
+            Some("hello world|again".to_string())
+        );
+
+        // Arguments built at runtime are passed through unchanged
+        let path = std::env::temp_dir().join("dir with spaces");
+        let path = path.to_string_lossy();
+        assert_eq!(
+            execute("printf", &["%s", &path], TIMEOUT),
+            Some(path.to_string())
+        );
+    }
+
+    #[test]
+    fn test_execute_missing_command() {
+        assert_eq!(execute("starship-missing-command", &[], TIMEOUT), None);
+        assert_eq!(
+            execute_full("starship-missing-command", &[], TIMEOUT).err(),
+            Some(CommandError::Failed)
+        );
+    }
+
+    #[test]
+    fn test_execute_failing_command() {
+        assert_eq!(execute("false", &[], TIMEOUT), None);
+    }
+
+    #[test]
+    fn test_execute_full_stderr_only() {
+        let output = execute_full("sh", &["-c", "echo 'Python 2.7.17' >&2"], TIMEOUT).unwrap();
+        assert_eq!(output.stdout, "");
+        assert_eq!(output.stderr, "Python 2.7.17\n");
+        assert!(output.status.success());
+
+        // Only stdout is returned by `execute`
+        assert_eq!(
+            execute("sh", &["-c", "echo 'Python 2.7.17' >&2"], TIMEOUT),
+            Some("".to_string())
+        );
+    }
+
+    #[test]
+    fn test_execute_full_non_zero_exit() {
+        let output = execute_full(
+            "sh",
+            &["-c", "echo partial; echo oops >&2; exit 3"],
+            TIMEOUT,
+        )
+        .unwrap();
+        assert_eq!(output.stdout, "partial\n");
+        assert_eq!(output.stderr, "oops\n");
+        assert_eq!(output.status.code(), Some(3));
+
+        assert_eq!(
+            execute("sh", &["-c", "echo partial; exit 3"], TIMEOUT),
+            None
+        );
+    }
+
+    #[test]
+    fn test_execute_times_out() {
+        let start = Instant::now();
+        assert_eq!(execute("sleep", &["10"], Duration::from_millis(50)), None);
+        assert_eq!(
+            execute_full("sleep", &["10"], Duration::from_millis(50)).err(),
+            Some(CommandError::TimedOut)
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_execute_times_out_on_output_held_open() {
+        // The background `sleep` keeps stdout open after `sh` itself has exited
+        let start = Instant::now();
+        assert_eq!(
+            execute_full(
+                "sh",
+                &["-c", "sleep 10 & echo done"],
+                Duration::from_millis(200)
+            )
+            .err(),
+            Some(CommandError::TimedOut)
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
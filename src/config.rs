@@ -3,6 +3,7 @@ use crate::utils;
 use ansi_term::{Color, Style};
 
 use std::clone::Clone;
+use std::collections::HashMap;
 use std::marker::Sized;
 
 use dirs::home_dir;
@@ -59,7 +60,18 @@ impl<'a> ModuleConfig<'a> for &'a str {
 
 impl<'a> ModuleConfig<'a> for Style {
     fn from_config(config: &Value) -> Option<Self> {
-        parse_style_string(config.as_str()?)
+        let style_string = config.as_str()?;
+        let style = parse_style_string(style_string);
+
+        // `none` intentionally clears the style, anything else is a typo in the config
+        if style.is_none() && !is_none_style(config) {
+            log::warn!(
+                "Invalid style string {:?}, falling back to the default style",
+                style_string
+            );
+        }
+
+        style
     }
 }
 
@@ -120,6 +132,29 @@ where
     }
 }
 
+impl<'a, T> ModuleConfig<'a> for HashMap<&'a str, T>
+where
+    T: ModuleConfig<'a>,
+{
+    fn from_config(config: &'a Value) -> Option<Self> {
+        config
+            .as_table()?
+            .iter()
+            .map(|(key, value)| Some((key.as_str(), T::from_config(value)?)))
+            .collect()
+    }
+
+    /// Merge the entries of a toml table into `self`, keeping the existing entries
+    /// which aren't overridden.
+    fn load_config(&self, config: &'a Value) -> Self {
+        let mut new_config = self.clone();
+        if let Some(config) = Self::from_config(config) {
+            new_config.extend(config);
+        }
+        new_config
+    }
+}
+
 /// Root config of starship.
 pub struct StarshipConfig {
     pub config: Option<Value>,
@@ -165,11 +200,34 @@ impl StarshipConfig {
             }
         }?;
 
-        let config = toml::from_str(&toml_content).ok()?;
+        let mut config = toml::from_str(&toml_content).ok()?;
+        Self::merge_global_symbols(&mut config);
         log::debug!("Config parsed: \n{:?}", &config);
         Some(config)
     }
 
+    /// Copy each symbol in the top-level `[symbols]` table into the config of the
+    /// module it's named after, unless that module sets its own `symbol`
+    fn merge_global_symbols(config: &mut Value) {
+        let symbols = match config.get("symbols").and_then(Value::as_table) {
+            Some(symbols) => symbols.clone(),
+            None => return,
+        };
+        let config_table = match config.as_table_mut() {
+            Some(config_table) => config_table,
+            None => return,
+        };
+
+        for (module_name, symbol) in symbols {
+            let module_config = config_table
+                .entry(module_name)
+                .or_insert_with(|| Value::Table(toml::value::Table::new()));
+            if let Some(module_table) = module_config.as_table_mut() {
+                module_table.entry("symbol").or_insert(symbol);
+            }
+        }
+    }
+
     /// Get the subset of the table for a module by its name
     pub fn get_module_config(&self, module_name: &str) -> Option<&Value> {
         let module_config = self.config.as_ref()?.as_table()?.get(module_name);
@@ -198,6 +256,8 @@ impl StarshipConfig {
 pub struct SegmentConfig<'a> {
     pub value: &'a str,
     pub style: Option<Style>,
+    /// Drop the segment when rendering if its value is empty
+    pub hide_when_empty: bool,
 }
 
 impl<'a> ModuleConfig<'a> for SegmentConfig<'a> {
@@ -206,10 +266,15 @@ impl<'a> ModuleConfig<'a> for SegmentConfig<'a> {
             Value::String(ref config_str) => Some(Self {
                 value: config_str,
                 style: None,
+                hide_when_empty: false,
             }),
             Value::Table(ref config_table) => Some(Self {
                 value: config_table.get("value")?.as_str()?,
                 style: config_table.get("style").and_then(<Style>::from_config),
+                hide_when_empty: config_table
+                    .get("hide_when_empty")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
             }),
             _ => None,
         }
@@ -226,7 +291,15 @@ impl<'a> ModuleConfig<'a> for SegmentConfig<'a> {
                     new_config.value = value;
                 };
                 if let Some(style) = config_table.get("style") {
-                    new_config.style = <Style>::from_config(style);
+                    // An invalid style keeps the default, but `none` still clears it
+                    new_config.style = match <Style>::from_config(style) {
+                        Some(style) => Some(style),
+                        None if is_none_style(style) => None,
+                        None => new_config.style,
+                    };
+                };
+                if let Some(Value::Boolean(hide_when_empty)) = config_table.get("hide_when_empty") {
+                    new_config.hide_when_empty = *hide_when_empty;
                 };
             }
             _ => {}
@@ -237,7 +310,11 @@ impl<'a> ModuleConfig<'a> for SegmentConfig<'a> {
 
 impl<'a> SegmentConfig<'a> {
     pub fn new(value: &'a str) -> Self {
-        Self { value, style: None }
+        Self {
+            value,
+            style: None,
+            hide_when_empty: false,
+        }
     }
 
     /// Immutably set value
@@ -245,6 +322,7 @@ impl<'a> SegmentConfig<'a> {
         Self {
             value,
             style: self.style,
+            hide_when_empty: self.hide_when_empty,
         }
     }
 
@@ -253,6 +331,16 @@ impl<'a> SegmentConfig<'a> {
         Self {
             value: self.value,
             style,
+            hide_when_empty: self.hide_when_empty,
+        }
+    }
+
+    /// Immutably set whether the segment is hidden when its value is empty
+    pub fn with_hide_when_empty(&self, hide_when_empty: bool) -> Self {
+        Self {
+            value: self.value,
+            style: self.style,
+            hide_when_empty,
         }
     }
 }
@@ -309,6 +397,15 @@ fn parse_style_string(style_string: &str) -> Option<ansi_term::Style> {
         })
 }
 
+/// Check whether a style string contains the `none` token, which clears the style
+fn is_none_style(config: &Value) -> bool {
+    config
+        .as_str()
+        .unwrap_or_default()
+        .split_whitespace()
+        .any(|token| token.eq_ignore_ascii_case("none"))
+}
+
 /** Parse a string that represents a color setting, returning None if this fails
  There are three valid color formats:
   - #RRGGBB      (a hash followed by an RGB hex)
@@ -323,9 +420,10 @@ fn parse_color_string(color_string: &str) -> Option<ansi_term::Color> {
             "Attempting to read hexadecimal color string: {}",
             color_string
         );
-        let r: u8 = u8::from_str_radix(&color_string[1..3], 16).ok()?;
-        let g: u8 = u8::from_str_radix(&color_string[3..5], 16).ok()?;
-        let b: u8 = u8::from_str_radix(&color_string[5..7], 16).ok()?;
+        // Use `get` rather than slicing so short or non-ASCII strings can't panic
+        let r: u8 = u8::from_str_radix(color_string.get(1..3)?, 16).ok()?;
+        let g: u8 = u8::from_str_radix(color_string.get(3..5)?, 16).ok()?;
+        let b: u8 = u8::from_str_radix(color_string.get(5..7)?, 16).ok()?;
         log::trace!("Read RGB color string: {},{},{}", r, g, b);
         return Some(Color::RGB(r, g, b));
     }
@@ -507,6 +605,75 @@ mod tests {
         assert_eq!(rust_config.switch_c, Switch::OFF);
     }
 
+    #[test]
+    fn test_load_map_config() {
+        #[derive(Clone, ModuleConfig)]
+        struct TestConfig<'a> {
+            pub symbols: HashMap<&'a str, &'a str>,
+        }
+
+        let config = toml::toml! {
+            [symbols]
+            linux = "L"
+            windows = "W"
+        };
+        let mut default_symbols = HashMap::new();
+        default_symbols.insert("linux", "T");
+        default_symbols.insert("macos", "M");
+        let default_config = TestConfig {
+            symbols: default_symbols,
+        };
+        let rust_config = default_config.load_config(&config);
+
+        assert_eq!(rust_config.symbols.get("linux"), Some(&"L"));
+        assert_eq!(rust_config.symbols.get("macos"), Some(&"M"));
+        assert_eq!(rust_config.symbols.get("windows"), Some(&"W"));
+    }
+
+    #[test]
+    fn test_merge_global_symbols() {
+        let mut config = toml::toml! {
+            [symbols]
+            python = "py "
+            rust = "rs "
+
+            [rust]
+            symbol = "🦀 "
+        };
+        StarshipConfig::merge_global_symbols(&mut config);
+        let config = StarshipConfig {
+            config: Some(config),
+        };
+
+        let get_symbol = |module_name| {
+            config
+                .get_module_config(module_name)
+                .and_then(|module_config| module_config.get("symbol"))
+                .and_then(Value::as_str)
+        };
+        assert_eq!(get_symbol("python"), Some("py "));
+        assert_eq!(get_symbol("rust"), Some("🦀 "));
+        assert_eq!(get_symbol("golang"), None);
+    }
+
+    #[test]
+    fn test_load_segment_config() {
+        let config = toml::toml! {
+            value = "v"
+            hide_when_empty = true
+        };
+        let segment_config = SegmentConfig::new("").load_config(&config);
+        assert_eq!(segment_config.value, "v");
+        assert!(segment_config.hide_when_empty);
+
+        let config = Value::from("x");
+        let segment_config = SegmentConfig::new("")
+            .with_hide_when_empty(true)
+            .load_config(&config);
+        assert_eq!(segment_config.value, "x");
+        assert!(segment_config.hide_when_empty);
+    }
+
     #[test]
     fn test_from_string() {
         let config = Value::String(String::from("S"));
@@ -582,6 +749,32 @@ mod tests {
         assert!(<Style>::from_config(&config).is_none());
     }
 
+    #[test]
+    fn table_get_styles_malformed_hex_colors() {
+        // Hex colors that are too short or contain multibyte characters
+        for style_string in &["#12", "fg:#", "bg:#1234", "#ééé", "#1é2345"] {
+            let config = Value::from(*style_string);
+            assert!(<Style>::from_config(&config).is_none());
+        }
+    }
+
+    #[test]
+    fn segment_config_keeps_default_style_when_style_is_invalid() {
+        let default = SegmentConfig {
+            value: "S",
+            style: Some(Color::Red.bold()),
+            hide_when_empty: false,
+        };
+        let config = toml::toml! {
+            value = "T"
+            style = "blu"
+        };
+
+        let segment = default.load_config(&config);
+        assert_eq!(segment.value, "T");
+        assert_eq!(segment.style, Some(Color::Red.bold()));
+    }
+
     #[test]
     fn table_get_styles_ordered() {
         // Test a background style with inverted order (also test hex + ANSI)
@@ -0,0 +1,25 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct AndroidConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub version: SegmentConfig<'a>,
+    pub show_target_sdk: bool,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for AndroidConfig<'a> {
+    fn new() -> Self {
+        AndroidConfig {
+            symbol: SegmentConfig::new("🤖 "),
+            version: SegmentConfig::default(),
+            show_target_sdk: false,
+            style: Color::Green.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -18,6 +18,9 @@ pub struct AwsConfig<'a> {
     pub style: Style,
     pub disabled: bool,
     pub displayed_items: AwsItems,
+    pub show_sso_expiry: bool,
+    pub sso_expiry: SegmentConfig<'a>,
+    pub sso_expired: SegmentConfig<'a>,
 }
 
 impl<'a> RootModuleConfig<'a> for AwsConfig<'a> {
@@ -29,6 +32,9 @@ impl<'a> RootModuleConfig<'a> for AwsConfig<'a> {
             style: Color::Yellow.bold(),
             disabled: false,
             displayed_items: AwsItems::All,
+            show_sso_expiry: false,
+            sso_expiry: SegmentConfig::default(),
+            sso_expired: SegmentConfig::new(" [expired]").with_style(Some(Color::Red.bold())),
         }
     }
 }
@@ -0,0 +1,26 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+use std::collections::HashMap;
+
+#[derive(Clone, ModuleConfig)]
+pub struct AzureConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub subscription: SegmentConfig<'a>,
+    pub subscription_aliases: HashMap<&'a str, &'a str>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for AzureConfig<'a> {
+    fn new() -> Self {
+        AzureConfig {
+            symbol: SegmentConfig::new("🔷 "),
+            subscription: SegmentConfig::default(),
+            subscription_aliases: HashMap::new(),
+            style: Color::Blue.bold(),
+            disabled: true,
+        }
+    }
+}
@@ -7,12 +7,35 @@ use starship_module_config_derive::ModuleConfig;
 pub struct BatteryConfig<'a> {
     pub full_symbol: SegmentConfig<'a>,
     pub charging_symbol: SegmentConfig<'a>,
+    pub charging_frames: Vec<&'a str>,
     pub discharging_symbol: SegmentConfig<'a>,
     pub unknown_symbol: Option<SegmentConfig<'a>>,
     pub empty_symbol: Option<SegmentConfig<'a>>,
     pub display: Vec<BatteryDisplayConfig>,
+    pub hysteresis: f64,
     pub disabled: bool,
     pub percentage: SegmentConfig<'a>,
+    pub precision: u64,
+    pub display_time: bool,
+    pub time: SegmentConfig<'a>,
+    pub display_temperature: bool,
+    pub temperature: SegmentConfig<'a>,
+    pub high_temperature_threshold: f64,
+    pub high_temperature_style: Option<Style>,
+    pub display_alert: bool,
+    pub alert: SegmentConfig<'a>,
+    pub alert_health_threshold: f64,
+    pub alert_temperature_threshold: f64,
+    pub cache_duration: u64,
+    pub display_full_at: bool,
+    pub full_at: SegmentConfig<'a>,
+    pub full_at_format: &'a str,
+    pub aggregate: bool,
+    pub index: SegmentConfig<'a>,
+    pub separator: SegmentConfig<'a>,
+    pub display_ac: bool,
+    pub ac_symbol: SegmentConfig<'a>,
+    pub battery_symbol: SegmentConfig<'a>,
 }
 
 impl<'a> RootModuleConfig<'a> for BatteryConfig<'a> {
@@ -20,6 +43,7 @@ impl<'a> RootModuleConfig<'a> for BatteryConfig<'a> {
         BatteryConfig {
             full_symbol: SegmentConfig::new("•"),
             charging_symbol: SegmentConfig::new("↑"),
+            charging_frames: Vec::new(),
             discharging_symbol: SegmentConfig::new("↓"),
             unknown_symbol: None,
             empty_symbol: None,
@@ -27,8 +51,30 @@ impl<'a> RootModuleConfig<'a> for BatteryConfig<'a> {
                 threshold: 10,
                 style: Color::Red.bold(),
             }],
+            hysteresis: 0.0,
             disabled: false,
             percentage: SegmentConfig::default(),
+            precision: 0,
+            display_time: false,
+            time: SegmentConfig::default(),
+            display_temperature: false,
+            temperature: SegmentConfig::default(),
+            high_temperature_threshold: 45.0,
+            high_temperature_style: None,
+            display_alert: false,
+            alert: SegmentConfig::new(" ⚠"),
+            alert_health_threshold: 80.0,
+            alert_temperature_threshold: 45.0,
+            cache_duration: 0,
+            display_full_at: false,
+            full_at: SegmentConfig::default(),
+            full_at_format: "full at %H:%M",
+            aggregate: true,
+            index: SegmentConfig::default(),
+            separator: SegmentConfig::new(" "),
+            display_ac: false,
+            ac_symbol: SegmentConfig::new("🔌 "),
+            battery_symbol: SegmentConfig::new("🔋 "),
         }
     }
 }
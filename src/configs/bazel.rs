@@ -0,0 +1,25 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct BazelConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub version: SegmentConfig<'a>,
+    pub show_version: bool,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for BazelConfig<'a> {
+    fn new() -> Self {
+        BazelConfig {
+            symbol: SegmentConfig::new("🌿 "),
+            version: SegmentConfig::default(),
+            show_version: false,
+            style: Color::Green.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -6,6 +6,7 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct CmdDurationConfig<'a> {
     pub min_time: i64,
+    pub show_milliseconds: bool,
     pub prefix: &'a str,
     pub style: Style,
     pub disabled: bool,
@@ -14,7 +15,8 @@ pub struct CmdDurationConfig<'a> {
 impl<'a> RootModuleConfig<'a> for CmdDurationConfig<'a> {
     fn new() -> Self {
         CmdDurationConfig {
-            min_time: 2,
+            min_time: 2_000,
+            show_milliseconds: false,
             prefix: "took ",
             style: Color::Yellow.bold(),
             disabled: false,
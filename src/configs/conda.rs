@@ -14,14 +14,8 @@ pub struct CondaConfig<'a> {
 impl<'a> RootModuleConfig<'a> for CondaConfig<'a> {
     fn new() -> Self {
         CondaConfig {
-            symbol: SegmentConfig {
-                value: "C ",
-                style: None,
-            },
-            environment: SegmentConfig {
-                value: "",
-                style: None,
-            },
+            symbol: SegmentConfig::new("C "),
+            environment: SegmentConfig::default(),
             style: Color::Green.bold(),
             disabled: false,
         }
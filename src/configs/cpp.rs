@@ -0,0 +1,25 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct CppConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub version: SegmentConfig<'a>,
+    pub compiler: &'a str,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for CppConfig<'a> {
+    fn new() -> Self {
+        CppConfig {
+            symbol: SegmentConfig::new("Ⓒ "),
+            version: SegmentConfig::default(),
+            compiler: "cc",
+            style: Color::Blue.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+/// Options shared by every module which looks for project files in the current directory
+#[derive(Clone, ModuleConfig)]
+pub struct DetectionConfig {
+    /// Show the module in every directory, without looking for project files
+    pub detect_always: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for DetectionConfig {
+    fn new() -> Self {
+        DetectionConfig {
+            detect_always: false,
+        }
+    }
+}
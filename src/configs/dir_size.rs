@@ -0,0 +1,31 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct DirSizeConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub size: SegmentConfig<'a>,
+    pub threshold: &'a str,
+    pub recursive: bool,
+    pub max_depth: i64,
+    pub timeout: u64,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for DirSizeConfig<'a> {
+    fn new() -> Self {
+        DirSizeConfig {
+            symbol: SegmentConfig::new("💾 "),
+            size: SegmentConfig::default(),
+            threshold: "1 GiB",
+            recursive: false,
+            max_depth: 5,
+            timeout: 100,
+            style: Color::Cyan.bold(),
+            disabled: true,
+        }
+    }
+}
@@ -1,25 +1,36 @@
-use crate::config::{ModuleConfig, RootModuleConfig};
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
 
 use ansi_term::{Color, Style};
 use starship_module_config_derive::ModuleConfig;
 
 #[derive(Clone, ModuleConfig)]
-pub struct DirectoryConfig {
+pub struct DirectoryConfig<'a> {
     pub truncation_length: i64,
     pub truncate_to_repo: bool,
     pub fish_style_pwd_dir_length: i64,
     pub use_logical_path: bool,
+    pub read_only: SegmentConfig<'a>,
+    pub show_git_ignored: bool,
+    pub git_ignored: SegmentConfig<'a>,
+    pub show_symlink_target: bool,
+    pub symlink_target: SegmentConfig<'a>,
     pub style: Style,
     pub disabled: bool,
 }
 
-impl<'a> RootModuleConfig<'a> for DirectoryConfig {
+impl<'a> RootModuleConfig<'a> for DirectoryConfig<'a> {
     fn new() -> Self {
         DirectoryConfig {
             truncation_length: 3,
             truncate_to_repo: true,
             fish_style_pwd_dir_length: 0,
             use_logical_path: true,
+            read_only: SegmentConfig::new(" 🔒").with_style(Some(Color::Red.normal())),
+            show_git_ignored: false,
+            git_ignored: SegmentConfig::new(" (ignored)")
+                .with_style(Some(Color::Fixed(242).normal())),
+            show_symlink_target: false,
+            symlink_target: SegmentConfig::default().with_style(Some(Color::Fixed(242).normal())),
             style: Color::Cyan.bold(),
             disabled: false,
         }
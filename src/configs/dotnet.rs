@@ -9,6 +9,10 @@ pub struct DotnetConfig<'a> {
     pub version: SegmentConfig<'a>,
     pub style: Style,
     pub heuristic: bool,
+    pub show_tfm: bool,
+    pub tfm: SegmentConfig<'a>,
+    pub check_sdk_mismatch: bool,
+    pub sdk_mismatch: SegmentConfig<'a>,
     pub disabled: bool,
 }
 
@@ -19,6 +23,10 @@ impl<'a> RootModuleConfig<'a> for DotnetConfig<'a> {
             version: SegmentConfig::default(),
             style: Color::Blue.bold(),
             heuristic: true,
+            show_tfm: false,
+            tfm: SegmentConfig::default(),
+            check_sdk_mismatch: false,
+            sdk_mismatch: SegmentConfig::new(" ⚠"),
             disabled: false,
         }
     }
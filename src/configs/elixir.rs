@@ -0,0 +1,31 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+use std::collections::HashMap;
+
+#[derive(Clone, ModuleConfig)]
+pub struct ElixirConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub mix_env: SegmentConfig<'a>,
+    pub env_styles: HashMap<&'a str, Style>,
+    pub ignore_default_env: bool,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for ElixirConfig<'a> {
+    fn new() -> Self {
+        let mut env_styles = HashMap::new();
+        env_styles.insert("prod", Color::Red.bold());
+
+        ElixirConfig {
+            symbol: SegmentConfig::new("💧 "),
+            mix_env: SegmentConfig::default(),
+            env_styles,
+            ignore_default_env: false,
+            style: Color::Purple.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct FlutterConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub version: SegmentConfig<'a>,
+    pub channel: SegmentConfig<'a>,
+    pub show_version: bool,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for FlutterConfig<'a> {
+    fn new() -> Self {
+        FlutterConfig {
+            symbol: SegmentConfig::new("🐦 "),
+            version: SegmentConfig::default(),
+            channel: SegmentConfig::default(),
+            show_version: false,
+            style: Color::Blue.bold(),
+            disabled: false,
+        }
+    }
+}
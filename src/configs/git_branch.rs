@@ -9,6 +9,10 @@ pub struct GitBranchConfig<'a> {
     pub truncation_length: i64,
     pub truncation_symbol: &'a str,
     pub branch_name: SegmentConfig<'a>,
+    pub show_worktree: bool,
+    pub worktree: SegmentConfig<'a>,
+    pub show_remote: bool,
+    pub remote: SegmentConfig<'a>,
     pub style: Style,
     pub disabled: bool,
 }
@@ -20,6 +24,10 @@ impl<'a> RootModuleConfig<'a> for GitBranchConfig<'a> {
             truncation_length: std::i64::MAX,
             truncation_symbol: "…",
             branch_name: SegmentConfig::default(),
+            show_worktree: false,
+            worktree: SegmentConfig::default(),
+            show_remote: true,
+            remote: SegmentConfig::default(),
             style: Color::Purple.bold(),
             disabled: false,
         }
@@ -10,6 +10,8 @@ pub struct GitStatusConfig<'a> {
     pub behind: SegmentConfig<'a>,
     pub diverged: SegmentConfig<'a>,
     pub show_sync_count: bool,
+    pub clean: SegmentConfig<'a>,
+    pub show_clean: bool,
     pub conflicted: SegmentConfig<'a>,
     pub conflicted_count: CountConfig,
     pub deleted: SegmentConfig<'a>,
@@ -22,6 +24,11 @@ pub struct GitStatusConfig<'a> {
     pub staged_count: CountConfig,
     pub untracked: SegmentConfig<'a>,
     pub untracked_count: CountConfig,
+    pub lfs: SegmentConfig<'a>,
+    pub lfs_count: CountConfig,
+    pub submodules: SegmentConfig<'a>,
+    pub show_submodules: bool,
+    pub disabled_indicators: Vec<&'a str>,
     pub prefix: &'a str,
     pub suffix: &'a str,
     pub style: Style,
@@ -37,6 +44,8 @@ impl<'a> RootModuleConfig<'a> for GitStatusConfig<'a> {
             diverged: SegmentConfig::new("⇕"),
             conflicted: SegmentConfig::new("="),
             show_sync_count: false,
+            clean: SegmentConfig::new("✓"),
+            show_clean: false,
             conflicted_count: CountConfig::default(),
             deleted: SegmentConfig::new("✘"),
             deleted_count: CountConfig::default(),
@@ -48,6 +57,11 @@ impl<'a> RootModuleConfig<'a> for GitStatusConfig<'a> {
             staged_count: CountConfig::default(),
             untracked: SegmentConfig::new("?"),
             untracked_count: CountConfig::default(),
+            lfs: SegmentConfig::new("LFS"),
+            lfs_count: CountConfig::default(),
+            submodules: SegmentConfig::new("⊂"),
+            show_submodules: false,
+            disabled_indicators: Vec::new(),
             prefix: "[",
             suffix: "] ",
             style: Color::Red.bold(),
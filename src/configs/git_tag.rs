@@ -0,0 +1,27 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct GitTagConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub tag_name: SegmentConfig<'a>,
+    pub show_all: bool,
+    pub separator: &'a str,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for GitTagConfig<'a> {
+    fn new() -> Self {
+        GitTagConfig {
+            symbol: SegmentConfig::new("🏷 "),
+            tag_name: SegmentConfig::default(),
+            show_all: false,
+            separator: ", ",
+            style: Color::Cyan.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct HelmConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub chart: SegmentConfig<'a>,
+    pub namespace: SegmentConfig<'a>,
+    pub separator: &'a str,
+    pub show_context: bool,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for HelmConfig<'a> {
+    fn new() -> Self {
+        HelmConfig {
+            symbol: SegmentConfig::new("⎈ "),
+            chart: SegmentConfig::default(),
+            namespace: SegmentConfig::default(),
+            separator: "@",
+            show_context: false,
+            style: Color::Blue.bold(),
+            disabled: false,
+        }
+    }
+}
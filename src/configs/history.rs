@@ -0,0 +1,23 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct HistoryConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub number: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for HistoryConfig<'a> {
+    fn new() -> Self {
+        HistoryConfig {
+            symbol: SegmentConfig::new("!"),
+            number: SegmentConfig::default(),
+            style: Color::White.dimmed(),
+            disabled: true,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct K8sPodConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub pod: SegmentConfig<'a>,
+    pub namespace: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for K8sPodConfig<'a> {
+    fn new() -> Self {
+        K8sPodConfig {
+            symbol: SegmentConfig::new("☸ "),
+            pod: SegmentConfig::default(),
+            namespace: SegmentConfig::default(),
+            style: Color::Cyan.bold(),
+            disabled: false,
+        }
+    }
+}
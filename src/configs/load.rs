@@ -0,0 +1,36 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct LoadConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub load: SegmentConfig<'a>,
+    pub threshold: f64,
+    pub display: Vec<LoadDisplayConfig>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for LoadConfig<'a> {
+    fn new() -> Self {
+        LoadConfig {
+            symbol: SegmentConfig::new("⚖️  "),
+            load: SegmentConfig::default(),
+            threshold: 1.0,
+            display: vec![LoadDisplayConfig {
+                threshold: 4.0,
+                style: Color::Red.bold(),
+            }],
+            style: Color::Yellow.bold(),
+            disabled: true,
+        }
+    }
+}
+
+#[derive(Clone, ModuleConfig)]
+pub struct LoadDisplayConfig {
+    pub threshold: f64,
+    pub style: Style,
+}
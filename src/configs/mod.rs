@@ -1,28 +1,57 @@
+pub mod android;
 pub mod aws;
+pub mod azure;
 pub mod battery;
+pub mod bazel;
+pub mod bun;
 pub mod character;
 pub mod cmd_duration;
 pub mod conda;
+pub mod cpp;
+pub mod crystal;
+pub mod detection;
+pub mod dir_size;
 pub mod directory;
 pub mod dotnet;
+pub mod elixir;
 pub mod env_var;
+pub mod flutter;
 pub mod git_branch;
 pub mod git_state;
 pub mod git_status;
+pub mod git_tag;
 pub mod go;
+pub mod gradle;
+pub mod helm;
+pub mod history;
 pub mod hostname;
 pub mod java;
 pub mod jobs;
+pub mod k8s_pod;
 pub mod kubernetes;
+pub mod load;
 pub mod memory_usage;
+pub mod multiplexer;
+pub mod nix_flake;
 pub mod nix_shell;
 pub mod nodejs;
+pub mod ocaml;
+pub mod os;
 pub mod package;
+pub mod perl;
+pub mod podman;
+pub mod profile;
+pub mod pulumi;
 pub mod python;
 pub mod ruby;
 pub mod rust;
+pub mod sandbox;
+pub mod slurm;
 mod starship_root;
+pub mod terraform;
 pub mod time;
 pub mod username;
+pub mod vcs_branch;
+pub mod wsl;
 
 pub use starship_root::*;
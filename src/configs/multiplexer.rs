@@ -0,0 +1,25 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct MultiplexerConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub tmux: SegmentConfig<'a>,
+    pub screen: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for MultiplexerConfig<'a> {
+    fn new() -> Self {
+        MultiplexerConfig {
+            symbol: SegmentConfig::new("⧉ "),
+            tmux: SegmentConfig::new("tmux"),
+            screen: SegmentConfig::new("screen"),
+            style: Color::Green.bold(),
+            disabled: true,
+        }
+    }
+}
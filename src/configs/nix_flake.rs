@@ -0,0 +1,23 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct NixFlakeConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub description: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for NixFlakeConfig<'a> {
+    fn new() -> Self {
+        NixFlakeConfig {
+            symbol: SegmentConfig::new("❄️ "),
+            description: SegmentConfig::default(),
+            style: Color::Blue.bold(),
+            disabled: false,
+        }
+    }
+}
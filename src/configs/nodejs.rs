@@ -6,6 +6,8 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct NodejsConfig<'a> {
     pub symbol: SegmentConfig<'a>,
+    pub check_lockfile: bool,
+    pub lockfile_stale: SegmentConfig<'a>,
     pub style: Style,
     pub disabled: bool,
 }
@@ -14,6 +16,8 @@ impl<'a> RootModuleConfig<'a> for NodejsConfig<'a> {
     fn new() -> Self {
         NodejsConfig {
             symbol: SegmentConfig::new("⬢ "),
+            check_lockfile: false,
+            lockfile_stale: SegmentConfig::new(" ⚠"),
             style: Color::Green.bold(),
             disabled: false,
         }
@@ -0,0 +1,25 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct OCamlConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub switch: SegmentConfig<'a>,
+    pub ignore_default_switch: bool,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for OCamlConfig<'a> {
+    fn new() -> Self {
+        OCamlConfig {
+            symbol: SegmentConfig::new("🐫 "),
+            switch: SegmentConfig::default(),
+            ignore_default_switch: false,
+            style: Color::Yellow.bold(),
+            disabled: false,
+        }
+    }
+}
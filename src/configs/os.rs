@@ -0,0 +1,41 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+use std::collections::HashMap;
+
+#[derive(Clone, ModuleConfig)]
+pub struct OsConfig<'a> {
+    pub symbols: HashMap<&'a str, &'a str>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for OsConfig<'a> {
+    fn new() -> Self {
+        let mut symbols = HashMap::new();
+        // Operating systems, as named by `std::env::consts::OS`
+        symbols.insert("freebsd", "😈 ");
+        symbols.insert("linux", "🐧 ");
+        symbols.insert("macos", "🍎 ");
+        symbols.insert("windows", "🏁 ");
+        // Linux distributions, as named by the `ID` field of `/etc/os-release`
+        symbols.insert("alpine", "🏔️ ");
+        symbols.insert("arch", "🎗️ ");
+        symbols.insert("centos", "💠 ");
+        symbols.insert("debian", "🌀 ");
+        symbols.insert("fedora", "🎩 ");
+        symbols.insert("gentoo", "🗜️ ");
+        symbols.insert("manjaro", "🥭 ");
+        symbols.insert("nixos", "❄️ ");
+        symbols.insert("opensuse-leap", "🦎 ");
+        symbols.insert("opensuse-tumbleweed", "🦎 ");
+        symbols.insert("ubuntu", "🎯 ");
+
+        OsConfig {
+            symbols,
+            style: Color::White.bold(),
+            disabled: true,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct PodmanConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub connection: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for PodmanConfig<'a> {
+    fn new() -> Self {
+        PodmanConfig {
+            symbol: SegmentConfig::new("🦭 "),
+            connection: SegmentConfig::default(),
+            style: Color::Purple.bold(),
+            disabled: false,
+        }
+    }
+}
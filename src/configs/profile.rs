@@ -0,0 +1,29 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct ProfileConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub guix: SegmentConfig<'a>,
+    pub nix: SegmentConfig<'a>,
+    pub show_generation: bool,
+    pub generation: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for ProfileConfig<'a> {
+    fn new() -> Self {
+        ProfileConfig {
+            symbol: SegmentConfig::new("λ "),
+            guix: SegmentConfig::new("guix"),
+            nix: SegmentConfig::new("nix"),
+            show_generation: true,
+            generation: SegmentConfig::default(),
+            style: Color::Yellow.bold(),
+            disabled: false,
+        }
+    }
+}
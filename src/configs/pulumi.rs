@@ -0,0 +1,23 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct PulumiConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub stack: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for PulumiConfig<'a> {
+    fn new() -> Self {
+        PulumiConfig {
+            symbol: SegmentConfig::new("🧊 "),
+            stack: SegmentConfig::default(),
+            style: Color::Purple.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -9,6 +9,35 @@ pub struct PythonConfig<'a> {
     pub version: SegmentConfig<'a>,
     pub pyenv_prefix: SegmentConfig<'a>,
     pub pyenv_version_name: bool,
+    pub version_command: Vec<&'a str>,
+    pub scan_parents: bool,
+    pub prefer_requires_python: bool,
+    pub show_eol: bool,
+    pub eol: SegmentConfig<'a>,
+    pub check_venv_version: bool,
+    pub venv_version_mismatch: SegmentConfig<'a>,
+    pub show_conda_base: bool,
+    pub detect_poetry: bool,
+    pub show_architecture: bool,
+    pub architecture: SegmentConfig<'a>,
+    pub show_source: bool,
+    pub source: SegmentConfig<'a>,
+    pub show_server_interface: bool,
+    pub server_interface: SegmentConfig<'a>,
+    pub show_pip_index: bool,
+    pub pip_index: SegmentConfig<'a>,
+    pub show_django_settings: bool,
+    pub django_settings: SegmentConfig<'a>,
+    pub django_production_patterns: Vec<&'a str>,
+    pub django_production_style: Style,
+    pub show_formatter: bool,
+    pub formatter: SegmentConfig<'a>,
+    pub show_coverage: bool,
+    pub coverage: SegmentConfig<'a>,
+    pub show_tests: bool,
+    pub tests: SegmentConfig<'a>,
+    pub check_requirements: bool,
+    pub requirements_mismatch: SegmentConfig<'a>,
     pub style: Style,
     pub disabled: bool,
 }
@@ -17,9 +46,38 @@ impl<'a> RootModuleConfig<'a> for PythonConfig<'a> {
     fn new() -> Self {
         PythonConfig {
             symbol: SegmentConfig::new("🐍 "),
-            version: SegmentConfig::default(),
+            version: SegmentConfig::default().with_hide_when_empty(true),
             pyenv_prefix: SegmentConfig::new("pyenv "),
             pyenv_version_name: false,
+            version_command: vec!["python", "--version"],
+            scan_parents: false,
+            prefer_requires_python: false,
+            show_eol: false,
+            eol: SegmentConfig::new(" ⚠").with_style(Some(Color::Red.bold())),
+            check_venv_version: false,
+            venv_version_mismatch: SegmentConfig::new(" ⚠"),
+            show_conda_base: false,
+            detect_poetry: false,
+            show_architecture: false,
+            architecture: SegmentConfig::default(),
+            show_source: false,
+            source: SegmentConfig::default(),
+            show_server_interface: false,
+            server_interface: SegmentConfig::default(),
+            show_pip_index: false,
+            pip_index: SegmentConfig::default(),
+            show_django_settings: false,
+            django_settings: SegmentConfig::default(),
+            django_production_patterns: vec!["prod"],
+            django_production_style: Color::Red.bold(),
+            show_formatter: false,
+            formatter: SegmentConfig::new(" 🧹"),
+            show_coverage: false,
+            coverage: SegmentConfig::new(" ☂"),
+            show_tests: false,
+            tests: SegmentConfig::new(" 🧪"),
+            check_requirements: false,
+            requirements_mismatch: SegmentConfig::new(" ⚠"),
             style: Color::Yellow.bold(),
             disabled: false,
         }
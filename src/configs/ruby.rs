@@ -2,18 +2,28 @@ use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
 
 use ansi_term::{Color, Style};
 use starship_module_config_derive::ModuleConfig;
+use std::collections::HashMap;
 
 #[derive(Clone, ModuleConfig)]
 pub struct RubyConfig<'a> {
     pub symbol: SegmentConfig<'a>,
+    pub rails_env: SegmentConfig<'a>,
+    pub env_styles: HashMap<&'a str, Style>,
+    pub ignore_default_env: bool,
     pub style: Style,
     pub disabled: bool,
 }
 
 impl<'a> RootModuleConfig<'a> for RubyConfig<'a> {
     fn new() -> Self {
+        let mut env_styles = HashMap::new();
+        env_styles.insert("production", Color::Red.bold().underline());
+
         RubyConfig {
             symbol: SegmentConfig::new("💎 "),
+            rails_env: SegmentConfig::default(),
+            env_styles,
+            ignore_default_env: false,
             style: Color::Red.bold(),
             disabled: false,
         }
@@ -0,0 +1,23 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct SandboxConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub app: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for SandboxConfig<'a> {
+    fn new() -> Self {
+        SandboxConfig {
+            symbol: SegmentConfig::new("📦 "),
+            app: SegmentConfig::default(),
+            style: Color::Purple.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct SlurmConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub job_id: SegmentConfig<'a>,
+    pub job_name: SegmentConfig<'a>,
+    pub partition: SegmentConfig<'a>,
+    pub show_job_name: bool,
+    pub show_partition: bool,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for SlurmConfig<'a> {
+    fn new() -> Self {
+        SlurmConfig {
+            symbol: SegmentConfig::new("🖥 "),
+            job_id: SegmentConfig::default(),
+            job_name: SegmentConfig::default(),
+            partition: SegmentConfig::default(),
+            show_job_name: true,
+            show_partition: true,
+            style: Color::Cyan.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -1,12 +1,21 @@
 use crate::config::{ModuleConfig, RootModuleConfig};
 
 use starship_module_config_derive::ModuleConfig;
+use std::collections::HashMap;
 
 #[derive(Clone, ModuleConfig)]
 pub struct StarshipRootConfig<'a> {
     pub add_newline: bool,
     pub prompt_order: Vec<&'a str>,
     pub scan_timeout: u64,
+    pub command_timeout: u64,
+    pub groups: HashMap<&'a str, ModuleGroupConfig<'a>>,
+    pub collapse_whitespace: bool,
+    pub max_width: Option<MaxWidth>,
+    pub max_width_drop_order: Vec<&'a str>,
+    pub max_width_marker: &'a str,
+    pub prompt_cache: bool,
+    pub prompt_cache_max_age: u64,
 }
 
 impl<'a> RootModuleConfig<'a> for StarshipRootConfig<'a> {
@@ -19,36 +28,141 @@ impl<'a> RootModuleConfig<'a> for StarshipRootConfig<'a> {
             prompt_order: vec![
                 "username",
                 "hostname",
+                "os",
+                "wsl",
+                "sandbox",
+                "multiplexer",
                 "kubernetes",
+                "k8s_pod",
+                "helm",
                 "directory",
+                "dir_size",
                 "git_branch",
+                "git_tag",
                 "git_state",
                 "git_status",
+                "vcs_branch",
                 "package",
                 // ↓ Toolchain version modules ↓
                 // (Let's keep these sorted alphabetically)
+                "android",
+                "bazel",
+                "bun",
+                "cpp",
+                "crystal",
                 "dotnet",
+                "elixir",
+                "flutter",
                 "golang",
+                "gradle",
                 "java",
                 "nodejs",
+                "ocaml",
+                "perl",
                 "python",
                 "ruby",
                 "rust",
+                "terraform",
                 // ↑ Toolchain version modules ↑
                 "nix_shell",
+                "nix_flake",
+                "profile",
                 "conda",
+                "slurm",
                 "memory_usage",
+                "load",
                 "aws",
+                "azure",
+                "podman",
+                "pulumi",
                 "env_var",
                 "cmd_duration",
                 "line_break",
                 "jobs",
+                "history",
                 #[cfg(feature = "battery")]
                 "battery",
                 "time",
                 "character",
             ],
             scan_timeout: 30,
+            command_timeout: 500,
+            groups: HashMap::new(),
+            collapse_whitespace: false,
+            max_width: None,
+            max_width_drop_order: Vec::new(),
+            max_width_marker: "… ",
+            prompt_cache: false,
+            prompt_cache_max_age: 300,
+        }
+    }
+}
+
+/// Modules which are rendered together between a pair of delimiters, when the name
+/// of the group is used in `prompt_order`
+#[derive(Clone)]
+pub struct ModuleGroupConfig<'a> {
+    pub modules: Vec<&'a str>,
+    pub open: &'a str,
+    pub close: &'a str,
+    pub separator: &'a str,
+}
+
+impl<'a> ModuleConfig<'a> for ModuleGroupConfig<'a> {
+    /// Only `modules` is required, the delimiters fall back to their defaults
+    fn from_config(config: &'a toml::Value) -> Option<Self> {
+        let config = config.as_table()?;
+        let get_str = |key: &str, default: &'a str| {
+            config
+                .get(key)
+                .and_then(<&str>::from_config)
+                .unwrap_or(default)
+        };
+
+        Some(ModuleGroupConfig {
+            modules: Vec::from_config(config.get("modules")?)?,
+            open: get_str("open", "["),
+            close: get_str("close", "] "),
+            separator: get_str("separator", " "),
+        })
+    }
+}
+
+/// The widest a line of the prompt may be before modules are dropped from it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaxWidth {
+    /// An absolute number of columns, e.g. `80`
+    Columns(usize),
+    /// A percentage of the terminal width from `$COLUMNS`, e.g. `"50%"`
+    Percentage(f64),
+}
+
+impl MaxWidth {
+    /// Get the maximum width in columns, or `None` if the terminal width is
+    /// needed but unknown
+    pub fn to_columns(self, terminal_width: Option<usize>) -> Option<usize> {
+        match self {
+            MaxWidth::Columns(columns) => Some(columns),
+            MaxWidth::Percentage(percentage) => {
+                Some((terminal_width? as f64 * percentage / 100.0) as usize)
+            }
+        }
+    }
+}
+
+impl<'a> ModuleConfig<'a> for MaxWidth {
+    fn from_config(config: &'a toml::Value) -> Option<Self> {
+        match config {
+            toml::Value::Integer(columns) if *columns > 0 => {
+                Some(MaxWidth::Columns(*columns as usize))
+            }
+            toml::Value::String(percentage) if percentage.ends_with('%') => {
+                match percentage.trim_end_matches('%').trim().parse::<f64>() {
+                    Ok(percentage) if percentage > 0.0 => Some(MaxWidth::Percentage(percentage)),
+                    _ => None,
+                }
+            }
+            _ => None,
         }
     }
 }
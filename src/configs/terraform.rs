@@ -0,0 +1,27 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct TerraformConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub workspace: SegmentConfig<'a>,
+    pub check_lock_file: bool,
+    pub lock_file_stale: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for TerraformConfig<'a> {
+    fn new() -> Self {
+        TerraformConfig {
+            symbol: SegmentConfig::new("💠 "),
+            workspace: SegmentConfig::default(),
+            check_lock_file: false,
+            lock_file_stale: SegmentConfig::new(" ⚠"),
+            style: Color::Fixed(105).bold(),
+            disabled: false,
+        }
+    }
+}
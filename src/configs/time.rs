@@ -1,4 +1,4 @@
-use crate::config::{ModuleConfig, RootModuleConfig};
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
 
 use ansi_term::{Color, Style};
 use starship_module_config_derive::ModuleConfig;
@@ -10,6 +10,9 @@ pub struct TimeConfig<'a> {
     pub style: Style,
     pub disabled: bool,
     pub utc_time_offset: &'a str,
+    pub show_time_zone: bool,
+    pub time_zone: SegmentConfig<'a>,
+    pub dst: SegmentConfig<'a>,
 }
 
 impl<'a> RootModuleConfig<'a> for TimeConfig<'a> {
@@ -20,6 +23,9 @@ impl<'a> RootModuleConfig<'a> for TimeConfig<'a> {
             style: Color::Yellow.bold(),
             disabled: true,
             utc_time_offset: "local",
+            show_time_zone: false,
+            time_zone: SegmentConfig::default(),
+            dst: SegmentConfig::new(" (DST)"),
         }
     }
 }
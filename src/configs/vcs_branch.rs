@@ -0,0 +1,25 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct VcsBranchConfig<'a> {
+    pub fossil_symbol: SegmentConfig<'a>,
+    pub svn_symbol: SegmentConfig<'a>,
+    pub branch_name: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for VcsBranchConfig<'a> {
+    fn new() -> Self {
+        VcsBranchConfig {
+            fossil_symbol: SegmentConfig::new("fossil "),
+            svn_symbol: SegmentConfig::new("svn "),
+            branch_name: SegmentConfig::default(),
+            style: Color::Purple.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::config::{ModuleConfig, RootModuleConfig, SegmentConfig};
+
+use ansi_term::{Color, Style};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct WslConfig<'a> {
+    pub symbol: SegmentConfig<'a>,
+    pub distro: SegmentConfig<'a>,
+    pub style: Style,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for WslConfig<'a> {
+    fn new() -> Self {
+        WslConfig {
+            symbol: SegmentConfig::new("WSL "),
+            distro: SegmentConfig::default(),
+            style: Color::Blue.bold(),
+            disabled: false,
+        }
+    }
+}
@@ -1,17 +1,29 @@
-use crate::config::StarshipConfig;
+use crate::command::{self, CommandError, CommandOutput};
+use crate::config::{RootModuleConfig, StarshipConfig};
+use crate::configs::detection::DetectionConfig;
 use crate::module::Module;
+use crate::modules;
+#[cfg(feature = "battery")]
+use crate::modules::battery::{BatteryInfoProvider, BatteryInfoProviderImpl};
 
 use clap::ArgMatches;
 use git2::{Repository, RepositoryState};
 use once_cell::sync::OnceCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::ffi::OsStr;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::string::String;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
 use std::time::{Duration, SystemTime};
 
+/// The output of each command, which modules asking for it at once wait on together
+type CommandCache = Mutex<HashMap<Vec<String>, Arc<OnceCell<Result<CommandOutput, CommandError>>>>>;
+
 /// Context contains data or common methods that may be used by multiple modules.
 /// The data contained within Context will be relevant to this particular rendering
 /// of the prompt.
@@ -30,6 +42,31 @@ pub struct Context<'a> {
 
     /// Private field to store Git information for modules who need it
     repo: OnceCell<Repo>,
+
+    /// The shell the user is assumed to be running
+    pub shell: Shell,
+
+    /// The reasons modules gave for not being rendered, used by `--explain`
+    skip_reasons: Mutex<HashMap<String, SkipReason>>,
+
+    /// Whether scanning `current_dir` was cut short by `scan_timeout`
+    dir_scan_timed_out: AtomicBool,
+
+    /// The module being computed on each thread, which timeouts are attributed to
+    current_modules: Mutex<HashMap<ThreadId, String>>,
+
+    /// The modules whose directory scan or commands were cut short by a timeout
+    timed_out_modules: Mutex<HashSet<String>>,
+
+    /// The modules which ran a command that couldn't be started or exited with an error
+    failed_modules: Mutex<HashSet<String>>,
+
+    /// The output of commands run for this prompt, keyed on the program and its args
+    command_cache: CommandCache,
+
+    /// The source of battery information, which can be replaced in tests
+    #[cfg(feature = "battery")]
+    pub battery_info_provider: &'a (dyn BatteryInfoProvider + Send + Sync),
 }
 
 impl<'a> Context<'a> {
@@ -76,6 +113,15 @@ impl<'a> Context<'a> {
             current_dir,
             dir_files: OnceCell::new(),
             repo: OnceCell::new(),
+            shell: Context::get_shell(),
+            skip_reasons: Mutex::new(HashMap::new()),
+            dir_scan_timed_out: AtomicBool::new(false),
+            current_modules: Mutex::new(HashMap::new()),
+            timed_out_modules: Mutex::new(HashSet::new()),
+            failed_modules: Mutex::new(HashSet::new()),
+            command_cache: Mutex::new(HashMap::new()),
+            #[cfg(feature = "battery")]
+            battery_info_provider: &BatteryInfoProviderImpl,
         }
     }
 
@@ -88,6 +134,12 @@ impl<'a> Context<'a> {
         dir
     }
 
+    /// Identify the shell from `STARSHIP_SHELL`, which is set by the init scripts
+    fn get_shell() -> Shell {
+        let shell = env::var("STARSHIP_SHELL").unwrap_or_default();
+        Shell::from_name(&shell)
+    }
+
     /// Create a new module
     pub fn new_module(&self, name: &str) -> Module {
         let config = self.config.get_module_config(name);
@@ -105,11 +157,142 @@ impl<'a> Context<'a> {
         disabled == Some(true)
     }
 
+    /// Check if the current shell is listed in the `only_on_shells` option of the module.
+    ///
+    /// Modules without the option, or with an empty list, are shown on every shell.
+    pub fn is_module_enabled_for_shell(&self, name: &str) -> bool {
+        let config = self.config.get_module_config(name);
+
+        let only_on_shells =
+            match config.and_then(|table| table.as_table()?.get("only_on_shells")?.as_array()) {
+                Some(only_on_shells) if !only_on_shells.is_empty() => only_on_shells,
+                _ => return true,
+            };
+
+        only_on_shells
+            .iter()
+            .filter_map(toml::Value::as_str)
+            .any(|shell| Shell::from_name(shell) == self.shell)
+    }
+
+    /// Check if the `detect_always` option of the module is true in configuration file,
+    /// and the tool the module shows is installed.
+    ///
+    /// The tool is checked by running `probe`, which should be a command the module
+    /// runs anyway, so that its cached output is reused. Modules without a probe don't
+    /// support the option, and ignore it with a warning.
+    pub fn is_module_detect_always(&self, name: &str, probe: Option<&[&str]>) -> bool {
+        if !DetectionConfig::try_load(self.config.get_module_config(name)).detect_always {
+            return false;
+        }
+
+        match probe {
+            Some([program, args @ ..]) => self.exec_cmd(program, args).is_some(),
+            _ => {
+                log::warn!("The `{}` module doesn't support `detect_always`", name);
+                false
+            }
+        }
+    }
+
+    /// Record why a module wasn't rendered, to be reported by `starship prompt --explain`
+    pub fn set_skip_reason(&self, name: &str, reason: SkipReason) {
+        if let Ok(mut skip_reasons) = self.skip_reasons.lock() {
+            skip_reasons.insert(name.to_string(), reason);
+        }
+    }
+
+    /// Get the reason a module wasn't rendered.
+    ///
+    /// Modules whose directory scan or commands timed out while they were computed by
+    /// `compute_module` report that first, and modules which ran a failing command
+    /// report it unless they recorded another reason. Modules which didn't record a
+    /// reason are assumed to be disabled in the configuration, or otherwise to not
+    /// apply to the current directory.
+    pub fn get_skip_reason(&self, name: &str) -> SkipReason {
+        let timed_out = self
+            .timed_out_modules
+            .lock()
+            .map(|timed_out_modules| timed_out_modules.contains(name))
+            .unwrap_or(false);
+        if timed_out {
+            return SkipReason::TimedOut;
+        }
+
+        let recorded = self
+            .skip_reasons
+            .lock()
+            .ok()
+            .and_then(|skip_reasons| skip_reasons.get(name).copied());
+        let command_failed = self
+            .failed_modules
+            .lock()
+            .map(|failed_modules| failed_modules.contains(name))
+            .unwrap_or(false);
+
+        match recorded {
+            Some(reason) => reason,
+            None if command_failed => SkipReason::CommandFailed,
+            None if self.is_module_disabled_in_config(name) => SkipReason::Disabled,
+            None if !self.is_module_enabled_for_shell(name) => SkipReason::OtherShell,
+            None => SkipReason::NotApplicable,
+        }
+    }
+
+    /// Compute a module, attributing the timeouts hit while doing so to it
+    pub fn compute_module(&'a self, name: &str) -> Option<Module<'a>> {
+        let thread = thread::current().id();
+        if let Ok(mut current_modules) = self.current_modules.lock() {
+            current_modules.insert(thread, name.to_string());
+        }
+
+        let module = modules::handle(name, self);
+
+        if let Ok(mut current_modules) = self.current_modules.lock() {
+            current_modules.remove(&thread);
+        }
+        module
+    }
+
+    /// Record that the module being computed on this thread was cut short by a timeout
+    fn record_timeout(&self) {
+        self.record_current_module(&self.timed_out_modules);
+    }
+
+    /// Record that the module being computed on this thread ran a failing command
+    fn record_command_failure(&self) {
+        self.record_current_module(&self.failed_modules);
+    }
+
+    fn record_current_module(&self, modules: &Mutex<HashSet<String>>) {
+        let thread = thread::current().id();
+        let name = self
+            .current_modules
+            .lock()
+            .ok()
+            .and_then(|current_modules| current_modules.get(&thread).cloned());
+
+        if let (Some(name), Ok(mut modules)) = (name, modules.lock()) {
+            modules.insert(name);
+        }
+    }
+
     // returns a new ScanDir struct with reference to current dir_files of context
     // see ScanDir for methods
-    pub fn try_begin_scan(&'a self) -> Option<ScanDir<'a>> {
+    //
+    // When `detect_always` is set for the module and its `probe` command succeeds,
+    // the directory isn't scanned and every scan matches.
+    pub fn try_begin_scan(&'a self, name: &str, probe: Option<&[&str]>) -> Option<ScanDir<'a>> {
+        let detect_always = self.is_module_detect_always(name, probe);
+        let dir_files: &[PathBuf] = if detect_always {
+            &[]
+        } else {
+            self.get_dir_files().ok()?
+        };
+
         Some(ScanDir {
-            dir_files: self.get_dir_files().ok()?,
+            dir_files,
+            detect_always,
             files: &[],
             folders: &[],
             extensions: &[],
@@ -128,41 +311,157 @@ impl<'a> Context<'a> {
                     .as_ref()
                     .and_then(|repo| repo.workdir().map(Path::to_path_buf));
                 let state = repository.as_ref().map(|repo| repo.state());
+                let remote = repository.as_ref().and_then(get_upstream_remote);
 
                 Ok(Repo {
                     branch,
                     root,
                     state,
+                    remote,
                 })
             })
     }
 
+    /// Execute a command and return its stdout, giving up after `command_timeout`
+    ///
+    /// See `exec_cmd_full` for how the output is cached.
+    pub fn exec_cmd(&self, program: &str, args: &[&str]) -> Option<String> {
+        let output = self.exec_cmd_full(program, args)?;
+        command::successful_stdout(program, args, output)
+    }
+
+    /// Execute a command and return its stdout, stderr and exit status, giving up after
+    /// `command_timeout`
+    ///
+    /// The output is cached, so a command run by several modules is only spawned once
+    /// per prompt. A command which timed out, couldn't be started or exited with an
+    /// error is reported as such for every module which runs it.
+    pub fn exec_cmd_full(&self, program: &str, args: &[&str]) -> Option<CommandOutput> {
+        let key: Vec<String> = std::iter::once(program)
+            .chain(args.iter().copied())
+            .map(str::to_string)
+            .collect();
+        // Modules asking for the same command at once wait on a single run of it
+        let cell = match self.command_cache.lock() {
+            Ok(mut cache) => cache.entry(key).or_default().clone(),
+            Err(_) => Arc::new(OnceCell::new()),
+        };
+        let output = match cell.get() {
+            Some(output) => {
+                log::trace!("Using cached output of `{} {}`", program, args.join(" "));
+                output.clone()
+            }
+            None => cell
+                .get_or_init(|| {
+                    let command_timeout =
+                        Duration::from_millis(self.config.get_root_config().command_timeout);
+                    command::execute_full(program, args, command_timeout)
+                })
+                .clone(),
+        };
+
+        match &output {
+            Err(CommandError::TimedOut) => self.record_timeout(),
+            Err(CommandError::Failed) => self.record_command_failure(),
+            Ok(output) if !output.status.success() => self.record_command_failure(),
+            Ok(_) => {}
+        }
+        output.ok()
+    }
+
+    /// Get the files in `current_dir`, which are only listed once per prompt
+    ///
+    /// When listing them took longer than `scan_timeout`, the list is cut short and
+    /// every module which uses it is reported as timed out.
     pub fn get_dir_files(&self) -> Result<&Vec<PathBuf>, std::io::Error> {
         let start_time = SystemTime::now();
         let scan_timeout = Duration::from_millis(self.config.get_root_config().scan_timeout);
 
-        self.dir_files
-            .get_or_try_init(|| -> Result<Vec<PathBuf>, std::io::Error> {
-                let dir_files = fs::read_dir(&self.current_dir)?
-                    .take_while(|_item| {
-                        SystemTime::now().duration_since(start_time).unwrap() < scan_timeout
-                    })
-                    .filter_map(Result::ok)
-                    .map(|entry| entry.path())
-                    .collect::<Vec<PathBuf>>();
-
-                log::trace!(
-                    "Building a vector of directory files took {:?}",
-                    SystemTime::now().duration_since(start_time).unwrap()
-                );
-                Ok(dir_files)
-            })
+        let dir_files =
+            self.dir_files
+                .get_or_try_init(|| -> Result<Vec<PathBuf>, std::io::Error> {
+                    let dir_files = fs::read_dir(&self.current_dir)?
+                        .take_while(|_item| {
+                            let in_time = SystemTime::now().duration_since(start_time).unwrap()
+                                < scan_timeout;
+                            if !in_time {
+                                self.dir_scan_timed_out.store(true, Ordering::Relaxed);
+                            }
+                            in_time
+                        })
+                        .filter_map(Result::ok)
+                        .map(|entry| entry.path())
+                        .collect::<Vec<PathBuf>>();
+
+                    log::trace!(
+                        "Building a vector of directory files took {:?}",
+                        SystemTime::now().duration_since(start_time).unwrap()
+                    );
+                    Ok(dir_files)
+                })?;
+
+        if self.dir_scan_timed_out.load(Ordering::Relaxed) {
+            self.record_timeout();
+        }
+        Ok(dir_files)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Fish,
+    Powershell,
+    Zsh,
+    Unknown,
+}
+
+impl Shell {
+    /// Get the shell from its name, as used by `starship init`
+    pub fn from_name(name: &str) -> Shell {
+        match name {
+            "bash" => Shell::Bash,
+            "fish" => Shell::Fish,
+            "powershell" => Shell::Powershell,
+            "zsh" => Shell::Zsh,
+            _ => Shell::Unknown,
+        }
+    }
+}
+
+/// The reason a module wasn't rendered
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SkipReason {
+    /// The module doesn't apply to the current directory or environment
+    NotApplicable,
+    /// The module is disabled in the configuration
+    Disabled,
+    /// The module is limited to other shells by `only_on_shells`
+    OtherShell,
+    /// Scanning the current directory took longer than `scan_timeout`, or a command
+    /// took longer than `command_timeout`
+    TimedOut,
+    /// A command the module relies on couldn't be run or failed
+    CommandFailed,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self {
+            SkipReason::NotApplicable => "not applicable",
+            SkipReason::Disabled => "disabled",
+            SkipReason::OtherShell => "not enabled for this shell",
+            SkipReason::TimedOut => "timed out",
+            SkipReason::CommandFailed => "command failed",
+        };
+        write!(f, "{}", reason)
     }
 }
 
 pub struct Repo {
     /// If `current_dir` is a git repository or is contained within one,
-    /// this is the current branch name of that repo.
+    /// this is the current branch name of that repo, or the abbreviated hash
+    /// of the commit HEAD points to when HEAD is detached.
     pub branch: Option<String>,
 
     /// If `current_dir` is a git repository or is contained within one,
@@ -171,12 +470,16 @@ pub struct Repo {
 
     /// State
     pub state: Option<RepositoryState>,
+
+    /// If the current branch tracks an upstream branch, this is the name of its remote.
+    pub remote: Option<String>,
 }
 
 // A struct of Criteria which will be used to verify current PathBuf is
 // of X language, criteria can be set via the builder pattern
 pub struct ScanDir<'a> {
-    dir_files: &'a Vec<PathBuf>,
+    dir_files: &'a [PathBuf],
+    detect_always: bool,
     files: &'a [&'a str],
     folders: &'a [&'a str],
     extensions: &'a [&'a str],
@@ -201,13 +504,14 @@ impl<'a> ScanDir<'a> {
     /// based on the current Pathbuf check to see
     /// if any of this criteria match or exist and returning a boolean
     pub fn is_match(&self) -> bool {
-        self.dir_files.iter().any(|path| {
-            if path.is_dir() {
-                path_has_name(path, self.folders)
-            } else {
-                path_has_name(path, self.files) || has_extension(path, self.extensions)
-            }
-        })
+        self.detect_always
+            || self.dir_files.iter().any(|path| {
+                if path.is_dir() {
+                    path_has_name(path, self.folders)
+                } else {
+                    path_has_name(path, self.files) || has_extension(path, self.extensions)
+                }
+            })
     }
 }
 
@@ -243,13 +547,33 @@ pub fn has_extension<'a>(dir_entry: &PathBuf, extensions: &'a [&'a str]) -> bool
     false
 }
 
+/// Get the name of the current branch, or the abbreviated hash of the commit
+/// HEAD points to when it's detached
 fn get_current_branch(repository: &Repository) -> Option<String> {
     let head = repository.head().ok()?;
+    if !head.is_branch() {
+        let commit = head.peel_to_commit().ok()?;
+        let short_id = commit.as_object().short_id().ok()?;
+        return short_id.as_str().map(std::string::ToString::to_string);
+    }
+
     let shorthand = head.shorthand();
 
     shorthand.map(std::string::ToString::to_string)
 }
 
+/// Get the name of the remote the current branch tracks, which is read from the
+/// repository's config without contacting the remote
+fn get_upstream_remote(repository: &Repository) -> Option<String> {
+    let head = repository.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+
+    let remote = repository.branch_upstream_remote(head.name()?).ok()?;
+    remote.as_str().map(std::string::ToString::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,23 +609,99 @@ mod tests {
         assert_eq!(has_extension(&buf, &extensions), true)
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_exec_cmd_is_cached() {
+        let context = Context::new_with_dir(ArgMatches::default(), "/");
+        let log_dir = tempfile::tempdir().unwrap();
+        let log_file = log_dir.path().join("invocations");
+        let script = format!("echo run >> '{}'; echo hello", log_file.display());
+
+        assert_eq!(
+            context.exec_cmd("sh", &["-c", &script]),
+            Some("hello\n".to_string())
+        );
+        assert_eq!(
+            context.exec_cmd("sh", &["-c", &script]),
+            Some("hello\n".to_string())
+        );
+        let output = context.exec_cmd_full("sh", &["-c", &script]).unwrap();
+        assert_eq!(output.stdout, "hello\n");
+        assert_eq!(fs::read_to_string(&log_file).unwrap(), "run\n");
+
+        // Different arguments aren't served from the cache
+        assert_eq!(
+            context.exec_cmd("sh", &["-c", &format!("{} ", script)]),
+            Some("hello\n".to_string())
+        );
+        assert_eq!(fs::read_to_string(&log_file).unwrap(), "run\nrun\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_exec_cmd_is_run_once_for_concurrent_callers() {
+        let context = Context::new_with_dir(ArgMatches::default(), "/");
+        let log_dir = tempfile::tempdir().unwrap();
+        let log_file = log_dir.path().join("invocations");
+        let script = format!(
+            "echo run >> '{}'; sleep 0.2; echo hello",
+            log_file.display()
+        );
+
+        thread::scope(|scope| {
+            let callers: Vec<_> = (0..2)
+                .map(|_| scope.spawn(|| context.exec_cmd("sh", &["-c", &script])))
+                .collect();
+            for caller in callers {
+                assert_eq!(caller.join().unwrap(), Some("hello\n".to_string()));
+            }
+        });
+        assert_eq!(fs::read_to_string(&log_file).unwrap(), "run\n");
+    }
+
+    #[test]
+    fn test_is_module_enabled_for_shell() {
+        let mut context = Context::new_with_dir(ArgMatches::default(), "/");
+        context.config = StarshipConfig {
+            config: Some(toml::toml! {
+                [character]
+                only_on_shells = ["fish", "zsh"]
+
+                [directory]
+                only_on_shells = []
+            }),
+        };
+
+        context.shell = Shell::Zsh;
+        assert!(context.is_module_enabled_for_shell("character"));
+        assert!(context.is_module_enabled_for_shell("directory"));
+        assert!(context.is_module_enabled_for_shell("time"));
+
+        context.shell = Shell::Bash;
+        assert!(!context.is_module_enabled_for_shell("character"));
+        assert!(context.is_module_enabled_for_shell("directory"));
+        assert!(context.is_module_enabled_for_shell("time"));
+    }
+
     #[test]
     fn test_criteria_scan_fails() {
         let failing_criteria = ScanDir {
-            dir_files: &vec![PathBuf::new()],
+            dir_files: &[PathBuf::new()],
             files: &["package.json"],
             extensions: &["js"],
             folders: &["node_modules"],
+            detect_always: false,
         };
 
         // fails if buffer does not match any criteria
         assert_eq!(failing_criteria.is_match(), false);
 
         let failing_dir_criteria = ScanDir {
-            dir_files: &vec![PathBuf::from("/package.js/dog.go")],
+            dir_files: &[PathBuf::from("/package.js/dog.go")],
             files: &["package.json"],
             extensions: &["js"],
             folders: &["node_modules"],
+            detect_always: false,
         };
 
         // fails when passed a pathbuf dir matches extension path
@@ -311,12 +711,47 @@ mod tests {
     #[test]
     fn test_criteria_scan_passes() {
         let passing_criteria = ScanDir {
-            dir_files: &vec![PathBuf::from("package.json")],
+            dir_files: &[PathBuf::from("package.json")],
             files: &["package.json"],
             extensions: &["js"],
             folders: &["node_modules"],
+            detect_always: false,
         };
 
         assert_eq!(passing_criteria.is_match(), true);
     }
+
+    #[test]
+    fn test_criteria_scan_passes_with_detect_always() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut context = Context::new_with_dir(ArgMatches::default(), dir.path());
+        context.config = StarshipConfig {
+            config: Some(toml::toml! {
+                [golang]
+                detect_always = true
+
+                [rust]
+                detect_always = true
+
+                [helm]
+                detect_always = true
+            }),
+        };
+        let installed: &[&str] = &[env!("CARGO"), "--version"];
+        let missing: &[&str] = &["starship-missing-tool", "--version"];
+
+        let golang_scan = context.try_begin_scan("golang", Some(installed)).unwrap();
+        assert!(golang_scan.set_files(&["go.mod"]).is_match());
+
+        // The tool isn't installed, so the directory is scanned as usual
+        let rust_scan = context.try_begin_scan("rust", Some(missing)).unwrap();
+        assert!(!rust_scan.set_files(&["Cargo.toml"]).is_match());
+
+        // The module doesn't support the option
+        let helm_scan = context.try_begin_scan("helm", None).unwrap();
+        assert!(!helm_scan.set_files(&["Chart.yaml"]).is_match());
+
+        let nodejs_scan = context.try_begin_scan("nodejs", Some(installed)).unwrap();
+        assert!(!nodejs_scan.set_files(&["package.json"]).is_match());
+    }
 }
@@ -7,9 +7,10 @@ function fish_prompt
     end
     set -l exit_code $status
     # Account for changes in variable name between v2.7 and v3.0
-    set -l CMD_DURATION "$CMD_DURATION$cmd_duration"
-    set -l starship_duration (math --scale=0 "$CMD_DURATION / 1000")
-    ::STARSHIP:: prompt --status=$exit_code --keymap=$keymap --cmd-duration=$starship_duration --jobs=(count (jobs -p))
+    set -l starship_duration "$CMD_DURATION$cmd_duration"
+    # $history holds the previous commands, so the next one is one past its length
+    set -l history_number (math (count $history) + 1)
+    ::STARSHIP:: prompt --status=$exit_code --keymap=$keymap --cmd-duration=$starship_duration --jobs=(count (jobs -p)) --history-number=$history_number
 end
 
 # disable virtualenv prompt, it breaks starship
@@ -8,11 +8,13 @@ function global:prompt {
     $jobs = @(Get-Job | Where-Object { $_.State -eq 'Running' }).Count
 
     if ($lastCmd = Get-History -Count 1) {
-        $duration = [math]::Round(($lastCmd.EndExecutionTime - $lastCmd.StartExecutionTime).TotalSeconds)
+        $duration = [math]::Round(($lastCmd.EndExecutionTime - $lastCmd.StartExecutionTime).TotalMilliseconds)
+        # The next command gets the id after the one of the last command
+        $historyNumber = $lastCmd.Id + 1
         # & ensures the path is interpreted as something to execute
-        $out = @(&::STARSHIP:: prompt --status=$lastexitcode --jobs=$jobs --cmd-duration=$duration)
+        $out = @(&::STARSHIP:: prompt --status=$lastexitcode --jobs=$jobs --cmd-duration=$duration --history-number=$historyNumber)
     } else {
-        $out = @(&::STARSHIP:: prompt --status=$lastexitcode --jobs=$jobs)
+        $out = @(&::STARSHIP:: prompt --status=$lastexitcode --jobs=$jobs --history-number=1)
     }
 
     # Convert stdout (array of lines) to expected return type string
@@ -1,9 +1,11 @@
 // Lib is present to allow for benchmarking
+mod command;
 pub mod config;
 pub mod configs;
 pub mod context;
 pub mod module;
 pub mod modules;
 pub mod print;
+mod prompt_cache;
 pub mod segment;
 mod utils;
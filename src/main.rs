@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate clap;
 
+mod command;
 mod config;
 mod configs;
 mod context;
@@ -8,11 +9,13 @@ mod init;
 mod module;
 mod modules;
 mod print;
+mod prompt_cache;
 mod segment;
 mod utils;
 
 use crate::module::ALL_MODULES;
 use clap::{App, AppSettings, Arg, SubCommand};
+use std::time::SystemTime;
 
 fn main() {
     pretty_env_logger::init();
@@ -42,7 +45,7 @@ fn main() {
         .short("d")
         .long("cmd-duration")
         .value_name("CMD_DURATION")
-        .help("The execution duration of the last command, in seconds")
+        .help("The execution duration of the last command, in milliseconds")
         .takes_value(true);
 
     let keymap_arg = Arg::with_name("keymap")
@@ -60,10 +63,25 @@ fn main() {
         .help("The number of currently running jobs")
         .takes_value(true);
 
+    let history_number_arg = Arg::with_name("history_number")
+        .long("history-number")
+        .value_name("HISTORY_NUMBER")
+        .help("The history number of the next command")
+        .takes_value(true);
+
     let init_scripts_arg = Arg::with_name("print_full_init")
         .long("print-full-init")
         .help("Print the main initialization script (as opposed to the init stub)");
 
+    let explain_arg = Arg::with_name("explain")
+        .long("explain")
+        .help("Print to stderr which modules were rendered, and why the others were skipped");
+
+    let refresh_cache_arg = Arg::with_name("refresh_cache")
+        .long("refresh-cache")
+        .hidden(true)
+        .help("Render the prompt without reading the prompt cache, then update the cache");
+
     let matches = App::new("starship")
         .about("The cross-shell prompt for astronauts. ☄🌌️")
         // pull the version number from Cargo.toml
@@ -85,7 +103,10 @@ fn main() {
                 .arg(&path_arg)
                 .arg(&cmd_duration_arg)
                 .arg(&keymap_arg)
-                .arg(&jobs_arg),
+                .arg(&jobs_arg)
+                .arg(&history_number_arg)
+                .arg(&explain_arg)
+                .arg(&refresh_cache_arg),
         )
         .subcommand(
             SubCommand::with_name("module")
@@ -106,7 +127,12 @@ fn main() {
                 .arg(&path_arg)
                 .arg(&cmd_duration_arg)
                 .arg(&keymap_arg)
-                .arg(&jobs_arg),
+                .arg(&jobs_arg)
+                .arg(&history_number_arg),
+        )
+        .subcommand(
+            SubCommand::with_name("time")
+                .about("Prints the time since the Unix epoch in milliseconds, for timing commands in shells without a millisecond clock"),
         )
         .get_matches();
 
@@ -132,6 +158,10 @@ fn main() {
                 print::module(module_name, sub_m.clone());
             }
         }
+        ("time", _) => match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(time) => println!("{}", time.as_millis()),
+            Err(e) => log::warn!("System time is before the Unix epoch: {}", e),
+        },
         _ => {}
     }
 }
@@ -2,39 +2,68 @@ use crate::config::SegmentConfig;
 use crate::segment::Segment;
 use ansi_term::Style;
 use ansi_term::{ANSIString, ANSIStrings};
+use regex::Regex;
 use std::fmt;
 
 // List of all modules
 // Keep these ordered alphabetically.
 // Default ordering is handled in configs/mod.rs
 pub const ALL_MODULES: &[&str] = &[
+    "android",
     "aws",
+    "azure",
     #[cfg(feature = "battery")]
     "battery",
+    "bazel",
+    "bun",
     "character",
     "cmd_duration",
     "conda",
+    "cpp",
+    "crystal",
+    "dir_size",
     "directory",
     "dotnet",
+    "elixir",
     "env_var",
+    "flutter",
     "git_branch",
     "git_state",
     "git_status",
+    "git_tag",
     "golang",
+    "gradle",
+    "helm",
+    "history",
     "hostname",
     "java",
     "jobs",
+    "k8s_pod",
     "kubernetes",
     "line_break",
+    "load",
     "memory_usage",
+    "multiplexer",
+    "nix_flake",
     "nix_shell",
     "nodejs",
+    "ocaml",
+    "os",
     "package",
+    "perl",
+    "podman",
+    "profile",
+    "pulumi",
     "python",
     "ruby",
     "rust",
+    "sandbox",
+    "slurm",
+    "terraform",
     "time",
     "username",
+    "vcs_branch",
+    "wsl",
 ];
 
 /// A module is a collection of segments showing data for a single integration
@@ -57,6 +86,10 @@ pub struct Module<'a> {
 
     /// The suffix used to separate the current module from the next one.
     suffix: Affix,
+
+    /// Regex patterns and their replacements, applied in order to the module's value
+    /// when rendering.
+    substitutions: Vec<(Regex, String)>,
 }
 
 impl<'a> Module<'a> {
@@ -69,6 +102,7 @@ impl<'a> Module<'a> {
             prefix: Affix::default_prefix(name),
             segments: Vec::new(),
             suffix: Affix::default_suffix(name),
+            substitutions: load_substitutions(name, config),
         }
     }
 
@@ -77,6 +111,7 @@ impl<'a> Module<'a> {
         let mut segment = Segment::new(name);
         segment.set_style(segment_config.style.unwrap_or(self.style));
         segment.set_value(segment_config.value);
+        segment.set_hide_when_empty(segment_config.hide_when_empty);
         self.segments.push(segment);
 
         self.segments.last_mut().unwrap()
@@ -116,17 +151,27 @@ impl<'a> Module<'a> {
     /// Returns a vector of colored ANSIString elements to be later used with
     /// `ANSIStrings()` to optimize ANSI codes
     pub fn ansi_strings(&self) -> Vec<ANSIString> {
-        let shell = std::env::var("STARSHIP_SHELL").unwrap_or_default();
-        let ansi_strings = self
-            .segments
-            .iter()
-            .map(Segment::ansi_string)
-            .collect::<Vec<ANSIString>>();
-
-        let mut ansi_strings = match shell.as_str() {
-            "bash" => ansi_strings_modified(ansi_strings, shell),
-            "zsh" => ansi_strings_modified(ansi_strings, shell),
-            _ => ansi_strings,
+        let segments = self.segments.iter().filter(|segment| !segment.is_hidden());
+
+        let mut ansi_strings = if self.substitutions.is_empty() {
+            segments
+                .map(Segment::ansi_string)
+                .collect::<Vec<ANSIString>>()
+        } else {
+            let runs = segments
+                .map(|segment| (segment.get_value().to_string(), segment.get_style()))
+                .collect();
+            self.substitutions
+                .iter()
+                .fold(runs, |runs, (pattern, replacement)| {
+                    substitute(runs, pattern, replacement)
+                })
+                .into_iter()
+                .map(|(value, style)| match style {
+                    Some(style) => style.paint(value),
+                    None => ANSIString::from(value),
+                })
+                .collect()
         };
 
         ansi_strings.insert(0, self.prefix.ansi_string());
@@ -138,6 +183,12 @@ impl<'a> Module<'a> {
     pub fn to_string_without_prefix(&self) -> String {
         ANSIStrings(&self.ansi_strings()[1..]).to_string()
     }
+
+    /// Render only the segments of the module, without its prefix or suffix
+    pub fn to_string_without_affixes(&self) -> String {
+        let ansi_strings = self.ansi_strings();
+        ANSIStrings(&ansi_strings[1..ansi_strings.len() - 1]).to_string()
+    }
 }
 
 impl<'a> fmt::Display for Module<'a> {
@@ -147,46 +198,115 @@ impl<'a> fmt::Display for Module<'a> {
     }
 }
 
-/// Many shells cannot deal with raw unprintable characters (like ANSI escape sequences) and
-/// miscompute the cursor position as a result, leading to strange visual bugs. Here, we wrap these
-/// characters in shell-specific escape codes to indicate to the shell that they are zero-length.
-fn ansi_strings_modified(ansi_strings: Vec<ANSIString>, shell: String) -> Vec<ANSIString> {
-    const ESCAPE_BEGIN: char = '\u{1b}';
-    const MAYBE_ESCAPE_END: char = 'm';
-    ansi_strings
+/// Text made up of differently styled runs, such as the values of a module's segments
+type StyledRuns = Vec<(String, Option<Style>)>;
+
+/// Replace every match of `pattern` in the text of `runs`, which may span several runs
+///
+/// The text around the matches keeps its style, and each replacement takes the style
+/// of the run its match starts in.
+fn substitute(runs: StyledRuns, pattern: &Regex, replacement: &str) -> StyledRuns {
+    let text = runs
         .iter()
-        .map(|ansi| {
-            let mut escaped = false;
-            let final_string: String = ansi
-                .to_string()
-                .chars()
-                .map(|x| match x {
-                    ESCAPE_BEGIN => {
-                        escaped = true;
-                        match shell.as_str() {
-                            "bash" => String::from("\u{5c}\u{5b}\u{1b}"), // => \[ESC
-                            "zsh" => String::from("\u{25}\u{7b}\u{1b}"),  // => %{ESC
-                            _ => x.to_string(),
-                        }
-                    }
-                    MAYBE_ESCAPE_END => {
-                        if escaped {
-                            escaped = false;
-                            match shell.as_str() {
-                                "bash" => String::from("m\u{5c}\u{5d}"), // => m\]
-                                "zsh" => String::from("m\u{25}\u{7d}"),  // => m%}
-                                _ => x.to_string(),
-                            }
-                        } else {
-                            x.to_string()
-                        }
-                    }
-                    _ => x.to_string(),
-                })
-                .collect();
-            ANSIString::from(final_string)
+        .map(|(value, _)| value.as_str())
+        .collect::<String>();
+    if !pattern.is_match(&text) {
+        return runs;
+    }
+
+    // The byte range each run covers in `text`
+    let ranges = runs
+        .iter()
+        .scan(0, |start, (value, style)| {
+            let range = (*start, *start + value.len(), *style);
+            *start += value.len();
+            Some(range)
         })
-        .collect::<Vec<ANSIString>>()
+        .collect::<Vec<_>>();
+    let copy_text = |substituted: &mut StyledRuns, from: usize, to: usize| {
+        for &(start, end, style) in &ranges {
+            let (start, end) = (start.max(from), end.min(to));
+            if start < end {
+                substituted.push((text[start..end].to_string(), style));
+            }
+        }
+    };
+    let style_at = |offset: usize| {
+        ranges
+            .iter()
+            .find(|(start, end, _)| (*start..*end).contains(&offset))
+            .or_else(|| ranges.last())
+            .and_then(|(_, _, style)| *style)
+    };
+
+    let mut substituted = Vec::new();
+    let mut copied_to = 0;
+    for captures in pattern.captures_iter(&text) {
+        let matched = captures.get(0).unwrap();
+        copy_text(&mut substituted, copied_to, matched.start());
+
+        let mut value = String::new();
+        captures.expand(replacement, &mut value);
+        substituted.push((value, style_at(matched.start())));
+        copied_to = matched.end();
+    }
+    copy_text(&mut substituted, copied_to, text.len());
+
+    substituted
+}
+
+/// Compile the `substitutions` array of a module's configuration, keeping the order
+/// they're written in
+///
+/// Substitutions which aren't a table of a valid regex `pattern` and a string
+/// `replacement` are skipped with a warning.
+fn load_substitutions(name: &str, config: Option<&toml::Value>) -> Vec<(Regex, String)> {
+    let substitutions = match config.and_then(|table| table.as_table()?.get("substitutions")) {
+        Some(substitutions) => substitutions,
+        None => return Vec::new(),
+    };
+
+    let substitutions = match substitutions.as_array() {
+        Some(substitutions) => substitutions,
+        None => {
+            log::warn!(
+                "Expected an array of {{ pattern, replacement }} tables for {}.substitutions",
+                name
+            );
+            return Vec::new();
+        }
+    };
+
+    substitutions
+        .iter()
+        .filter_map(|substitution| {
+            let get_str = |key: &str| substitution.get(key).and_then(toml::Value::as_str);
+            let (pattern, replacement) = match (get_str("pattern"), get_str("replacement")) {
+                (Some(pattern), Some(replacement)) => (pattern, replacement),
+                _ => {
+                    log::warn!(
+                        "Skipping substitution {} in {}: expected a string pattern and replacement",
+                        substitution,
+                        name
+                    );
+                    return None;
+                }
+            };
+
+            match Regex::new(pattern) {
+                Ok(regex) => Some((regex, replacement.to_string())),
+                Err(e) => {
+                    log::warn!(
+                        "Skipping invalid substitution pattern {:?} in {}: {}",
+                        pattern,
+                        name,
+                        e
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
 }
 
 /// Module affixes are to be used for the prefix or suffix of a module.
@@ -253,6 +373,7 @@ impl fmt::Display for Affix {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ansi_term::Color;
 
     #[test]
     fn test_module_is_empty_with_no_segments() {
@@ -264,6 +385,7 @@ mod tests {
             prefix: Affix::default_prefix(name),
             segments: Vec::new(),
             suffix: Affix::default_suffix(name),
+            substitutions: Vec::new(),
         };
 
         assert!(module.is_empty());
@@ -279,8 +401,95 @@ mod tests {
             prefix: Affix::default_prefix(name),
             segments: vec![Segment::new("test_segment")],
             suffix: Affix::default_suffix(name),
+            substitutions: Vec::new(),
         };
 
         assert!(module.is_empty());
     }
+
+    #[test]
+    fn test_module_hides_empty_segments() {
+        let mut module = Module::new("unit_test", None);
+        module.set_style(Color::Red.bold());
+        module.get_prefix().set_value("");
+        module.create_segment("symbol", &SegmentConfig::new("S "));
+        module.create_segment(
+            "version",
+            &SegmentConfig::new("").with_hide_when_empty(true),
+        );
+
+        assert_eq!(
+            module.to_string(),
+            format!("{} ", Color::Red.bold().paint("S "))
+        );
+    }
+
+    #[test]
+    fn test_module_shows_non_empty_hidable_segments() {
+        let mut module = Module::new("unit_test", None);
+        module.get_prefix().set_value("");
+        module.create_segment("symbol", &SegmentConfig::new("S "));
+        module.create_segment(
+            "version",
+            &SegmentConfig::new("v1.0.0").with_hide_when_empty(true),
+        );
+
+        assert_eq!(module.to_string(), "S v1.0.0 ");
+    }
+
+    #[test]
+    fn test_module_applies_substitutions() {
+        let config = toml::toml! {
+            substitutions = [
+                { pattern = "^S v", replacement = "S " },
+                { pattern = "(\\d+)\\.(\\d+)\\.\\d+", replacement = "$1.$2" },
+                { pattern = "[invalid", replacement = "skipped" },
+                { pattern = "missing replacement" },
+            ]
+        };
+        let mut module = Module::new("unit_test", Some(&config));
+        module.get_prefix().set_value("");
+        module.create_segment("symbol", &SegmentConfig::new("S "));
+        module.create_segment("version", &SegmentConfig::new("v1.20.3"));
+
+        assert_eq!(module.to_string(), "S 1.20 ");
+    }
+
+    #[test]
+    fn test_module_applies_substitutions_in_order() {
+        let config = toml::toml! {
+            substitutions = [
+                { pattern = "b", replacement = "c" },
+                { pattern = "a", replacement = "b" },
+            ]
+        };
+        let mut module = Module::new("unit_test", Some(&config));
+        module.get_prefix().set_value("");
+        module.create_segment("value", &SegmentConfig::new("ab"));
+
+        assert_eq!(module.to_string(), "bc ");
+    }
+
+    #[test]
+    fn test_module_substitutions_keep_segment_styles() {
+        let config = toml::toml! {
+            substitutions = [{ pattern = "on (\\w)", replacement = "@$1" }]
+        };
+        let mut module = Module::new("unit_test", Some(&config));
+        module.get_prefix().set_value("");
+        module.create_segment("prefix", &SegmentConfig::new("on "));
+        module.create_segment(
+            "branch",
+            &SegmentConfig::new("master").with_style(Some(Color::Purple.bold())),
+        );
+
+        // The match spans both segments, and is styled like the segment it starts in
+        assert_eq!(
+            module.to_string(),
+            format!(
+                "{} ",
+                ANSIStrings(&[ANSIString::from("@m"), Color::Purple.bold().paint("aster")])
+            )
+        );
+    }
 }
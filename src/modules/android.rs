@@ -0,0 +1,138 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::android::AndroidConfig;
+use crate::utils;
+
+const GRADLE_FILES: &[&str] = &[
+    "build.gradle",
+    "build.gradle.kts",
+    "app/build.gradle",
+    "app/build.gradle.kts",
+];
+
+/// Creates a module with the Android SDK version targeted by the current project
+///
+/// Will display the Android SDK version if any of the following criteria are met:
+///     - Current directory contains an `AndroidManifest.xml` file
+///     - Current directory, or its `app` directory, contains a `build.gradle` or
+///       `build.gradle.kts` file which applies an Android Gradle plugin
+///
+/// The `compileSdkVersion` is shown by default, or the `targetSdkVersion` when
+/// `show_target_sdk` is set.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let has_manifest = context
+        .try_begin_scan("android", None)?
+        .set_files(&["AndroidManifest.xml"])
+        .is_match();
+
+    let build_gradle = GRADLE_FILES
+        .iter()
+        .filter_map(|file| utils::read_file(context.current_dir.join(file)).ok())
+        .find(|contents| is_android_gradle_file(contents));
+
+    if !has_manifest && build_gradle.is_none() {
+        return None;
+    }
+
+    let mut module = context.new_module("android");
+    let config: AndroidConfig = AndroidConfig::try_load(module.config);
+
+    let sdk_keys: &[&str] = if config.show_target_sdk {
+        &["targetSdkVersion", "targetSdk"]
+    } else {
+        &["compileSdkVersion", "compileSdk"]
+    };
+    let sdk_version = parse_sdk_version(&build_gradle?, sdk_keys)?;
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment(
+        "version",
+        &config.version.with_value(&format!("API {}", sdk_version)),
+    );
+
+    Some(module)
+}
+
+fn is_android_gradle_file(build_gradle: &str) -> bool {
+    build_gradle.contains("com.android.") || build_gradle.contains("android {")
+}
+
+/// Find the SDK version assigned to any of `keys` in a Groovy or Kotlin Gradle script
+///
+/// Handles the forms `compileSdkVersion 29`, `compileSdkVersion(29)`,
+/// `compileSdk = 29` and `compileSdkVersion "android-29"`.
+fn parse_sdk_version(build_gradle: &str, keys: &[&str]) -> Option<String> {
+    build_gradle.lines().map(str::trim).find_map(|line| {
+        keys.iter().find_map(|key| {
+            if !line.starts_with(key) {
+                return None;
+            }
+
+            let value = &line[key.len()..];
+            // Skip longer keys sharing the same prefix, e.g. `compileSdkVersion` for `compileSdk`
+            if value.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+                return None;
+            }
+
+            let version = value
+                .trim_matches(|c: char| " =()\"'".contains(c))
+                .trim_start_matches("android-");
+            if version.is_empty() || !version.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+
+            Some(version.to_string())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPILE_SDK_KEYS: &[&str] = &["compileSdkVersion", "compileSdk"];
+    const TARGET_SDK_KEYS: &[&str] = &["targetSdkVersion", "targetSdk"];
+
+    #[test]
+    fn test_parse_groovy_sdk_version() {
+        let build_gradle = "android {\n    compileSdkVersion 29\n    defaultConfig {\n        minSdkVersion 21\n        targetSdkVersion 28\n    }\n}\n";
+        assert_eq!(
+            parse_sdk_version(build_gradle, COMPILE_SDK_KEYS),
+            Some("29".to_string())
+        );
+        assert_eq!(
+            parse_sdk_version(build_gradle, TARGET_SDK_KEYS),
+            Some("28".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_kotlin_sdk_version() {
+        let build_gradle = "android {\n    compileSdk = 33\n    defaultConfig {\n        targetSdkVersion(31)\n    }\n}\n";
+        assert_eq!(
+            parse_sdk_version(build_gradle, COMPILE_SDK_KEYS),
+            Some("33".to_string())
+        );
+        assert_eq!(
+            parse_sdk_version(build_gradle, TARGET_SDK_KEYS),
+            Some("31".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_platform_sdk_version() {
+        let build_gradle = "android {\n    compileSdkVersion \"android-30\"\n}\n";
+        assert_eq!(
+            parse_sdk_version(build_gradle, COMPILE_SDK_KEYS),
+            Some("30".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_sdk_version() {
+        let build_gradle = "android {\n    compileSdkVersion rootProject.ext.compileSdk\n}\n";
+        assert_eq!(parse_sdk_version(build_gradle, COMPILE_SDK_KEYS), None);
+        assert_eq!(parse_sdk_version(build_gradle, TARGET_SDK_KEYS), None);
+    }
+}
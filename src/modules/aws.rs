@@ -1,14 +1,17 @@
 use std::env;
-use std::fs::File;
+use std::ffi::OsStr;
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
 use std::str::FromStr;
 
+use chrono::{DateTime, Utc};
 use dirs::home_dir;
 
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::aws::{AwsConfig, AwsItems};
+use crate::utils;
 
 type Profile = String;
 type Region = String;
@@ -111,5 +114,84 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         }
     };
 
+    if config.show_sso_expiry {
+        if let Some(expires_at) = get_sso_expiry() {
+            let remaining_seconds = (expires_at - Utc::now()).num_seconds();
+            if remaining_seconds > 0 {
+                let remaining = format!(" [{}]", format_time_remaining(remaining_seconds));
+                module.create_segment("sso_expiry", &config.sso_expiry.with_value(&remaining));
+            } else {
+                module.create_segment("sso_expired", &config.sso_expired);
+            }
+        }
+    }
+
     Some(module)
 }
+
+/// Get when the most recent AWS SSO session expires, from the token cache of the AWS CLI
+fn get_sso_expiry() -> Option<DateTime<Utc>> {
+    let cache_dir = home_dir()?.join(".aws/sso/cache");
+
+    fs::read_dir(cache_dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension() == Some(OsStr::new("json")))
+        .filter_map(|path| utils::read_file(path).ok())
+        .filter_map(|json| parse_sso_expiry(&json))
+        .max()
+}
+
+fn parse_sso_expiry(json: &str) -> Option<DateTime<Utc>> {
+    let cached: serde_json::Value = serde_json::from_str(json).ok()?;
+
+    // The cache also holds client registrations, which expire but aren't sessions
+    cached.get("accessToken")?;
+    let expires_at = cached.get("expiresAt")?.as_str()?;
+
+    // Older versions of the AWS CLI write the time zone as "UTC" rather than "Z"
+    let expires_at = expires_at.replace("UTC", "Z");
+    DateTime::parse_from_rfc3339(&expires_at)
+        .ok()
+        .map(|expires_at| expires_at.with_timezone(&Utc))
+}
+
+fn format_time_remaining(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    match minutes / 60 {
+        0 => format!("{}m", minutes),
+        hours => format!("{}h{}m", hours, minutes % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sso_expiry() {
+        let token = r#"{"startUrl": "https://starship.awsapps.com/start", "region": "us-east-1", "accessToken": "secret", "expiresAt": "2020-02-14T04:05:45Z"}"#;
+        assert_eq!(
+            parse_sso_expiry(token).map(|expires_at| expires_at.to_rfc3339()),
+            Some("2020-02-14T04:05:45+00:00".to_string())
+        );
+
+        let token = r#"{"accessToken": "secret", "expiresAt": "2020-02-14T04:05:45UTC"}"#;
+        assert_eq!(
+            parse_sso_expiry(token).map(|expires_at| expires_at.to_rfc3339()),
+            Some("2020-02-14T04:05:45+00:00".to_string())
+        );
+
+        let registration =
+            r#"{"clientId": "id", "clientSecret": "secret", "expiresAt": "2020-05-14T04:05:45Z"}"#;
+        assert_eq!(parse_sso_expiry(registration), None);
+    }
+
+    #[test]
+    fn test_format_time_remaining() {
+        assert_eq!(format_time_remaining(59), "0m");
+        assert_eq!(format_time_remaining(45 * 60), "45m");
+        assert_eq!(format_time_remaining(2 * 3600 + 5 * 60 + 30), "2h5m");
+    }
+}
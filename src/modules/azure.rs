@@ -0,0 +1,93 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+use super::{Context, Module, RootModuleConfig, SkipReason};
+
+use crate::configs::azure::AzureConfig;
+use crate::utils;
+
+type JValue = serde_json::Value;
+
+const AZURE_PROFILE_FILE: &str = "azureProfile.json";
+
+/// Creates a module with the default Azure subscription
+///
+/// The subscription is read from the `azureProfile.json` file kept by the Azure
+/// CLI in `~/.azure`, or in `$AZURE_CONFIG_DIR` when it is set. Long subscription
+/// names can be shortened with `subscription_aliases`.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("azure");
+    let config: AzureConfig = AzureConfig::try_load(module.config);
+
+    if config.disabled {
+        context.set_skip_reason("azure", SkipReason::Disabled);
+        return None;
+    }
+
+    let subscription = get_default_subscription(&get_azure_config_dir()?)?;
+    let subscription = config
+        .subscription_aliases
+        .get(subscription.as_str())
+        .map(|alias| (*alias).to_string())
+        .unwrap_or(subscription);
+
+    module.set_style(config.style);
+    module.get_prefix().set_value("on ");
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment(
+        "subscription",
+        &config.subscription.with_value(&subscription),
+    );
+
+    Some(module)
+}
+
+fn get_azure_config_dir() -> Option<PathBuf> {
+    match env::var("AZURE_CONFIG_DIR") {
+        Ok(config_dir) if !config_dir.is_empty() => Some(PathBuf::from(config_dir)),
+        _ => dirs::home_dir().map(|home_dir| home_dir.join(".azure")),
+    }
+}
+
+fn get_default_subscription(config_dir: &Path) -> Option<String> {
+    let profile = utils::read_file(config_dir.join(AZURE_PROFILE_FILE)).ok()?;
+    parse_default_subscription(&profile)
+}
+
+/// Find the name of the subscription flagged `isDefault` in an Azure profile
+fn parse_default_subscription(profile: &str) -> Option<String> {
+    // The Azure CLI writes the profile with a byte order mark
+    let profile: JValue = serde_json::from_str(profile.trim_start_matches('\u{feff}')).ok()?;
+
+    profile
+        .get("subscriptions")?
+        .as_array()?
+        .iter()
+        .find(|subscription| subscription.get("isDefault") == Some(&JValue::Bool(true)))?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_default_subscription() {
+        let profile = "\u{feff}{\"subscriptions\": [\
+            {\"name\": \"Development\", \"isDefault\": false},\
+            {\"name\": \"Production\", \"isDefault\": true}\
+        ]}";
+        assert_eq!(
+            parse_default_subscription(profile),
+            Some("Production".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_default_subscription() {
+        assert_eq!(parse_default_subscription("{\"subscriptions\": []}"), None);
+        assert_eq!(parse_default_subscription(""), None);
+    }
+}
@@ -1,102 +1,1550 @@
-use super::{Context, Module, RootModuleConfig};
-use crate::configs::battery::BatteryConfig;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Local};
+
+use super::{Context, Module, RootModuleConfig, Shell};
+use crate::configs::battery::{BatteryConfig, BatteryDisplayConfig};
+use crate::utils;
+
+/// The file in the user's cache directory the last battery reading is cached in,
+/// shared by every shell
+const CACHE_FILE: &str = "battery";
+
+/// The file in the user's cache directory the last reading of each battery is cached in
+/// when `aggregate` is disabled
+const BATTERIES_CACHE_FILE: &str = "batteries";
+
+/// The file in the user's cache directory the last chosen display is kept in when
+/// `hysteresis` is set
+const DISPLAY_FILE: &str = "battery-display";
 
 /// Creates a module for the battery percentage and charging state
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     // TODO: Update when v1.0 printing refactor is implemented to only
     // print escapes in a prompt context.
-    let shell = std::env::var("STARSHIP_SHELL").unwrap_or_default();
-    let percentage_char = match shell.as_str() {
-        "zsh" => "%%", // % is an escape in zsh, see PROMPT in `man zshmisc`
-        "powershell" => "`%",
+    let percentage_char = match context.shell {
+        Shell::Zsh => "%%", // % is an escape in zsh, see PROMPT in `man zshmisc`
+        Shell::Powershell => "`%",
         _ => "%",
     };
 
-    let battery_status = get_battery_status()?;
-    let BatteryStatus { state, percentage } = battery_status;
-
     let mut module = context.new_module("battery");
     let battery_config: BatteryConfig = BatteryConfig::try_load(module.config);
 
-    // Parse config under `display`
+    let cache_dir = if battery_config.cache_duration > 0 || battery_config.hysteresis > 0.0 {
+        utils::get_cache_dir()
+    } else {
+        None
+    };
+
+    let cache_duration = Duration::from_secs(battery_config.cache_duration);
+    let battery_status = match (&cache_dir, battery_config.cache_duration) {
+        (Some(cache_dir), secs) if secs > 0 => get_cached_battery_status(
+            context.battery_info_provider,
+            &cache_dir.join(CACHE_FILE),
+            cache_duration,
+            SystemTime::now(),
+        ),
+        _ => context.battery_info_provider.get_battery_status(),
+    }?;
+
+    // Parse config under `display`, using the combined charge of every battery
     let display_styles = &battery_config.display;
-    let display_style = display_styles
-        .iter()
-        .find(|display_style| percentage <= display_style.threshold as f32);
+    let hysteresis = battery_config.hysteresis as f32;
+    let display_file = match &cache_dir {
+        Some(cache_dir) if hysteresis > 0.0 => Some(cache_dir.join(DISPLAY_FILE)),
+        _ => None,
+    };
+    let previous_display = display_file.as_deref().and_then(read_previous_display);
+    let display = select_display(
+        display_styles,
+        battery_status.percentage,
+        hysteresis,
+        previous_display,
+    );
+    if let Some(display_file) = &display_file {
+        write_previous_display(display_file, display);
+    }
+    let display_style = &display_styles[display?];
 
-    if let Some(display_style) = display_style {
-        // Set style based on percentage
-        module.set_style(display_style.style);
-        module.get_prefix().set_value("");
+    // Set style based on percentage
+    module.set_style(display_style.style);
+    module.get_prefix().set_value("");
 
-        match state {
-            battery::State::Full => {
-                module.create_segment("full_symbol", &battery_config.full_symbol);
+    if battery_config.display_ac {
+        match is_on_ac_power(battery_status.state) {
+            Some(true) => {
+                module.create_segment("ac_symbol", &battery_config.ac_symbol);
             }
-            battery::State::Charging => {
-                module.create_segment("charging_symbol", &battery_config.charging_symbol);
+            Some(false) => {
+                module.create_segment("battery_symbol", &battery_config.battery_symbol);
             }
-            battery::State::Discharging => {
-                module.create_segment("discharging_symbol", &battery_config.discharging_symbol);
+            None => {}
+        }
+    }
+
+    if battery_config.aggregate {
+        add_status_segments(
+            &mut module,
+            &battery_config,
+            &battery_status,
+            percentage_char,
+        )?;
+    } else {
+        let provider = context.battery_info_provider;
+        let batteries = match (&cache_dir, battery_config.cache_duration) {
+            (Some(cache_dir), secs) if secs > 0 => get_cached_battery_statuses(
+                &cache_dir.join(BATTERIES_CACHE_FILE),
+                cache_duration,
+                SystemTime::now(),
+                || provider.get_battery_statuses(),
+            ),
+            _ => provider.get_battery_statuses(),
+        };
+
+        let mut is_first = true;
+        for (index, status) in batteries.iter().enumerate() {
+            // A battery in a state which can't be displayed is left out, not the whole module
+            if !is_displayable_state(status.state) {
+                log::debug!(
+                    "Skipping battery {} in unhandled state `{}`",
+                    index + 1,
+                    status.state
+                );
+                continue;
             }
-            battery::State::Unknown => {
-                log::debug!("Unknown detected");
-                if let Some(unknown_symbol) = battery_config.unknown_symbol {
-                    module.create_segment("unknown_symbol", &unknown_symbol);
-                }
+
+            if !is_first {
+                module.create_segment("separator", &battery_config.separator);
             }
-            battery::State::Empty => {
-                if let Some(empty_symbol) = battery_config.empty_symbol {
-                    module.create_segment("empty_symbol", &empty_symbol);
-                }
+            is_first = false;
+            let label = format!("{}:", index + 1);
+            module.create_segment("index", &battery_config.index.with_value(&label));
+            add_status_segments(&mut module, &battery_config, status, percentage_char)?;
+        }
+    }
+
+    Some(module)
+}
+
+/// Whether a battery state has a symbol to display it with
+fn is_displayable_state(state: battery::State) -> bool {
+    matches!(
+        state,
+        battery::State::Full
+            | battery::State::Charging
+            | battery::State::Discharging
+            | battery::State::Unknown
+            | battery::State::Empty
+    )
+}
+
+/// Add the segments describing a single battery status, returning `None` if the
+/// battery is in a state which can't be displayed
+fn add_status_segments(
+    module: &mut Module,
+    battery_config: &BatteryConfig,
+    battery_status: &BatteryStatus,
+    percentage_char: &str,
+) -> Option<()> {
+    let BatteryStatus {
+        state,
+        percentage,
+        temperature,
+        health,
+        time_to_full,
+        time_to_empty,
+    } = *battery_status;
+
+    match state {
+        battery::State::Full => {
+            module.create_segment("full_symbol", &battery_config.full_symbol);
+        }
+        battery::State::Charging => {
+            let charging_symbol =
+                match select_frame(&battery_config.charging_frames, SystemTime::now()) {
+                    Some(frame) => battery_config.charging_symbol.with_value(frame),
+                    None => battery_config.charging_symbol.clone(),
+                };
+            module.create_segment("charging_symbol", &charging_symbol);
+        }
+        battery::State::Discharging => {
+            module.create_segment("discharging_symbol", &battery_config.discharging_symbol);
+        }
+        battery::State::Unknown => {
+            log::debug!("Unknown detected");
+            if let Some(unknown_symbol) = &battery_config.unknown_symbol {
+                module.create_segment("unknown_symbol", unknown_symbol);
             }
-            _ => {
-                log::debug!("Unhandled battery state `{}`", state);
-                return None;
+        }
+        battery::State::Empty => {
+            if let Some(empty_symbol) = &battery_config.empty_symbol {
+                module.create_segment("empty_symbol", empty_symbol);
             }
         }
+        _ => {
+            log::debug!("Unhandled battery state `{}`", state);
+            return None;
+        }
+    }
 
-        let mut percent_string = Vec::<String>::with_capacity(2);
-        // Round the percentage to a whole number
-        percent_string.push(percentage.round().to_string());
-        percent_string.push(percentage_char.to_string());
-        module.create_segment(
-            "percentage",
-            &battery_config
-                .percentage
-                .with_value(percent_string.join("").as_ref()),
+    let percent_string = format!(
+        "{}{}",
+        format_percentage(percentage, battery_config.precision),
+        percentage_char
+    );
+    module.create_segment(
+        "percentage",
+        &battery_config.percentage.with_value(&percent_string),
+    );
+
+    // The estimate depends on whether the battery is charging or discharging, and
+    // isn't always available
+    let time_remaining = match state {
+        battery::State::Charging => time_to_full,
+        battery::State::Discharging => time_to_empty,
+        _ => None,
+    };
+    if let (true, Some(time_remaining)) = (battery_config.display_time, time_remaining) {
+        let time_string = format!(" {}", format_time_remaining(time_remaining));
+        module.create_segment("time", &battery_config.time.with_value(&time_string));
+    }
+
+    // The temperature is only reported on some platforms, so skip it when unavailable
+    if let (true, Some(temperature)) = (battery_config.display_temperature, temperature) {
+        let temperature_string = format!(" {}", format_temperature(temperature));
+        let mut temperature_config = battery_config.temperature.with_value(&temperature_string);
+        if let Some(high_temperature_style) = battery_config.high_temperature_style {
+            if temperature >= battery_config.high_temperature_threshold as f32 {
+                temperature_config = temperature_config.with_style(Some(high_temperature_style));
+            }
+        }
+        module.create_segment("temperature", &temperature_config);
+    }
+
+    // The estimate is only meaningful while charging, and isn't always available
+    if let (true, battery::State::Charging, Some(time_to_full)) =
+        (battery_config.display_full_at, state, time_to_full)
+    {
+        if let Some(full_at) =
+            format_full_at(time_to_full, Local::now(), battery_config.full_at_format)
+        {
+            module.create_segment(
+                "full_at",
+                &battery_config.full_at.with_value(&format!(" {}", full_at)),
+            );
+        }
+    }
+
+    if battery_config.display_alert
+        && needs_alert(
+            health,
+            temperature,
+            battery_config.alert_health_threshold as f32,
+            battery_config.alert_temperature_threshold as f32,
+        )
+    {
+        module.create_segment("alert", &battery_config.alert);
+    }
+
+    Some(())
+}
+
+/// Work out whether the device is plugged in from the combined state of its batteries,
+/// which is charging or full while on AC power, or `None` if the state doesn't tell
+fn is_on_ac_power(state: battery::State) -> Option<bool> {
+    match state {
+        battery::State::Charging | battery::State::Full => Some(true),
+        battery::State::Discharging | battery::State::Empty => Some(false),
+        _ => None,
+    }
+}
+
+/// Choose the index of the display for the battery's charge
+///
+/// The display chosen for the previous prompt is kept until the charge rises more than
+/// `hysteresis` past its threshold, so the style doesn't flicker while the charge
+/// hovers at a threshold.
+fn select_display(
+    displays: &[BatteryDisplayConfig],
+    percentage: f32,
+    hysteresis: f32,
+    previous: Option<usize>,
+) -> Option<usize> {
+    displays.iter().enumerate().position(|(index, display)| {
+        let threshold = display.threshold as f32;
+        if previous == Some(index) {
+            percentage <= threshold + hysteresis
+        } else {
+            percentage <= threshold
+        }
+    })
+}
+
+/// Read the index of the display chosen for the previous prompt, if one was shown
+fn read_previous_display(display_file: &Path) -> Option<usize> {
+    utils::read_file(display_file).ok()?.trim().parse().ok()
+}
+
+/// Remember the index of the chosen display, which is left empty when none was shown
+fn write_previous_display(display_file: &Path, display: Option<usize>) {
+    let contents = display.map(|index| index.to_string()).unwrap_or_default();
+    if let Err(e) = utils::write_file_atomically(display_file, &contents) {
+        log::debug!(
+            "Unable to write the battery display {:?}: {}",
+            display_file,
+            e
         );
+    }
+}
+
+/// Pick the frame of the charging animation to show at `now`, advancing once per second
+fn select_frame<'a>(frames: &[&'a str], now: SystemTime) -> Option<&'a str> {
+    if frames.is_empty() {
+        return None;
+    }
 
-        Some(module)
+    let now_secs = now.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(frames[(now_secs % frames.len() as u64) as usize])
+}
+
+/// Format the charge with `precision` decimal places
+fn format_percentage(percentage: f32, precision: u64) -> String {
+    if precision == 0 {
+        // Round the percentage to a whole number
+        percentage.round().to_string()
     } else {
-        None
+        format!("{:.*}", precision as usize, percentage)
+    }
+}
+
+/// Format a temperature in degrees Celsius with one decimal place
+fn format_temperature(celsius: f32) -> String {
+    format!("{:.1}°C", celsius)
+}
+
+/// Format the time until the battery is full or empty in hours and minutes, e.g. `1h20m`
+fn format_time_remaining(time_remaining: Duration) -> String {
+    let minutes = time_remaining.as_secs() / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "<1m".to_string(),
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h{}m", hours, minutes),
+    }
+}
+
+/// Format the clock time at which charging will complete, counting `time_to_full` from `now`
+fn format_full_at(time_to_full: Duration, now: DateTime<Local>, format: &str) -> Option<String> {
+    let full_at = now + chrono::Duration::from_std(time_to_full).ok()?;
+    Some(full_at.format(format).to_string())
+}
+
+/// Check whether the battery has worn below the health threshold, or is hotter than
+/// the temperature threshold
+fn needs_alert(
+    health: f32,
+    temperature: Option<f32>,
+    health_threshold: f32,
+    temperature_threshold: f32,
+) -> bool {
+    let is_too_hot = match temperature {
+        Some(temperature) => temperature >= temperature_threshold,
+        None => false,
+    };
+
+    health < health_threshold || is_too_hot
+}
+
+/// Get the battery status from the cache when it was read less than `cache_duration`
+/// ago, otherwise query the provider and cache the new reading
+fn get_cached_battery_status(
+    provider: &dyn BatteryInfoProvider,
+    cache_file: &Path,
+    cache_duration: Duration,
+    now: SystemTime,
+) -> Option<BatteryStatus> {
+    get_cached_battery_statuses(cache_file, cache_duration, now, || {
+        provider.get_battery_status().into_iter().collect()
+    })
+    .into_iter()
+    .next()
+}
+
+/// Get the status of each battery from the cache when they were read less than
+/// `cache_duration` ago, otherwise read them with `read_statuses` and cache them
+fn get_cached_battery_statuses<F>(
+    cache_file: &Path,
+    cache_duration: Duration,
+    now: SystemTime,
+    read_statuses: F,
+) -> Vec<BatteryStatus>
+where
+    F: FnOnce() -> Vec<BatteryStatus>,
+{
+    if let Some(battery_statuses) = read_battery_cache(cache_file, cache_duration, now) {
+        return battery_statuses;
+    }
+
+    let battery_statuses = read_statuses();
+    if battery_statuses.is_empty() {
+        return battery_statuses;
+    }
+
+    let contents = format_battery_cache(&battery_statuses, now);
+    if let Err(e) = utils::write_file_atomically(cache_file, &contents) {
+        log::debug!("Unable to write the battery cache {:?}: {}", cache_file, e);
+    }
+    battery_statuses
+}
+
+/// Read the cached battery statuses, unless they are older than `cache_duration`
+fn read_battery_cache(
+    cache_file: &Path,
+    cache_duration: Duration,
+    now: SystemTime,
+) -> Option<Vec<BatteryStatus>> {
+    let cache = utils::read_file(cache_file).ok()?;
+    let mut lines = cache.lines();
+
+    let read_at = UNIX_EPOCH + Duration::from_secs(lines.next()?.parse().ok()?);
+    // A reading from the future means the clock changed, so it can't be trusted
+    match now.duration_since(read_at) {
+        Ok(age) if age < cache_duration => {}
+        _ => return None,
+    }
+
+    let mut battery_statuses = Vec::new();
+    while let Some(percentage) = lines.next() {
+        battery_statuses.push(parse_battery_status(percentage, &mut lines)?);
+    }
+
+    if battery_statuses.is_empty() {
+        return None;
+    }
+
+    Some(battery_statuses)
+}
+
+/// Parse the cached lines of a single battery status, starting from its `percentage`
+fn parse_battery_status<'a, I>(percentage: &str, lines: &mut I) -> Option<BatteryStatus>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let percentage = percentage.parse().ok()?;
+    let state = parse_state(lines.next()?)?;
+    let temperature = match lines.next()? {
+        "" => None,
+        temperature => Some(temperature.parse().ok()?),
+    };
+    let health = lines.next()?.parse().ok()?;
+    let time_to_full = parse_cached_duration(lines.next()?)?;
+    let time_to_empty = parse_cached_duration(lines.next()?)?;
+
+    Some(BatteryStatus {
+        percentage,
+        state,
+        temperature,
+        health,
+        time_to_full,
+        time_to_empty,
+    })
+}
+
+/// Parse a cached duration in seconds, which is empty when no duration was reported.
+/// The outer `Option` is `None` when the line can't be parsed.
+fn parse_cached_duration(line: &str) -> Option<Option<Duration>> {
+    match line {
+        "" => Some(None),
+        secs => Some(Some(Duration::from_secs(secs.parse().ok()?))),
+    }
+}
+
+/// Format battery statuses as the lines of the cache file, starting with when they were read
+fn format_battery_cache(battery_statuses: &[BatteryStatus], now: SystemTime) -> String {
+    let read_at = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    battery_statuses
+        .iter()
+        .fold(format!("{}\n", read_at), |mut cache, battery_status| {
+            cache.push_str(&format_battery_status(battery_status));
+            cache
+        })
+}
+
+/// Format a single battery status as lines of the cache file
+fn format_battery_status(battery_status: &BatteryStatus) -> String {
+    let temperature = battery_status
+        .temperature
+        .map(|temperature| temperature.to_string())
+        .unwrap_or_default();
+    let format_duration = |duration: Option<Duration>| {
+        duration
+            .map(|duration| duration.as_secs().to_string())
+            .unwrap_or_default()
+    };
+
+    format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n",
+        battery_status.percentage,
+        format_state(battery_status.state),
+        temperature,
+        battery_status.health,
+        format_duration(battery_status.time_to_full),
+        format_duration(battery_status.time_to_empty)
+    )
+}
+
+fn format_state(state: battery::State) -> &'static str {
+    match state {
+        battery::State::Charging => "charging",
+        battery::State::Discharging => "discharging",
+        battery::State::Empty => "empty",
+        battery::State::Full => "full",
+        _ => "unknown",
+    }
+}
+
+fn parse_state(state: &str) -> Option<battery::State> {
+    match state {
+        "charging" => Some(battery::State::Charging),
+        "discharging" => Some(battery::State::Discharging),
+        "empty" => Some(battery::State::Empty),
+        "full" => Some(battery::State::Full),
+        "unknown" => Some(battery::State::Unknown),
+        _ => None,
+    }
+}
+
+/// A source of battery information, which can be replaced in tests
+pub trait BatteryInfoProvider {
+    /// The status of every battery combined, as if they were a single battery
+    fn get_battery_status(&self) -> Option<BatteryStatus>;
+
+    /// The status of each battery on its own
+    fn get_battery_statuses(&self) -> Vec<BatteryStatus> {
+        self.get_battery_status().into_iter().collect()
+    }
+}
+
+/// Reads battery information from the system using the `battery` crate
+pub struct BatteryInfoProviderImpl;
+
+impl BatteryInfoProvider for BatteryInfoProviderImpl {
+    fn get_battery_status(&self) -> Option<BatteryStatus> {
+        let readings = get_battery_readings()?;
+        if readings.is_empty() {
+            log::debug!("No batteries found");
+        }
+        combine_battery_readings(&readings)
+    }
+
+    fn get_battery_statuses(&self) -> Vec<BatteryStatus> {
+        get_battery_readings()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|reading| combine_battery_readings(std::slice::from_ref(reading)))
+            .collect()
     }
 }
 
-fn get_battery_status() -> Option<BatteryStatus> {
+/// Read every battery the system reports, skipping any which can't be accessed
+fn get_battery_readings() -> Option<Vec<BatteryReading>> {
+    use battery::units::{energy::watt_hour, power::watt, thermodynamic_temperature, time};
+
     let battery_manager = battery::Manager::new().ok()?;
-    match battery_manager.batteries().ok()?.next() {
-        Some(Ok(battery)) => {
-            log::debug!("Battery found: {:?}", battery);
-            let battery_status = BatteryStatus {
-                percentage: battery.state_of_charge().value * 100.0,
-                state: battery.state(),
+    let to_duration =
+        |time: battery::units::Time| Duration::from_secs_f32(time.get::<time::second>());
+    let readings = battery_manager
+        .batteries()
+        .ok()?
+        .filter_map(|battery| match battery {
+            Ok(battery) => {
+                log::debug!("Battery found: {:?}", battery);
+                Some(BatteryReading {
+                    energy: battery.energy().get::<watt_hour>(),
+                    energy_full: battery.energy_full().get::<watt_hour>(),
+                    energy_full_design: battery.energy_full_design().get::<watt_hour>(),
+                    energy_rate: battery.energy_rate().get::<watt>(),
+                    state: battery.state(),
+                    temperature: battery.temperature().map(|temperature| {
+                        temperature.get::<thermodynamic_temperature::degree_celsius>()
+                    }),
+                    time_to_full: battery.time_to_full().map(to_duration),
+                    time_to_empty: battery.time_to_empty().map(to_duration),
+                })
+            }
+            Err(e) => {
+                log::debug!("Unable to access battery information:\n{}", &e);
+                None
+            }
+        })
+        .collect();
+
+    Some(readings)
+}
+
+/// A reading of a single battery, with energy in watt-hours and power in watts
+struct BatteryReading {
+    energy: f32,
+    energy_full: f32,
+    energy_full_design: f32,
+    energy_rate: f32,
+    state: battery::State,
+    temperature: Option<f32>,
+    time_to_full: Option<Duration>,
+    time_to_empty: Option<Duration>,
+}
+
+/// Combine the readings of every battery into one status, as if they were a single
+/// battery holding all of their energy
+///
+/// The time estimates of a single battery come from the platform, but for several
+/// batteries they're calculated from the total energy and rate of discharge.
+fn combine_battery_readings(readings: &[BatteryReading]) -> Option<BatteryStatus> {
+    let energy: f32 = readings.iter().map(|reading| reading.energy).sum();
+    let energy_full: f32 = readings.iter().map(|reading| reading.energy_full).sum();
+    let energy_full_design: f32 = readings
+        .iter()
+        .map(|reading| reading.energy_full_design)
+        .sum();
+    let energy_rate: f32 = readings
+        .iter()
+        .map(|reading| reading.energy_rate.abs())
+        .sum();
+    if energy_full <= 0.0 {
+        return None;
+    }
+
+    let has_state = |state: battery::State| readings.iter().any(|reading| reading.state == state);
+    let all_have_state =
+        |state: battery::State| readings.iter().all(|reading| reading.state == state);
+    let state = if has_state(battery::State::Charging) {
+        battery::State::Charging
+    } else if has_state(battery::State::Discharging) {
+        battery::State::Discharging
+    } else if all_have_state(battery::State::Full) {
+        battery::State::Full
+    } else if all_have_state(battery::State::Empty) {
+        battery::State::Empty
+    } else {
+        battery::State::Unknown
+    };
+
+    let (time_to_full, time_to_empty) = match readings {
+        [reading] => (reading.time_to_full, reading.time_to_empty),
+        _ => {
+            let time_to = |energy: f32| {
+                if energy_rate > 0.0 {
+                    Some(Duration::from_secs_f32(energy / energy_rate * 3600.0))
+                } else {
+                    None
+                }
             };
+            (time_to((energy_full - energy).max(0.0)), time_to(energy))
+        }
+    };
+
+    Some(BatteryStatus {
+        percentage: energy / energy_full * 100.0,
+        state,
+        temperature: readings
+            .iter()
+            .filter_map(|reading| reading.temperature)
+            .fold(None, |hottest: Option<f32>, temperature| {
+                Some(hottest.map_or(temperature, |hottest| hottest.max(temperature)))
+            }),
+        health: if energy_full_design > 0.0 {
+            energy_full / energy_full_design * 100.0
+        } else {
+            100.0
+        },
+        time_to_full,
+        time_to_empty,
+    })
+}
+
+#[derive(Clone)]
+pub struct BatteryStatus {
+    pub percentage: f32,
+    pub state: battery::State,
+    /// The battery temperature in degrees Celsius, if the platform reports it
+    pub temperature: Option<f32>,
+    /// The battery's current full capacity as a percentage of its design capacity
+    pub health: f32,
+    /// The estimated time until the battery is fully charged, if the platform reports it
+    pub time_to_full: Option<Duration>,
+    /// The estimated time until the battery is empty, if the platform reports it
+    pub time_to_empty: Option<Duration>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StarshipConfig;
+    use ansi_term::{ANSIStrings, Color, Style};
+    use clap::ArgMatches;
+    use std::fs;
+
+    struct MockBatteryInfoProvider(Option<BatteryStatus>);
 
-            Some(battery_status)
+    impl BatteryInfoProvider for MockBatteryInfoProvider {
+        fn get_battery_status(&self) -> Option<BatteryStatus> {
+            self.0.clone()
         }
-        Some(Err(e)) => {
-            log::debug!("Unable to access battery information:\n{}", &e);
-            None
+    }
+
+    fn render_battery(status: BatteryStatus, config: toml::Value) -> Option<String> {
+        let provider = MockBatteryInfoProvider(Some(status));
+        let mut context = Context::new_with_dir(ArgMatches::default(), "/");
+        context.config = StarshipConfig {
+            config: Some(config),
+        };
+        context.battery_info_provider = &provider;
+
+        module(&context).map(|module| module.to_string())
+    }
+
+    /// Reports several batteries, along with their combined status
+    struct MockBatteriesInfoProvider {
+        combined: BatteryStatus,
+        batteries: Vec<BatteryStatus>,
+    }
+
+    impl BatteryInfoProvider for MockBatteriesInfoProvider {
+        fn get_battery_status(&self) -> Option<BatteryStatus> {
+            Some(self.combined.clone())
         }
-        None => {
-            log::debug!("No batteries found");
+
+        fn get_battery_statuses(&self) -> Vec<BatteryStatus> {
+            self.batteries.clone()
+        }
+    }
+
+    /// Render a laptop with one battery nearly empty and the other nearly full
+    fn render_divergent_batteries(config: toml::Value) -> Option<String> {
+        let status = |percentage, state| BatteryStatus {
+            percentage,
+            state,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let provider = MockBatteriesInfoProvider {
+            combined: status(50.0, battery::State::Discharging),
+            batteries: vec![
+                status(5.0, battery::State::Discharging),
+                status(95.0, battery::State::Full),
+            ],
+        };
+        let mut context = Context::new_with_dir(ArgMatches::default(), "/");
+        context.config = StarshipConfig {
+            config: Some(config),
+        };
+        context.battery_info_provider = &provider;
+
+        module(&context).map(|module| module.to_string())
+    }
+
+    /// Counts the queries made to it, to tell whether a reading came from the cache
+    struct CountingBatteryInfoProvider {
+        status: BatteryStatus,
+        queries: std::cell::Cell<u32>,
+    }
+
+    impl BatteryInfoProvider for CountingBatteryInfoProvider {
+        fn get_battery_status(&self) -> Option<BatteryStatus> {
+            self.queries.set(self.queries.get() + 1);
+            Some(self.status.clone())
+        }
+    }
+
+    #[test]
+    fn test_select_display_with_hysteresis() {
+        let displays = [
+            BatteryDisplayConfig {
+                threshold: 10,
+                style: Color::Red.bold(),
+            },
+            BatteryDisplayConfig {
+                threshold: 30,
+                style: Color::Yellow.bold(),
+            },
+        ];
+
+        // The charge oscillates around the 10% boundary while discharging, then while charging
+        let mut previous = select_display(&displays, 10.4, 2.0, Some(1));
+        assert_eq!(previous, Some(1));
+        for &percentage in &[9.8, 10.4, 9.6, 11.2, 10.1, 11.9] {
+            previous = select_display(&displays, percentage, 2.0, previous);
+            assert_eq!(previous, Some(0), "at {}%", percentage);
+        }
+        previous = select_display(&displays, 12.1, 2.0, previous);
+        assert_eq!(previous, Some(1));
+        for &percentage in &[11.9, 10.5, 11.2] {
+            previous = select_display(&displays, percentage, 2.0, previous);
+            assert_eq!(previous, Some(1), "at {}%", percentage);
+        }
+
+        // The module stays shown around the highest threshold until the charge passes it
+        for &percentage in &[30.5, 29.5, 31.9] {
+            previous = select_display(&displays, percentage, 2.0, previous);
+            assert_eq!(previous, Some(1), "at {}%", percentage);
+        }
+        previous = select_display(&displays, 32.5, 2.0, previous);
+        assert_eq!(previous, None);
+        assert_eq!(select_display(&displays, 31.0, 2.0, previous), None);
+    }
+
+    #[test]
+    fn test_select_display_without_hysteresis() {
+        let displays = [BatteryDisplayConfig {
+            threshold: 10,
+            style: Color::Red.bold(),
+        }];
+
+        assert_eq!(select_display(&displays, 9.9, 0.0, None), Some(0));
+        assert_eq!(select_display(&displays, 10.1, 0.0, Some(0)), None);
+        assert_eq!(select_display(&displays, 10.1, 0.0, None), None);
+    }
+
+    #[test]
+    fn test_previous_display() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let display_file = dir.path().join(DISPLAY_FILE);
+        assert_eq!(read_previous_display(&display_file), None);
+
+        write_previous_display(&display_file, Some(1));
+        assert_eq!(read_previous_display(&display_file), Some(1));
+
+        write_previous_display(&display_file, None);
+        assert_eq!(read_previous_display(&display_file), None);
+
+        dir.close()
+    }
+
+    #[test]
+    fn test_select_frame() {
+        let frames = ["▁", "▃", "▅", "▇"];
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+
+        assert_eq!(select_frame(&frames, at(1_000)), Some("▁"));
+        assert_eq!(select_frame(&frames, at(1_001)), Some("▃"));
+        assert_eq!(select_frame(&frames, at(1_003)), Some("▇"));
+        assert_eq!(select_frame(&frames, at(1_004)), Some("▁"));
+        // Frames only advance once per second
+        assert_eq!(
+            select_frame(&frames, at(1_002) + Duration::from_millis(999)),
+            Some("▅")
+        );
+    }
+
+    #[test]
+    fn test_select_frame_without_frames() {
+        assert_eq!(
+            select_frame(&[], UNIX_EPOCH + Duration::from_secs(1_000)),
             None
+        );
+    }
+
+    #[test]
+    fn test_battery_cache_hit_and_miss() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache_file = dir.path().join(CACHE_FILE);
+        let cache_duration = Duration::from_secs(10);
+        let provider = CountingBatteryInfoProvider {
+            status: BatteryStatus {
+                percentage: 42.5,
+                state: battery::State::Charging,
+                temperature: Some(31.5),
+                health: 90.0,
+                time_to_full: Some(Duration::from_secs(1_800)),
+                time_to_empty: Some(Duration::from_secs(7_200)),
+            },
+            queries: std::cell::Cell::new(0),
+        };
+        let start = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        // Nothing is cached yet
+        let status = get_cached_battery_status(&provider, &cache_file, cache_duration, start);
+        assert_eq!(provider.queries.get(), 1);
+        assert_eq!(status.map(|status| status.percentage), Some(42.5));
+
+        // The reading is reused within the cache duration
+        let later = start + Duration::from_secs(9);
+        let status = get_cached_battery_status(&provider, &cache_file, cache_duration, later)
+            .expect("the cached status should be read");
+        assert_eq!(provider.queries.get(), 1);
+        assert_eq!(status.percentage, 42.5);
+        assert_eq!(status.state, battery::State::Charging);
+        assert_eq!(status.temperature, Some(31.5));
+        assert_eq!(status.health, 90.0);
+        assert_eq!(status.time_to_full, Some(Duration::from_secs(1_800)));
+        assert_eq!(status.time_to_empty, Some(Duration::from_secs(7_200)));
+
+        // The reading expires after the cache duration
+        let expired = start + Duration::from_secs(10);
+        get_cached_battery_status(&provider, &cache_file, cache_duration, expired);
+        assert_eq!(provider.queries.get(), 2);
+
+        dir.close()
+    }
+
+    #[test]
+    fn test_battery_cache_of_each_battery() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache_file = dir.path().join(BATTERIES_CACHE_FILE);
+        let cache_duration = Duration::from_secs(10);
+        let status = |percentage, state| BatteryStatus {
+            percentage,
+            state,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: Some(Duration::from_secs(600)),
+        };
+        let batteries = vec![
+            status(5.0, battery::State::Discharging),
+            status(95.0, battery::State::Full),
+        ];
+        let queries = std::cell::Cell::new(0);
+        let read_statuses = || {
+            queries.set(queries.get() + 1);
+            batteries.clone()
+        };
+        let start = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        get_cached_battery_statuses(&cache_file, cache_duration, start, read_statuses);
+        assert_eq!(queries.get(), 1);
+
+        let later = start + Duration::from_secs(9);
+        let cached = get_cached_battery_statuses(&cache_file, cache_duration, later, read_statuses);
+        assert_eq!(queries.get(), 1);
+        assert_eq!(cached.len(), 2);
+        assert_eq!(cached[0].percentage, 5.0);
+        assert_eq!(cached[0].time_to_empty, Some(Duration::from_secs(600)));
+        assert_eq!(cached[1].percentage, 95.0);
+        assert_eq!(cached[1].state, battery::State::Full);
+
+        dir.close()
+    }
+
+    #[test]
+    fn test_battery_cache_from_the_future() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache_file = dir.path().join(CACHE_FILE);
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        fs::write(
+            &cache_file,
+            format_battery_cache(&[status], now + Duration::from_secs(5)),
+        )?;
+
+        assert!(read_battery_cache(&cache_file, Duration::from_secs(10), now).is_none());
+        dir.close()
+    }
+
+    #[test]
+    fn test_format_percentage() {
+        assert_eq!(format_percentage(99.4, 0), "99");
+        assert_eq!(format_percentage(4.5, 0), "5");
+        assert_eq!(format_percentage(99.44, 1), "99.4");
+        assert_eq!(format_percentage(100.0, 1), "100.0");
+        assert_eq!(format_percentage(7.126, 2), "7.13");
+    }
+
+    #[test]
+    fn test_format_temperature() {
+        assert_eq!(format_temperature(36.66), "36.7°C");
+        assert_eq!(format_temperature(40.0), "40.0°C");
+    }
+
+    #[test]
+    fn test_format_time_remaining() {
+        assert_eq!(format_time_remaining(Duration::from_secs(80 * 60)), "1h20m");
+        assert_eq!(
+            format_time_remaining(Duration::from_secs(45 * 60 + 30)),
+            "45m"
+        );
+        assert_eq!(
+            format_time_remaining(Duration::from_secs(2 * 60 * 60)),
+            "2h"
+        );
+        assert_eq!(format_time_remaining(Duration::from_secs(30)), "<1m");
+    }
+
+    fn reading(energy: f32, energy_rate: f32, state: battery::State) -> BatteryReading {
+        BatteryReading {
+            energy,
+            energy_full: 50.0,
+            energy_full_design: 50.0,
+            energy_rate,
+            state,
+            temperature: None,
+            time_to_full: None,
+            time_to_empty: None,
         }
     }
-}
 
-struct BatteryStatus {
-    percentage: f32,
-    state: battery::State,
+    #[test]
+    fn test_combine_single_battery() {
+        let mut battery = reading(25.0, 10.0, battery::State::Discharging);
+        battery.energy_full_design = 62.5;
+        battery.temperature = Some(30.0);
+        battery.time_to_empty = Some(Duration::from_secs(9_000));
+
+        let status = combine_battery_readings(&[battery]).unwrap();
+        assert_eq!(status.percentage, 50.0);
+        assert_eq!(status.state, battery::State::Discharging);
+        assert_eq!(status.temperature, Some(30.0));
+        assert_eq!(status.health, 80.0);
+        // A single battery's estimates come straight from the platform
+        assert_eq!(status.time_to_empty, Some(Duration::from_secs(9_000)));
+        assert_eq!(status.time_to_full, None);
+    }
+
+    #[test]
+    fn test_combine_discharging_batteries() {
+        let mut internal = reading(40.0, 10.0, battery::State::Discharging);
+        internal.temperature = Some(31.0);
+        let mut external = reading(10.0, 0.0, battery::State::Unknown);
+        external.temperature = Some(35.0);
+
+        let status = combine_battery_readings(&[internal, external]).unwrap();
+        assert_eq!(status.percentage, 50.0);
+        assert_eq!(status.state, battery::State::Discharging);
+        assert_eq!(status.temperature, Some(35.0));
+        assert_eq!(status.health, 100.0);
+        // 50Wh left at 10W
+        assert_eq!(status.time_to_empty, Some(Duration::from_secs(5 * 60 * 60)));
+        assert_eq!(status.time_to_full, Some(Duration::from_secs(5 * 60 * 60)));
+    }
+
+    #[test]
+    fn test_combine_charging_batteries() {
+        let status = combine_battery_readings(&[
+            reading(50.0, 0.0, battery::State::Full),
+            reading(20.0, 15.0, battery::State::Charging),
+        ])
+        .unwrap();
+        assert_eq!(status.percentage, 70.0);
+        assert_eq!(status.state, battery::State::Charging);
+        // 30Wh to go at 15W
+        assert_eq!(status.time_to_full, Some(Duration::from_secs(2 * 60 * 60)));
+    }
+
+    #[test]
+    fn test_combine_idle_batteries() {
+        let status = combine_battery_readings(&[
+            reading(50.0, 0.0, battery::State::Full),
+            reading(50.0, 0.0, battery::State::Full),
+        ])
+        .unwrap();
+        assert_eq!(status.percentage, 100.0);
+        assert_eq!(status.state, battery::State::Full);
+        assert_eq!(status.time_to_full, None);
+        assert_eq!(status.time_to_empty, None);
+
+        let status = combine_battery_readings(&[
+            reading(50.0, 0.0, battery::State::Full),
+            reading(0.0, 0.0, battery::State::Empty),
+        ])
+        .unwrap();
+        assert_eq!(status.state, battery::State::Unknown);
+    }
+
+    #[test]
+    fn test_combine_no_batteries() {
+        assert!(combine_battery_readings(&[]).is_none());
+    }
+
+    #[test]
+    fn test_format_full_at() {
+        use chrono::offset::TimeZone;
+
+        let now = Local.ymd(2019, 7, 8).and_hms(14, 2, 10);
+        assert_eq!(
+            format_full_at(Duration::from_secs(30 * 60), now, "full at %H:%M"),
+            Some("full at 14:32".to_string())
+        );
+        assert_eq!(
+            format_full_at(Duration::from_secs(10 * 60 * 60), now, "%H:%M"),
+            Some("00:02".to_string())
+        );
+    }
+
+    #[test]
+    fn test_no_battery() {
+        let provider = MockBatteryInfoProvider(None);
+        let mut context = Context::new_with_dir(ArgMatches::default(), "/");
+        context.battery_info_provider = &provider;
+
+        assert!(module(&context).is_none());
+    }
+
+    #[test]
+    fn test_display_percentage_with_precision() {
+        let status = BatteryStatus {
+            percentage: 9.44,
+            state: battery::State::Discharging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            precision = 1
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓9.4%"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_percentage_with_zsh_escape() {
+        let status = BatteryStatus {
+            percentage: 9.44,
+            state: battery::State::Discharging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let provider = MockBatteryInfoProvider(Some(status));
+        let mut context = Context::new_with_dir(ArgMatches::default(), "/");
+        context.config = StarshipConfig {
+            config: Some(toml::toml! {
+                [battery]
+                precision = 1
+            }),
+        };
+        context.battery_info_provider = &provider;
+        context.shell = Shell::Zsh;
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓9.4%%"));
+        assert_eq!(
+            module(&context).map(|module| module.to_string()),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_display_temperature() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: Some(36.66),
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_temperature = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓5% 36.7°C"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_missing_temperature() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_temperature = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓5%"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_high_temperature() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: Some(50.0),
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_temperature = true
+            high_temperature_style = "bold yellow"
+        };
+
+        let expected = format!(
+            "{} ",
+            ANSIStrings(&[
+                Color::Red.bold().paint("↓5%"),
+                Color::Yellow.bold().paint(" 50.0°C"),
+            ])
+        );
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_alert_for_low_health() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: None,
+            health: 65.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_alert = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓5% ⚠"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_alert_for_high_temperature() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: Some(50.0),
+            health: 95.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_alert = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓5% ⚠"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_no_alert_for_healthy_battery() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: Some(30.0),
+            health: 95.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_alert = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓5%"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_no_alert_when_disabled() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: Some(50.0),
+            health: 65.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓5%"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_full_at() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Charging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: Some(Duration::from_secs(1_800)),
+            time_to_empty: None,
+        };
+        // A format without the time keeps the output independent of the clock
+        let config = toml::toml! {
+            [battery]
+            display_full_at = true
+            full_at_format = "full soon"
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↑5% full soon"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_no_full_at_without_estimate() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Charging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_full_at = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↑5%"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_no_full_at_when_discharging() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: Some(Duration::from_secs(1_800)),
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_full_at = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓5%"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_time_when_discharging() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: Some(Duration::from_secs(1_800)),
+            time_to_empty: Some(Duration::from_secs(80 * 60)),
+        };
+        let config = toml::toml! {
+            [battery]
+            display_time = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓5% 1h20m"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_time_when_charging() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Charging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: Some(Duration::from_secs(45 * 60)),
+            time_to_empty: Some(Duration::from_secs(80 * 60)),
+        };
+        let config = toml::toml! {
+            [battery]
+            display_time = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↑5% 45m"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_no_time_without_estimate() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: Some(Duration::from_secs(1_800)),
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_time = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("↓5%"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_ac_when_charging() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Charging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_ac = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("🔌 ↑5%"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_ac_when_discharging() {
+        let status = BatteryStatus {
+            percentage: 5.0,
+            state: battery::State::Discharging,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let config = toml::toml! {
+            [battery]
+            display_ac = true
+        };
+
+        let expected = format!("{} ", Color::Red.bold().paint("🔋 ↓5%"));
+        assert_eq!(render_battery(status, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_ac_from_combined_state() {
+        let config = toml::toml! {
+            [battery]
+            aggregate = false
+            display_ac = true
+
+            [[battery.display]]
+            threshold = 60
+            style = "bold yellow"
+        };
+
+        let expected = format!("{} ", Color::Yellow.bold().paint("🔋 1:↓5% 2:•95%"));
+        assert_eq!(render_divergent_batteries(config), Some(expected));
+    }
+
+    #[test]
+    fn test_aggregate_batteries() {
+        let config = toml::toml! {
+            [[battery.display]]
+            threshold = 60
+            style = "bold yellow"
+        };
+
+        let expected = format!("{} ", Color::Yellow.bold().paint("↓50%"));
+        assert_eq!(render_divergent_batteries(config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_each_battery() {
+        let config = toml::toml! {
+            [battery]
+            aggregate = false
+
+            [[battery.display]]
+            threshold = 60
+            style = "bold yellow"
+        };
+
+        let expected = format!("{} ", Color::Yellow.bold().paint("1:↓5% 2:•95%"));
+        assert_eq!(render_divergent_batteries(config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_each_battery_styled_by_combined_charge() {
+        // One battery is below the default threshold, but the combined charge isn't
+        let config = toml::toml! {
+            [battery]
+            aggregate = false
+        };
+
+        assert_eq!(render_divergent_batteries(config), None);
+    }
+
+    #[test]
+    fn test_display_each_battery_skips_unhandled_state() {
+        let status = |percentage, state| BatteryStatus {
+            percentage,
+            state,
+            temperature: None,
+            health: 100.0,
+            time_to_full: None,
+            time_to_empty: None,
+        };
+        let provider = MockBatteriesInfoProvider {
+            combined: status(50.0, battery::State::Discharging),
+            batteries: vec![
+                status(5.0, battery::State::Discharging),
+                status(50.0, battery::State::__Nonexhaustive),
+                status(95.0, battery::State::Full),
+            ],
+        };
+        let mut context = Context::new_with_dir(ArgMatches::default(), "/");
+        context.config = StarshipConfig {
+            config: Some(toml::toml! {
+                [battery]
+                aggregate = false
+
+                [[battery.display]]
+                threshold = 60
+                style = "bold yellow"
+            }),
+        };
+        context.battery_info_provider = &provider;
+
+        let expected = format!("{} ", Color::Yellow.bold().paint("1:↓5% 3:•95%"));
+        assert_eq!(
+            module(&context).map(|module| module.to_string()),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_display_each_battery_with_custom_labels() {
+        let config = toml::toml! {
+            [battery]
+            aggregate = false
+            index = { value = "", style = "dimmed" }
+            separator = " | "
+
+            [[battery.display]]
+            threshold = 60
+            style = "bold yellow"
+        };
+
+        let (index_style, style) = (Style::new().dimmed(), Color::Yellow.bold());
+        let expected = format!(
+            "{} ",
+            ANSIStrings(&[
+                index_style.paint("1:"),
+                style.paint("↓5% | "),
+                index_style.paint("2:"),
+                style.paint("•95%"),
+            ])
+        );
+        assert_eq!(render_divergent_batteries(config), Some(expected));
+    }
 }
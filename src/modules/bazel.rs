@@ -0,0 +1,79 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::bazel::BazelConfig;
+
+/// Creates a module for the current Bazel workspace
+///
+/// Will display the Bazel symbol if any of the following criteria are met:
+///     - Current directory contains a `WORKSPACE` file
+///     - Current directory contains a `WORKSPACE.bazel` file
+///     - Current directory contains a `BUILD` file
+///
+/// The Bazel version is only displayed if `show_version` is enabled, since
+/// `bazel version` may have to start a Bazel server, which is slow.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_bazel_workspace = context
+        .try_begin_scan("bazel", None)?
+        .set_files(&["WORKSPACE", "WORKSPACE.bazel", "BUILD"])
+        .is_match();
+
+    if !is_bazel_workspace {
+        return None;
+    }
+
+    let mut module = context.new_module("bazel");
+    let config: BazelConfig = BazelConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+
+    if config.show_version {
+        if let Some(formatted_version) =
+            get_bazel_version(context).and_then(|v| format_bazel_version(&v))
+        {
+            module.create_segment("version", &config.version.with_value(&formatted_version));
+        }
+    }
+
+    Some(module)
+}
+
+fn get_bazel_version(context: &Context) -> Option<String> {
+    context.exec_cmd("bazel", &["version"])
+}
+
+fn format_bazel_version(bazel_stdout: &str) -> Option<String> {
+    // bazel version output looks like this:
+    // Build label: 2.0.0
+    // Build target: bazel-out/k8-opt/bin/src/main/java/com/google/devtools/build/lib/bazel/BazelServer_deploy.jar
+    // ...
+
+    let version = bazel_stdout
+        .lines()
+        .find(|line| line.starts_with("Build label:"))?
+        .trim_start_matches("Build label:")
+        .trim();
+
+    if version.is_empty() {
+        return None;
+    }
+
+    Some(format!("v{}", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bazel_version() {
+        let input = "Build label: 2.0.0\nBuild target: bazel-out/k8-opt/bin/src/main/java/com/google/devtools/build/lib/bazel/BazelServer_deploy.jar\nBuild time: Thu Dec 19 12:30:18 2019 (1576758618)\n";
+        assert_eq!(format_bazel_version(input), Some("v2.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_format_bazel_version_without_label() {
+        let input = "Build label: \nBuild target: unknown\n";
+        assert_eq!(format_bazel_version(input), None);
+    }
+}
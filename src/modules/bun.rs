@@ -0,0 +1,57 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::bun::BunConfig;
+
+/// Creates a module with the current Bun version
+///
+/// Will display the Bun version if any of the following criteria are met:
+///     - Current directory contains a `bun.lockb` file
+///     - Current directory contains a `bunfig.toml` file
+///     - `detect_always` is set to `true` in the module configuration
+///
+/// A `package.json` alone isn't enough, since it's shared with Node.js projects,
+/// which are shown by the `nodejs` module.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_bun_project = context
+        .try_begin_scan("bun", Some(&["bun", "--version"]))?
+        .set_files(&["bun.lockb", "bunfig.toml"])
+        .is_match();
+
+    if !is_bun_project {
+        return None;
+    }
+
+    let mut module = context.new_module("bun");
+    let config: BunConfig = BunConfig::try_load(module.config);
+
+    let bun_version = context.exec_cmd("bun", &["--version"])?;
+    let formatted_version = format_bun_version(&bun_version)?;
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("version", &config.version.with_value(&formatted_version));
+
+    Some(module)
+}
+
+/// Format the output of `bun --version`, e.g. `1.0.0`, with a leading `v`
+fn format_bun_version(bun_stdout: &str) -> Option<String> {
+    let version = bun_stdout.trim().trim_start_matches('v');
+    if version.is_empty() {
+        return None;
+    }
+
+    Some(format!("v{}", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bun_version() {
+        assert_eq!(format_bun_version("1.0.0\n"), Some("v1.0.0".to_string()));
+        assert_eq!(format_bun_version("v1.1.8"), Some("v1.1.8".to_string()));
+        assert_eq!(format_bun_version(""), None);
+    }
+}
@@ -6,7 +6,8 @@ use crate::configs::cmd_duration::CmdDurationConfig;
 /// Outputs the time it took the last command to execute
 ///
 /// Will only print if last command took more than a certain amount of time to
-/// execute. Default is two seconds, but can be set by config option `min_time`.
+/// execute. Default is two seconds, but can be set in milliseconds by config
+/// option `min_time`.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("cmd_duration");
     let config: CmdDurationConfig = CmdDurationConfig::try_load(module.config);
@@ -36,7 +37,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     };
 
     module.set_style(module_color);
-    let cmd_duration_stacked = &format!("{}{}", config.prefix, render_time(elapsed));
+    let cmd_duration_stacked = &format!(
+        "{}{}",
+        config.prefix,
+        render_time(elapsed, config.show_milliseconds)
+    );
     module.create_segment("cmd_duration", &SegmentConfig::new(&cmd_duration_stacked));
     module.get_prefix().set_value("");
 
@@ -44,8 +49,16 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 }
 
 // Render the time into a nice human-readable string
-fn render_time(raw_seconds: u64) -> String {
-    // Calculate a simple breakdown into days/hours/minutes/seconds
+//
+// Milliseconds are only shown for durations under a second, unless `show_millis` is set
+fn render_time(raw_millis: u64, show_millis: bool) -> String {
+    // Every component of zero is hidden, which would leave nothing to show
+    if raw_millis == 0 {
+        return "0ms".to_string();
+    }
+
+    // Calculate a simple breakdown into days/hours/minutes/seconds/milliseconds
+    let (millis, raw_seconds) = (raw_millis % 1000, raw_millis / 1000);
     let (seconds, raw_minutes) = (raw_seconds % 60, raw_seconds / 60);
     let (minutes, raw_hours) = (raw_minutes % 60, raw_minutes / 60);
     let (hours, days) = (raw_hours % 24, raw_hours / 24);
@@ -53,11 +66,14 @@ fn render_time(raw_seconds: u64) -> String {
     let components = [days, hours, minutes, seconds];
     let suffixes = ["d", "h", "m", "s"];
 
-    let rendered_components: Vec<String> = components
+    let mut rendered_components: Vec<String> = components
         .iter()
         .zip(&suffixes)
         .map(render_time_component)
         .collect();
+    if show_millis || raw_millis < 1000 {
+        rendered_components.push(render_time_component((&millis, &"ms")));
+    }
     rendered_components.join("")
 }
 
@@ -73,20 +89,40 @@ fn render_time_component((component, suffix): (&u64, &&str)) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_0ms() {
+        assert_eq!(render_time(0, false), "0ms")
+    }
+    #[test]
+    fn test_450ms() {
+        assert_eq!(render_time(450, false), "450ms")
+    }
     #[test]
     fn test_10s() {
-        assert_eq!(render_time(10 as u64), "10s")
+        assert_eq!(render_time(10_000, false), "10s")
+    }
+    #[test]
+    fn test_10s_hides_millis() {
+        assert_eq!(render_time(10_450, false), "10s")
+    }
+    #[test]
+    fn test_10s_shows_millis() {
+        assert_eq!(render_time(10_450, true), "10s450ms")
     }
     #[test]
     fn test_90s() {
-        assert_eq!(render_time(90 as u64), "1m30s")
+        assert_eq!(render_time(90_000, false), "1m30s")
+    }
+    #[test]
+    fn test_150s() {
+        assert_eq!(render_time(150_000, false), "2m30s")
     }
     #[test]
     fn test_10110s() {
-        assert_eq!(render_time(10110 as u64), "2h48m30s")
+        assert_eq!(render_time(10_110_000, false), "2h48m30s")
     }
     #[test]
     fn test_1d() {
-        assert_eq!(render_time(86400 as u64), "1d")
+        assert_eq!(render_time(86_400_000, false), "1d")
     }
 }
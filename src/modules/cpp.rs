@@ -0,0 +1,92 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::cpp::CppConfig;
+
+/// Creates a module with the version of the C/C++ compiler
+///
+/// Will display the compiler version if any of the following criteria are met:
+///     - Current directory contains a file with the `.c`, `.cpp`, `.h` or `.hpp` extension
+///     - Current directory contains a `Makefile` or `CMakeLists.txt` file
+///     - `detect_always` is set to `true` in the module configuration
+///
+/// The compiler is `cc` by default, and can be changed with the `compiler` option
+/// (e.g. `gcc` or `clang`).
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("cpp");
+    let config: CppConfig = CppConfig::try_load(module.config);
+
+    let is_cpp_project = context
+        .try_begin_scan("cpp", Some(&[config.compiler, "--version"]))?
+        .set_files(&["Makefile", "CMakeLists.txt"])
+        .set_extensions(&["c", "cpp", "h", "hpp"])
+        .is_match();
+
+    if !is_cpp_project {
+        return None;
+    }
+
+    let compiler_version = get_compiler_version(context, config.compiler)?;
+    let formatted_version = format_compiler_version(&compiler_version)?;
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("version", &config.version.with_value(&formatted_version));
+
+    Some(module)
+}
+
+fn get_compiler_version(context: &Context, compiler: &str) -> Option<String> {
+    context.exec_cmd(compiler, &["--version"])
+}
+
+/// Find the version in the first line of a compiler's `--version` output
+///
+/// The version is the first word starting with a dotted number, e.g.
+/// `gcc (GCC) 9.2.0` or `clang version 10.0.0-4ubuntu1`.
+fn format_compiler_version(compiler_stdout: &str) -> Option<String> {
+    let version = compiler_stdout
+        .lines()
+        .next()?
+        .split_whitespace()
+        .map(|word| word.trim_start_matches('('))
+        .filter(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|word| {
+            let end = word
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(word.len());
+            word[..end].trim_end_matches('.')
+        })
+        .find(|version| version.contains('.'))?;
+
+    Some(format!("v{}", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_gcc_version() {
+        let input = "gcc (GCC) 9.2.0\nCopyright (C) 2019 Free Software Foundation, Inc.\n";
+        assert_eq!(format_compiler_version(input), Some("v9.2.0".to_string()));
+
+        let input = "gcc (Ubuntu 9.2.1-9ubuntu2) 9.2.1 20191008\n";
+        assert_eq!(format_compiler_version(input), Some("v9.2.1".to_string()));
+    }
+
+    #[test]
+    fn test_format_clang_version() {
+        let input = "clang version 10.0.0-4ubuntu1\nTarget: x86_64-pc-linux-gnu\n";
+        assert_eq!(format_compiler_version(input), Some("v10.0.0".to_string()));
+
+        let input =
+            "Apple clang version 11.0.0 (clang-1100.0.33.8)\nTarget: x86_64-apple-darwin19.0.0\n";
+        assert_eq!(format_compiler_version(input), Some("v11.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_format_missing_version() {
+        assert_eq!(format_compiler_version(""), None);
+        assert_eq!(format_compiler_version("cc: no version here\n"), None);
+    }
+}
@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::crystal::CrystalConfig;
+
+/// Creates a module with the current Crystal version
+///
+/// Will display the Crystal version if any of the following criteria are met:
+///     - Current directory contains a `shard.yml` file
+///     - Current directory contains a file with the `.cr` extension
+///     - `detect_always` is set to `true` in the module configuration
+///
+/// When `check_shard_lock` is enabled, a warning is shown if `shard.yml` was
+/// modified after `shard.lock`, suggesting `shards install` needs to be run.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_crystal_project = context
+        .try_begin_scan("crystal", Some(&["crystal", "--version"]))?
+        .set_files(&["shard.yml"])
+        .set_extensions(&["cr"])
+        .is_match();
+
+    if !is_crystal_project {
+        return None;
+    }
+
+    let mut module = context.new_module("crystal");
+    let config: CrystalConfig = CrystalConfig::try_load(module.config);
+
+    let crystal_version = get_crystal_version(context)?;
+    let formatted_version = format_crystal_version(&crystal_version)?;
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("version", &config.version.with_value(&formatted_version));
+
+    if config.check_shard_lock && is_shard_lock_stale(&context.current_dir) {
+        module.create_segment("shard_lock_stale", &config.shard_lock_stale);
+    }
+
+    Some(module)
+}
+
+fn get_crystal_version(context: &Context) -> Option<String> {
+    context.exec_cmd("crystal", &["--version"])
+}
+
+/// Find the version in the output of `crystal --version`, e.g.
+/// `Crystal 0.31.1 (2019-10-02)`
+fn format_crystal_version(crystal_stdout: &str) -> Option<String> {
+    let version = crystal_stdout
+        .lines()
+        .next()?
+        .trim_start_matches("Crystal ")
+        .split_whitespace()
+        .next()?;
+
+    Some(format!("v{}", version))
+}
+
+/// Check whether `shard.yml` was last modified after `shard.lock` was written
+///
+/// Projects without a `shard.lock`, such as libraries, are never stale.
+fn is_shard_lock_stale(current_dir: &Path) -> bool {
+    match (
+        get_modified(&current_dir.join("shard.yml")),
+        get_modified(&current_dir.join("shard.lock")),
+    ) {
+        (Some(shard_yml_modified), Some(shard_lock_modified)) => {
+            shard_yml_modified > shard_lock_modified
+        }
+        _ => false,
+    }
+}
+
+fn get_modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_crystal_version() {
+        let input = "Crystal 0.31.1 (2019-10-02)\n\nLLVM: 8.0.1\nDefault target: x86_64-unknown-linux-gnu\n";
+        assert_eq!(format_crystal_version(input), Some("v0.31.1".to_string()));
+    }
+
+    #[test]
+    fn test_format_missing_crystal_version() {
+        assert_eq!(format_crystal_version(""), None);
+    }
+}
@@ -0,0 +1,174 @@
+use byte_unit::Byte;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::{Context, Module, RootModuleConfig, SkipReason};
+
+use crate::configs::dir_size::DirSizeConfig;
+
+/// Creates a module with the total size of the files in the current directory
+///
+/// Only the files directly in the current directory are counted, unless `recursive`
+/// is enabled. The recursive walk stops at `max_depth` levels of subdirectories, and
+/// after `timeout` milliseconds, in which case the size is shown as a lower bound
+/// (e.g. `1.2GiB+`).
+///
+/// The size is only displayed when it's at or above `threshold`.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("dir_size");
+    let config: DirSizeConfig = DirSizeConfig::try_load(module.config);
+
+    if config.disabled {
+        context.set_skip_reason("dir_size", SkipReason::Disabled);
+        return None;
+    }
+
+    let threshold = match Byte::from_str(config.threshold) {
+        Ok(threshold) => threshold.get_bytes() as u64,
+        Err(e) => {
+            log::warn!("Invalid dir_size threshold {:?}: {:?}", config.threshold, e);
+            0
+        }
+    };
+
+    let max_depth = if config.recursive {
+        config.max_depth.max(0) as usize
+    } else {
+        0
+    };
+    let deadline = Instant::now() + Duration::from_millis(config.timeout);
+    let dir_size = get_dir_size(&context.current_dir, max_depth, deadline);
+    if dir_size.bytes < threshold {
+        return None;
+    }
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("size", &config.size.with_value(&format_dir_size(&dir_size)));
+
+    Some(module)
+}
+
+struct DirSize {
+    bytes: u64,
+    /// Whether every file was counted before the deadline
+    complete: bool,
+}
+
+/// Add up the size of the files in `dir`, and in its subdirectories up to `max_depth`
+/// levels deep. Symlinks aren't followed.
+fn get_dir_size(dir: &Path, max_depth: usize, deadline: Instant) -> DirSize {
+    let mut dir_size = DirSize {
+        bytes: 0,
+        complete: true,
+    };
+    let mut pending: Vec<(PathBuf, usize)> = vec![(dir.to_path_buf(), 0)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::debug!("Unable to read {:?}: {}", dir, e);
+                continue;
+            }
+        };
+
+        for entry in entries.filter_map(Result::ok) {
+            if Instant::now() >= deadline {
+                dir_size.complete = false;
+                return dir_size;
+            }
+
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(_) => continue,
+            };
+            if metadata.is_file() {
+                dir_size.bytes += metadata.len();
+            } else if metadata.is_dir() && depth < max_depth {
+                pending.push((entry.path(), depth + 1));
+            }
+        }
+    }
+
+    dir_size
+}
+
+fn format_dir_size(dir_size: &DirSize) -> String {
+    let mut size = Byte::from_bytes(u128::from(dir_size.bytes))
+        .get_appropriate_unit(true)
+        .format(1);
+    size.retain(|c| c != ' ');
+    if !dir_size.complete {
+        size.push('+');
+    }
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    fn create_file(path: &Path, len: u64) -> io::Result<()> {
+        fs::File::create(path)?.set_len(len)
+    }
+
+    /// Create a directory holding 1000 bytes, with 200 bytes one level deep and
+    /// 30 bytes two levels deep
+    fn create_nested_dir() -> io::Result<tempfile::TempDir> {
+        let dir = tempfile::tempdir()?;
+        create_file(&dir.path().join("a.bin"), 600)?;
+        create_file(&dir.path().join("b.bin"), 400)?;
+        fs::create_dir_all(dir.path().join("sub/deeper"))?;
+        create_file(&dir.path().join("sub/c.bin"), 200)?;
+        create_file(&dir.path().join("sub/deeper/d.bin"), 30)?;
+        Ok(dir)
+    }
+
+    fn far_deadline() -> Instant {
+        Instant::now() + Duration::from_secs(60)
+    }
+
+    #[test]
+    fn test_shallow_dir_size() -> io::Result<()> {
+        let dir = create_nested_dir()?;
+        let dir_size = get_dir_size(dir.path(), 0, far_deadline());
+        assert_eq!(dir_size.bytes, 1000);
+        assert!(dir_size.complete);
+        dir.close()
+    }
+
+    #[test]
+    fn test_recursive_dir_size() -> io::Result<()> {
+        let dir = create_nested_dir()?;
+        assert_eq!(get_dir_size(dir.path(), 1, far_deadline()).bytes, 1200);
+        assert_eq!(get_dir_size(dir.path(), 5, far_deadline()).bytes, 1230);
+        dir.close()
+    }
+
+    #[test]
+    fn test_dir_size_after_deadline() -> io::Result<()> {
+        let dir = create_nested_dir()?;
+        let dir_size = get_dir_size(dir.path(), 5, Instant::now());
+        assert_eq!(dir_size.bytes, 0);
+        assert!(!dir_size.complete);
+        dir.close()
+    }
+
+    #[test]
+    fn test_format_dir_size() {
+        let dir_size = DirSize {
+            bytes: 1536,
+            complete: true,
+        };
+        assert_eq!(format_dir_size(&dir_size), "1.5KiB");
+
+        let dir_size = DirSize {
+            bytes: 3 * 1024 * 1024 * 1024,
+            complete: false,
+        };
+        assert_eq!(format_dir_size(&dir_size), "3.0GiB+");
+    }
+}
@@ -1,5 +1,7 @@
+use git2::Repository;
 use path_slash::PathExt;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use super::{Context, Module};
 
@@ -16,6 +18,17 @@ use crate::configs::directory::DirectoryConfig;
 ///
 /// **Truncation**
 /// Paths will be limited in length to `3` path components by default.
+///
+/// **Read-only**
+/// A lock symbol is appended if the current directory can't be written to.
+///
+/// **Git-ignored**
+/// When `show_git_ignored` is enabled, a marker is appended if the current
+/// directory is ignored by the git repo it's in.
+///
+/// **Symlink target**
+/// When `show_symlink_target` is enabled and the current directory is a
+/// symlink, the path it points to is appended.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     const HOME_SYMBOL: &str = "~";
 
@@ -71,28 +84,137 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             &truncated_dir_string,
         );
 
-        module.create_segment(
-            "path",
-            &SegmentConfig {
-                value: &fish_style_dir,
-                style: None,
-            },
-        );
+        module.create_segment("path", &SegmentConfig::new(&fish_style_dir));
     }
 
-    module.create_segment(
-        "path",
-        &SegmentConfig {
-            value: &truncated_dir_string,
-            style: None,
-        },
-    );
+    module.create_segment("path", &SegmentConfig::new(&truncated_dir_string));
+
+    if is_read_only(current_dir) {
+        module.create_segment("read_only", &config.read_only);
+    }
+
+    if config.show_git_ignored {
+        if let Some(repo_root) = &repo.root {
+            if is_git_ignored(repo_root, current_dir) {
+                module.create_segment("git_ignored", &config.git_ignored);
+            }
+        }
+    }
+
+    if config.show_symlink_target {
+        if let Some(target) = get_symlink_target(current_dir) {
+            let target_string = format!(" → {}", contract_path(&target, &home_dir, HOME_SYMBOL));
+            module.create_segment(
+                "symlink_target",
+                &config.symlink_target.with_value(&target_string),
+            );
+        }
+    }
 
     module.get_prefix().set_value("in ");
 
     Some(module)
 }
 
+/// Check whether the current user is denied writing to a directory
+///
+/// If the permissions can't be checked, the directory is assumed to be writable.
+#[cfg(unix)]
+fn is_read_only(dir: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = match CString::new(dir.as_os_str().as_bytes()) {
+        Ok(path) => path,
+        Err(e) => {
+            log::debug!("Unable to check permissions of {:?}: {}", dir, e);
+            return false;
+        }
+    };
+
+    // `access` checks against the real user and its groups, rather than only the mode
+    let access = if unsafe { libc::access(path.as_ptr(), libc::W_OK) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    };
+    is_write_denied(dir, access)
+}
+
+/// Check whether the result of `access` means the directory can't be written to
+///
+/// Other errors mean the permissions couldn't be checked, so they're only logged.
+#[cfg(unix)]
+fn is_write_denied(dir: &Path, access: std::io::Result<()>) -> bool {
+    let error = match access {
+        Ok(()) => return false,
+        Err(error) => error,
+    };
+
+    match error.raw_os_error() {
+        Some(libc::EACCES) | Some(libc::EROFS) => true,
+        _ => {
+            log::debug!("Unable to check permissions of {:?}: {}", dir, error);
+            false
+        }
+    }
+}
+
+/// Check whether the permissions of a directory prevent writing to it
+///
+/// If the permissions can't be read, the directory is assumed to be writable.
+#[cfg(not(unix))]
+fn is_read_only(dir: &Path) -> bool {
+    match fs::metadata(dir) {
+        Ok(metadata) => metadata.permissions().readonly(),
+        Err(e) => {
+            log::debug!("Unable to read permissions of {:?}: {}", dir, e);
+            false
+        }
+    }
+}
+
+/// Get the path a directory symlink points to, or `None` if it isn't a symlink
+///
+/// The target is returned even if it no longer exists, so that broken symlinks
+/// can still be shown.
+fn get_symlink_target(dir: &Path) -> Option<PathBuf> {
+    let metadata = fs::symlink_metadata(dir).ok()?;
+    if !metadata.file_type().is_symlink() {
+        return None;
+    }
+
+    match fs::read_link(dir) {
+        Ok(target) => Some(target),
+        Err(e) => {
+            log::debug!("Unable to read symlink {:?}: {}", dir, e);
+            None
+        }
+    }
+}
+
+/// Check whether a directory is ignored by the git repo it's in
+fn is_git_ignored(repo_root: &Path, dir: &Path) -> bool {
+    // The repo root is resolved by git, so `dir` may reach it through a symlink
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let (repo_root, dir) = (canonical(repo_root), canonical(dir));
+    let relative_dir = match dir.strip_prefix(&repo_root) {
+        Ok(relative_dir) if relative_dir != Path::new("") => relative_dir,
+        // The repo root itself can't be ignored
+        _ => return false,
+    };
+
+    match Repository::open(&repo_root)
+        .and_then(|repository| repository.is_path_ignored(relative_dir))
+    {
+        Ok(is_ignored) => is_ignored,
+        Err(e) => {
+            log::debug!("Unable to check whether {:?} is ignored: {}", dir, e);
+            false
+        }
+    }
+}
+
 /// Contract the root component of a path
 ///
 /// Replaces the `top_level_path` in a given `full_path` with the provided
@@ -182,11 +304,19 @@ fn to_fish_style(pwd_dir_length: usize, dir_string: String, truncated_dir_string
 
     components
         .into_iter()
-        .map(|word| match word {
-            "" => "",
-            _ if word.len() <= pwd_dir_length => word,
-            _ if word.starts_with('.') => &word[..=pwd_dir_length],
-            _ => &word[..pwd_dir_length],
+        .map(|word| {
+            // Keep the leading dot of hidden directories on top of `pwd_dir_length`
+            let length = if word.starts_with('.') {
+                pwd_dir_length + 1
+            } else {
+                pwd_dir_length
+            };
+
+            // Count characters rather than bytes, so multibyte names aren't split
+            match word.char_indices().nth(length) {
+                Some((end, _)) => &word[..end],
+                None => word,
+            }
         })
         .collect::<Vec<_>>()
         .join("/")
@@ -254,6 +384,53 @@ mod tests {
         assert_eq!(output, "/c");
     }
 
+    #[test]
+    fn read_only_missing_directory() {
+        let dir = Path::new("/this/directory/does/not/exist");
+        assert!(!is_read_only(dir));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn read_only_directory_owned_by_another_user() {
+        // `/` belongs to root and has write bits, which only root can use
+        let is_root = unsafe { libc::getuid() } == 0;
+        assert_eq!(is_read_only(Path::new("/")), !is_root);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn write_denied_by_access_errors() {
+        use std::io::Error;
+
+        let dir = Path::new("/starship");
+        assert!(!is_write_denied(dir, Ok(())));
+        assert!(is_write_denied(
+            dir,
+            Err(Error::from_raw_os_error(libc::EACCES))
+        ));
+        assert!(is_write_denied(
+            dir,
+            Err(Error::from_raw_os_error(libc::EROFS))
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn write_allowed_when_access_fails_otherwise() {
+        use std::io::Error;
+
+        let dir = Path::new("/starship");
+        assert!(!is_write_denied(
+            dir,
+            Err(Error::from_raw_os_error(libc::ENOENT))
+        ));
+        assert!(!is_write_denied(
+            dir,
+            Err(Error::from_raw_os_error(libc::ELOOP))
+        ));
+    }
+
     #[test]
     fn truncate_smaller_path_than_provided_length() {
         let path = "~/starship";
@@ -296,6 +473,13 @@ mod tests {
         assert_eq!(output, "engines/booster/rocket");
     }
 
+    #[test]
+    fn truncate_long_single_component() {
+        let path = format!("~/{}", "a".repeat(300));
+        let output = truncate(path, 1);
+        assert_eq!(output, "a".repeat(300));
+    }
+
     #[test]
     fn fish_style_with_user_home_contracted_path() {
         let path = "~/starship/engines/booster/rocket";
@@ -326,6 +510,20 @@ mod tests {
         assert_eq!(output, "/ab/Pa/no/in/");
     }
 
+    #[test]
+    fn fish_style_with_multibyte_directories() {
+        let path = "~/ünïcode/.日本語/rocket";
+        let output = to_fish_style(1, path.to_string(), "rocket");
+        assert_eq!(output, "~/ü/.日/");
+    }
+
+    #[test]
+    fn truncate_home_directory_as_current_directory() {
+        let home = Path::new("/Users/astronaut");
+        let output = truncate(contract_path(home, home, "~"), 1);
+        assert_eq!(output, "~");
+    }
+
     #[test]
     fn fish_style_with_duplicate_directories() {
         let path = "~/starship/tmp/C++/C++/C++";
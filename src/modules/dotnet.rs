@@ -1,9 +1,10 @@
 use std::ffi::OsStr;
 use std::iter::Iterator;
 use std::ops::Deref;
-use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
-use std::str;
+use std::path::Path;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
 
 use super::{Context, Module, RootModuleConfig};
 use crate::configs::dotnet::DotnetConfig;
@@ -12,15 +13,24 @@ type JValue = serde_json::Value;
 
 const GLOBAL_JSON_FILE: &str = "global.json";
 const PROJECT_JSON_FILE: &str = "project.json";
+const DIRECTORY_BUILD_PROPS_FILE: &str = "directory.build.props";
 
 /// A module which shows the latest (or pinned) version of the dotnet SDK
 ///
 /// Will display if any of the following files are present in
 /// the current directory:
-/// global.json, project.json, *.sln, *.csproj, *.fsproj, *.xproj
+/// global.json, project.json, Directory.Build.props, *.sln, *.csproj, *.fsproj, *.xproj
+/// or if `detect_always` is set to `true` in the module configuration.
+///
+/// A version pinned by a `global.json` is preferred, since running `dotnet` is slow.
+///
+/// When `show_tfm` is enabled, the target frameworks of the project in the current
+/// directory are also displayed, e.g. `net6.0`.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let dotnet_files = get_local_dotnet_files(context).ok()?;
-    if dotnet_files.is_empty() {
+    if dotnet_files.is_empty()
+        && !context.is_module_detect_always("dotnet", Some(&["dotnet", "--version"]))
+    {
         return None;
     }
 
@@ -30,26 +40,55 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     // Internally, this module uses its own mechanism for version detection.
     // Typically it is twice as fast as running `dotnet --version`.
     let enable_heuristic = config.heuristic;
+    let repo_root = context.get_repo().ok().and_then(|r| r.root.as_deref());
     let version = if enable_heuristic {
-        let repo_root = context
-            .get_repo()
-            .ok()
-            .and_then(|r| r.root.as_ref().map(PathBuf::as_path));
-        estimate_dotnet_version(&dotnet_files, &context.current_dir, repo_root)?
+        estimate_dotnet_version(context, &dotnet_files, repo_root)?
     } else {
-        get_version_from_cli()?
+        get_version_from_cli(context)?
     };
 
     module.set_style(config.style);
     module.create_segment("symbol", &config.symbol);
     module.create_segment("version", &config.version.with_value(&version.0));
 
+    if config.show_tfm {
+        if let Some(tfms) = get_target_frameworks(&dotnet_files) {
+            let tfm = format!(" [{}]", tfms.join(", "));
+            module.create_segment("tfm", &config.tfm.with_value(&tfm));
+        }
+    }
+
+    if config.check_sdk_mismatch && has_sdk_mismatch(context, &dotnet_files, repo_root) {
+        module.create_segment("sdk_mismatch", &config.sdk_mismatch);
+    }
+
     Some(module)
 }
 
+/// Check whether the SDK version pinned by a `global.json` differs from the version
+/// of the SDK which `dotnet --version` resolves to
+fn has_sdk_mismatch(context: &Context, files: &[DotNetFile], repo_root: Option<&Path>) -> bool {
+    let pinned_version = match files.iter().find(|f| f.file_type == FileType::GlobalJson) {
+        Some(global_json) => get_pinned_sdk_version_from_file(global_json.path),
+        None => try_find_nearby_global_json(&context.current_dir, repo_root),
+    };
+
+    match (pinned_version, get_version_from_cli(context)) {
+        (Some(pinned_version), Some(installed_version)) => {
+            log::debug!(
+                "Pinned .NET SDK version: {}, installed version: {}",
+                *pinned_version,
+                *installed_version
+            );
+            *pinned_version != *installed_version
+        }
+        _ => false,
+    }
+}
+
 fn estimate_dotnet_version<'a>(
+    context: &Context,
     files: &[DotNetFile<'a>],
-    current_dir: &Path,
     repo_root: Option<&Path>,
 ) -> Option<Version> {
     let get_file_of_type = |t: FileType| files.iter().find(|f| f.file_type == t);
@@ -58,22 +97,23 @@ fn estimate_dotnet_version<'a>(
     // but otherwise we can take any relevant file. We'll take whichever is first.
     let relevant_file = get_file_of_type(FileType::GlobalJson)
         .or_else(|| get_file_of_type(FileType::SolutionFile))
-        .or_else(|| files.iter().next())?;
+        .or_else(|| files.iter().next());
 
-    match relevant_file.file_type {
-        FileType::GlobalJson => {
-            get_pinned_sdk_version_from_file(relevant_file.path).or_else(get_latest_sdk_from_cli)
+    match relevant_file {
+        Some(file) if file.file_type == FileType::GlobalJson => {
+            get_pinned_sdk_version_from_file(file.path).or_else(|| get_latest_sdk_from_cli(context))
         }
-        FileType::SolutionFile => {
+        Some(file) if file.file_type == FileType::SolutionFile => {
             // With this heuristic, we'll assume that a "global.json" won't
             // be found in any directory above the solution file.
-            get_latest_sdk_from_cli()
+            get_latest_sdk_from_cli(context)
         }
         _ => {
-            // If we see a dotnet project, we'll check a small number of neighboring
-            // directories to see if we can find a global.json. Otherwise, assume the
-            // latest SDK is in use.
-            try_find_nearby_global_json(current_dir, repo_root).or_else(get_latest_sdk_from_cli)
+            // If we see a dotnet project, or none with `detect_always`, we'll check a small
+            // number of neighboring directories to see if we can find a global.json.
+            // Otherwise, assume the latest SDK is in use.
+            try_find_nearby_global_json(&context.current_dir, repo_root)
+                .or_else(|| get_latest_sdk_from_cli(context))
         }
     }
 }
@@ -163,6 +203,53 @@ fn get_pinned_sdk_version(json: &str) -> Option<Version> {
     }
 }
 
+/// Get the target frameworks of the first project file which sets them
+fn get_target_frameworks(files: &[DotNetFile]) -> Option<Vec<String>> {
+    files
+        .iter()
+        .filter(|f| f.file_type == FileType::ProjectFile)
+        .filter_map(|f| crate::utils::read_file(f.path).ok())
+        .find_map(|project| parse_target_frameworks(&project))
+}
+
+/// Read the `<TargetFramework>` element of an MSBuild project, or the `;` separated
+/// list in `<TargetFrameworks>` when a project targets several frameworks
+fn parse_target_frameworks(project: &str) -> Option<Vec<String>> {
+    let mut reader = Reader::from_str(project);
+    reader.trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut in_tfm_element = false;
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                in_tfm_element = matches!(e.name(), b"TargetFramework" | b"TargetFrameworks");
+            }
+            Ok(Event::Text(ref e)) if in_tfm_element => {
+                let tfms = e
+                    .unescape_and_decode(&reader)
+                    .ok()?
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|tfm| !tfm.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<String>>();
+                if !tfms.is_empty() {
+                    return Some(tfms);
+                }
+            }
+            Ok(Event::End(_)) => in_tfm_element = false,
+            Ok(Event::Eof) => return None,
+            Err(e) => {
+                log::debug!("Unable to parse project file: {}", e);
+                return None;
+            }
+            _ => (),
+        }
+        buf.clear();
+    }
+}
+
 fn get_local_dotnet_files<'a>(context: &'a Context) -> Result<Vec<DotNetFile<'a>>, std::io::Error> {
     Ok(context
         .get_dir_files()?
@@ -182,6 +269,7 @@ fn get_dotnet_file_type(path: &Path) -> Option<FileType> {
     match file_name_lower.as_ref().map(|f| f.as_ref()) {
         Some(GLOBAL_JSON_FILE) => return Some(FileType::GlobalJson),
         Some(PROJECT_JSON_FILE) => return Some(FileType::ProjectJson),
+        Some(DIRECTORY_BUILD_PROPS_FILE) => return Some(FileType::ProjectFile),
         _ => (),
     };
 
@@ -200,72 +288,44 @@ fn map_str_to_lower(value: Option<&OsStr>) -> Option<String> {
     Some(value?.to_str()?.to_ascii_lowercase())
 }
 
-fn get_version_from_cli() -> Option<Version> {
-    let version_output = match Command::new("dotnet").arg("--version").output() {
-        Ok(output) => output,
-        Err(e) => {
-            log::warn!("Failed to execute `dotnet --version`. {}", e);
-            return None;
-        }
-    };
-    let version = str::from_utf8(version_output.stdout.as_slice())
-        .ok()?
-        .trim();
-
-    let mut buffer = String::with_capacity(version.len() + 1);
-    buffer.push('v');
-    buffer.push_str(version);
-
-    Some(Version(buffer))
+fn get_version_from_cli(context: &Context) -> Option<Version> {
+    let version = context.exec_cmd("dotnet", &["--version"])?;
+    Some(Version(format!("v{}", version.trim())))
 }
 
-fn get_latest_sdk_from_cli() -> Option<Version> {
-    let mut cmd = Command::new("dotnet");
-    cmd.arg("--list-sdks")
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .stdin(Stdio::null());
-
-    let exit_code = match cmd.status() {
-        Ok(status) => status,
-        Err(e) => {
-            log::warn!("Failed to execute `dotnet --list-sdks`. {}", e);
-            return None;
-        }
-    };
-
-    if exit_code.success() {
-        let sdks_output = cmd.output().ok()?;
-        fn parse_failed<T>() -> Option<T> {
-            log::warn!("Unable to parse the output from `dotnet --list-sdks`.");
-            None
-        };
-        let latest_sdk = str::from_utf8(sdks_output.stdout.as_slice())
-            .ok()?
-            .lines()
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .last()
-            .or_else(parse_failed)?;
-        let take_until = latest_sdk.find('[').or_else(parse_failed)? - 1;
-        if take_until > 1 {
-            let version = &latest_sdk[..take_until];
-            let mut buffer = String::with_capacity(version.len() + 1);
-            buffer.push('v');
-            buffer.push_str(version);
-            Some(Version(buffer))
-        } else {
-            parse_failed()
-        }
-    } else {
+fn get_latest_sdk_from_cli(context: &Context) -> Option<Version> {
+    let sdks_output = context.exec_cmd_full("dotnet", &["--list-sdks"])?;
+    if !sdks_output.status.success() {
         // Older versions of the dotnet cli do not support the --list-sdks command
         // So, if the status code indicates failure, fall back to `dotnet --version`
         log::warn!(
             "Received a non-success exit code from `dotnet --list-sdks`. \
              Falling back to `dotnet --version`.",
         );
-        get_version_from_cli()
+        return get_version_from_cli(context);
+    }
+
+    let version = parse_latest_sdk(&sdks_output.stdout);
+    if version.is_none() {
+        log::warn!("Unable to parse the output from `dotnet --list-sdks`.");
+    }
+    version
+}
+
+/// Find the latest SDK in the output of `dotnet --list-sdks`, which lists one SDK
+/// per line in ascending order, e.g. `2.2.402 [/usr/share/dotnet/sdk]`
+fn parse_latest_sdk(sdks_stdout: &str) -> Option<Version> {
+    let latest_sdk = sdks_stdout
+        .lines()
+        .map(str::trim)
+        .rev()
+        .find(|l| !l.is_empty())?;
+    let version = latest_sdk[..latest_sdk.find('[')?].trim();
+    if version.is_empty() {
+        return None;
     }
+
+    Some(Version(format!("v{}", version)))
 }
 
 struct DotNetFile<'a> {
@@ -311,3 +371,66 @@ fn should_ignore_empty_global_json() {
     let version = get_pinned_sdk_version(json_text);
     assert!(version.is_none());
 }
+
+#[test]
+fn should_parse_latest_sdk() {
+    let sdks_text = "2.1.802 [/usr/share/dotnet/sdk]\n2.2.402 [/usr/share/dotnet/sdk]\n";
+
+    let version = parse_latest_sdk(sdks_text).unwrap();
+    assert_eq!("v2.2.402", version.0);
+}
+
+#[test]
+fn should_ignore_unparsable_sdk_list() {
+    assert!(parse_latest_sdk("").is_none());
+    assert!(parse_latest_sdk("[/usr/share/dotnet/sdk]").is_none());
+    assert!(parse_latest_sdk("2.2.402").is_none());
+}
+
+#[test]
+fn should_parse_target_framework() {
+    let project = r#"
+        <Project Sdk="Microsoft.NET.Sdk">
+          <PropertyGroup>
+            <OutputType>Exe</OutputType>
+            <TargetFramework>net6.0</TargetFramework>
+          </PropertyGroup>
+        </Project>
+    "#;
+
+    assert_eq!(
+        parse_target_frameworks(project),
+        Some(vec!["net6.0".to_string()])
+    );
+}
+
+#[test]
+fn should_parse_multiple_target_frameworks() {
+    let project = r#"
+        <Project Sdk="Microsoft.NET.Sdk">
+          <PropertyGroup>
+            <TargetFrameworks>netstandard2.0;net48;</TargetFrameworks>
+          </PropertyGroup>
+        </Project>
+    "#;
+
+    assert_eq!(
+        parse_target_frameworks(project),
+        Some(vec!["netstandard2.0".to_string(), "net48".to_string()])
+    );
+}
+
+#[test]
+fn should_ignore_project_without_target_framework() {
+    let project = r#"
+        <Project>
+          <PropertyGroup>
+            <LangVersion>latest</LangVersion>
+          </PropertyGroup>
+        </Project>
+    "#;
+
+    assert_eq!(parse_target_frameworks(project), None);
+    assert_eq!(parse_target_frameworks("<Project><TargetFramework>"), None);
+    assert_eq!(parse_target_frameworks(""), None);
+}
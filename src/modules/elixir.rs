@@ -0,0 +1,50 @@
+use std::env;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::elixir::ElixirConfig;
+
+/// The environment used by mix when `MIX_ENV` isn't set
+const DEFAULT_MIX_ENV: &str = "dev";
+
+/// Creates a module with the current mix environment of an Elixir project
+///
+/// Will display the mix environment if all of the following criteria are met:
+///     - Current directory contains a `mix.exs` file
+///     - The `MIX_ENV` environment variable is set
+///
+/// The default `dev` environment is hidden when `ignore_default_env` is set.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_mix_project = context
+        .try_begin_scan("elixir", None)?
+        .set_files(&["mix.exs"])
+        .is_match();
+
+    if !is_mix_project {
+        return None;
+    }
+
+    let mix_env = env::var("MIX_ENV")
+        .ok()
+        .filter(|mix_env| !mix_env.is_empty())?;
+
+    let mut module = context.new_module("elixir");
+    let config: ElixirConfig = ElixirConfig::try_load(module.config);
+
+    if config.ignore_default_env && mix_env == DEFAULT_MIX_ENV {
+        return None;
+    }
+
+    let style = config
+        .env_styles
+        .get(mix_env.as_str())
+        .copied()
+        .unwrap_or(config.style);
+
+    module.set_style(style);
+    module.get_prefix().set_value("via ");
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("mix_env", &config.mix_env.with_value(&mix_env));
+
+    Some(module)
+}
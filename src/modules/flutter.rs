@@ -0,0 +1,128 @@
+use yaml_rust::YamlLoader;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::flutter::FlutterConfig;
+use crate::utils;
+
+/// Creates a module with the current Flutter SDK channel
+///
+/// Will display the Flutter channel if the current directory contains a
+/// `pubspec.yaml` file and the channel can be found in the project's `.metadata` file.
+///
+/// `flutter --version` is very slow, so the Flutter version (and the channel, if
+/// `.metadata` doesn't contain it) is only read from it when `show_version` is enabled.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_flutter_project = context
+        .try_begin_scan("flutter", None)?
+        .set_files(&["pubspec.yaml"])
+        .is_match();
+
+    if !is_flutter_project {
+        return None;
+    }
+
+    let mut module = context.new_module("flutter");
+    let config: FlutterConfig = FlutterConfig::try_load(module.config);
+
+    let metadata_channel = utils::read_file(context.current_dir.join(".metadata"))
+        .ok()
+        .and_then(|metadata| parse_metadata_channel(&metadata));
+
+    let (version, channel) = if config.show_version {
+        match get_flutter_version(context).and_then(|output| parse_flutter_version(&output)) {
+            Some((version, cli_channel)) => (Some(version), metadata_channel.or(Some(cli_channel))),
+            None => (None, metadata_channel),
+        }
+    } else {
+        (None, metadata_channel)
+    };
+
+    if version.is_none() && channel.is_none() {
+        return None;
+    }
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+
+    if let Some(version) = &version {
+        module.create_segment("version", &config.version.with_value(version));
+    }
+    if let Some(channel) = channel {
+        let channel = if version.is_some() {
+            format!(" ({})", channel)
+        } else {
+            format!("({})", channel)
+        };
+        module.create_segment("channel", &config.channel.with_value(&channel));
+    }
+
+    Some(module)
+}
+
+/// Read the channel from a `.metadata` file, which looks like this:
+///
+/// version:
+///   revision: 27321ebbad34b0a3fafe99fac037102196d655ff
+///   channel: stable
+///
+/// project_type: app
+fn parse_metadata_channel(metadata: &str) -> Option<String> {
+    let yaml_docs = YamlLoader::load_from_str(metadata).ok()?;
+    let channel = yaml_docs.first()?["version"]["channel"].as_str()?.trim();
+
+    if channel.is_empty() {
+        return None;
+    }
+
+    Some(channel.to_string())
+}
+
+fn get_flutter_version(context: &Context) -> Option<String> {
+    context.exec_cmd("flutter", &["--version"])
+}
+
+/// Read the version and channel from the output of `flutter --version`, which looks like this:
+///
+/// Flutter 1.12.13+hotfix.5 • channel stable • https://github.com/flutter/flutter.git
+/// Framework • revision 27321ebbad (5 weeks ago) • 2019-12-10 18:15:01 -0800
+/// ...
+fn parse_flutter_version(flutter_stdout: &str) -> Option<(String, String)> {
+    let mut fields = flutter_stdout.lines().next()?.split('•').map(str::trim);
+
+    let version = fields.next()?;
+    let channel = fields.next()?;
+    if !version.starts_with("Flutter ") || !channel.starts_with("channel ") {
+        return None;
+    }
+
+    let version = version.trim_start_matches("Flutter ").trim();
+    let channel = channel.trim_start_matches("channel ").trim();
+
+    Some((format!("v{}", version), channel.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_metadata_channel() {
+        let input = "# This file tracks properties of this Flutter project.\nversion:\n  revision: 27321ebbad34b0a3fafe99fac037102196d655ff\n  channel: beta\n\nproject_type: app\n";
+        assert_eq!(parse_metadata_channel(input), Some("beta".to_string()));
+
+        let input = "project_type: app\n";
+        assert_eq!(parse_metadata_channel(input), None);
+    }
+
+    #[test]
+    fn test_parse_flutter_version() {
+        let input = "Flutter 1.12.13+hotfix.5 • channel stable • https://github.com/flutter/flutter.git\nFramework • revision 27321ebbad (5 weeks ago) • 2019-12-10 18:15:01 -0800\n";
+        assert_eq!(
+            parse_flutter_version(input),
+            Some(("v1.12.13+hotfix.5".to_string(), "stable".to_string()))
+        );
+
+        assert_eq!(parse_flutter_version(""), None);
+    }
+}
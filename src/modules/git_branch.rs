@@ -1,13 +1,32 @@
+use std::path::{Path, PathBuf};
 use unicode_segmentation::UnicodeSegmentation;
 
+use super::utils::vcs::{self, Vcs};
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::git_branch::GitBranchConfig;
+use crate::utils;
+
+/// The remote which `git clone` sets up, which isn't worth showing
+const DEFAULT_REMOTE: &str = "origin";
 
 /// Creates a module with the Git branch in the current directory
 ///
 /// Will display the branch name if the current directory is a git repo
+///
+/// When `show_worktree` is enabled, the name of the worktree is also displayed if
+/// the current directory is in a linked worktree rather than the main one.
+///
+/// When `show_remote` is enabled, the remote the branch tracks is displayed if it
+/// isn't `origin`, such as when tracking the upstream repository of a fork.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    // A checkout of another VCS nested in the repo is shown by its own module instead
+    if let Some((vcs, _)) = vcs::find_vcs(&context.current_dir) {
+        if vcs != Vcs::Git {
+            return None;
+        }
+    }
+
     let mut module = context.new_module("git_branch");
     let config = GitBranchConfig::try_load(module.config);
     module.set_style(config.style);
@@ -44,9 +63,50 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         &config.branch_name.with_value(&truncated_and_symbol),
     );
 
+    if config.show_remote {
+        if let Some(remote) = repo
+            .remote
+            .as_ref()
+            .filter(|remote| *remote != DEFAULT_REMOTE)
+        {
+            module.create_segment("remote", &config.remote.with_value(&format!(":{}", remote)));
+        }
+    }
+
+    if config.show_worktree {
+        if let Some(worktree_name) = repo.root.as_ref().and_then(|root| get_worktree_name(root)) {
+            module.create_segment(
+                "worktree",
+                &config.worktree.with_value(&format!(" ({})", worktree_name)),
+            );
+        }
+    }
+
     Some(module)
 }
 
+/// Get the name of a linked worktree, which is the name of its root directory
+///
+/// The `.git` of a linked worktree is a file pointing to its git directory, inside
+/// the main repository's `.git/worktrees`. That directory holds a `commondir` file,
+/// which tells it apart from the git directory of a submodule.
+fn get_worktree_name(repo_root: &Path) -> Option<String> {
+    let dot_git = utils::read_file(repo_root.join(".git")).ok()?;
+    let git_dir = dot_git.trim().trim_start_matches("gitdir:").trim();
+    if git_dir.is_empty() {
+        return None;
+    }
+
+    let git_dir = repo_root.join(PathBuf::from(git_dir));
+    if !git_dir.join("commondir").is_file() {
+        return None;
+    }
+
+    repo_root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
 fn get_graphemes(text: &str, length: usize) -> String {
     UnicodeSegmentation::graphemes(text, true)
         .take(length)
@@ -57,3 +117,53 @@ fn get_graphemes(text: &str, length: usize) -> String {
 fn graphemes_len(text: &str) -> usize {
     UnicodeSegmentation::graphemes(&text[..], true).count()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io;
+
+    /// Create the layout of a repository with one linked worktree and one submodule
+    fn create_worktree_layout() -> io::Result<tempfile::TempDir> {
+        let dir = tempfile::tempdir()?;
+        let worktree_git_dir = dir.path().join("main/.git/worktrees/feature");
+        fs::create_dir_all(&worktree_git_dir)?;
+        fs::write(worktree_git_dir.join("commondir"), "../..\n")?;
+        fs::write(worktree_git_dir.join("HEAD"), "ref: refs/heads/feature\n")?;
+
+        fs::create_dir(dir.path().join("feature"))?;
+        fs::write(
+            dir.path().join("feature/.git"),
+            "gitdir: ../main/.git/worktrees/feature\n",
+        )?;
+
+        let submodule_git_dir = dir.path().join("main/.git/modules/lib");
+        fs::create_dir_all(&submodule_git_dir)?;
+        fs::create_dir(dir.path().join("main/lib"))?;
+        fs::write(
+            dir.path().join("main/lib/.git"),
+            "gitdir: ../.git/modules/lib\n",
+        )?;
+
+        Ok(dir)
+    }
+
+    #[test]
+    fn worktree_name_of_linked_worktree() -> io::Result<()> {
+        let dir = create_worktree_layout()?;
+        assert_eq!(
+            get_worktree_name(&dir.path().join("feature")),
+            Some("feature".to_string())
+        );
+        dir.close()
+    }
+
+    #[test]
+    fn no_worktree_name_of_main_worktree_or_submodule() -> io::Result<()> {
+        let dir = create_worktree_layout()?;
+        assert_eq!(get_worktree_name(&dir.path().join("main")), None);
+        assert_eq!(get_worktree_name(&dir.path().join("main/lib")), None);
+        dir.close()
+    }
+}
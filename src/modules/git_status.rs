@@ -1,4 +1,7 @@
-use git2::{Repository, Status};
+use git2::{AttrCheckFlags, Repository, Status, SubmoduleIgnore};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
 
 use super::{Context, Module, RootModuleConfig};
 
@@ -19,6 +22,12 @@ use crate::configs::git_status::{CountConfig, GitStatusConfig};
 ///   - `+` — A new file has been added to the staging area
 ///   - `»` — A renamed file has been added to the staging area
 ///   - `✘` — A file's deletion has been added to the staging area
+///   - `LFS` — The repository uses Git LFS
+///   - `⊂` — A submodule isn't initialized or isn't at the commit the repo records
+///     (only checked when `show_submodules` is enabled)
+///
+/// Any of these indicators can be hidden by adding its name to `disabled_indicators`.
+/// When `show_clean` is enabled, `✓` is displayed if the working tree has no changes.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let repo = context.get_repo().ok()?;
     let branch_name = repo.branch.as_ref()?;
@@ -38,6 +47,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         .set_style(config.style);
     module.set_style(config.style);
 
+    let is_enabled = |indicator: &str| !config.disabled_indicators.contains(&indicator);
+
     let ahead_behind = get_ahead_behind(&repository, branch_name);
     if ahead_behind == Ok((0, 0)) {
         log::trace!("No ahead/behind found");
@@ -57,13 +68,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     // Add the conflicted segment
     if let Ok(repo_status) = repo_status {
-        create_segment_with_count(
-            &mut module,
-            "conflicted",
-            repo_status.conflicted,
-            &config.conflicted,
-            config.conflicted_count,
-        );
+        if is_enabled("conflicted") {
+            create_segment_with_count(
+                &mut module,
+                "conflicted",
+                repo_status.conflicted,
+                &config.conflicted,
+                config.conflicted_count,
+            );
+        }
     }
 
     // Add the ahead/behind segment
@@ -95,7 +108,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         };
 
         if ahead > 0 && behind > 0 {
-            module.create_segment("diverged", &config.diverged);
+            if is_enabled("diverged") {
+                module.create_segment("diverged", &config.diverged);
+            }
 
             if config.show_sync_count {
                 add_ahead(&mut module);
@@ -103,61 +118,90 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             }
         }
 
-        if ahead > 0 && behind == 0 {
+        if ahead > 0 && behind == 0 && is_enabled("ahead") {
             add_ahead(&mut module);
         }
 
-        if behind > 0 && ahead == 0 {
+        if behind > 0 && ahead == 0 && is_enabled("behind") {
             add_behind(&mut module);
         }
     }
 
     // Add the stashed segment
-    if stash_object.is_ok() {
+    if stash_object.is_ok() && is_enabled("stashed") {
         module.create_segment("stashed", &config.stashed);
     }
 
     // Add all remaining status segments
     if let Ok(repo_status) = repo_status {
-        create_segment_with_count(
-            &mut module,
-            "deleted",
-            repo_status.deleted,
-            &config.deleted,
-            config.deleted_count,
-        );
-
-        create_segment_with_count(
-            &mut module,
-            "renamed",
-            repo_status.renamed,
-            &config.renamed,
-            config.renamed_count,
-        );
-
-        create_segment_with_count(
-            &mut module,
-            "modified",
-            repo_status.modified,
-            &config.modified,
-            config.modified_count,
-        );
-
-        create_segment_with_count(
-            &mut module,
-            "staged",
-            repo_status.staged,
-            &config.staged,
-            config.staged_count,
-        );
-
-        create_segment_with_count(
-            &mut module,
-            "untracked",
-            repo_status.untracked,
-            &config.untracked,
-            config.untracked_count,
-        );
+        let indicators = [
+            (
+                "deleted",
+                repo_status.deleted,
+                &config.deleted,
+                config.deleted_count,
+            ),
+            (
+                "renamed",
+                repo_status.renamed,
+                &config.renamed,
+                config.renamed_count,
+            ),
+            (
+                "modified",
+                repo_status.modified,
+                &config.modified,
+                config.modified_count,
+            ),
+            (
+                "staged",
+                repo_status.staged,
+                &config.staged,
+                config.staged_count,
+            ),
+            (
+                "untracked",
+                repo_status.untracked,
+                &config.untracked,
+                config.untracked_count,
+            ),
+        ];
+
+        for (name, count, segment, count_config) in indicators.iter() {
+            if is_enabled(name) {
+                create_segment_with_count(&mut module, name, *count, segment, *count_config);
+            }
+        }
+
+        if config.show_clean && repo_status.is_clean() {
+            module.create_segment("clean", &config.clean);
+        }
+    }
+
+    // Add the LFS segment
+    if is_enabled("lfs") && uses_lfs(repo_root) {
+        module.create_segment("lfs", &config.lfs);
+
+        // Finding files which haven't been pulled means checking every file in
+        // the index, so it's only done when the count is enabled
+        if config.lfs_count.enabled {
+            let count = count_lfs_pointers(&repository, repo_root);
+            if count > 0 {
+                module.create_segment(
+                    "lfs_count",
+                    &SegmentConfig::new(&count.to_string()).with_style(config.lfs_count.style),
+                );
+            }
+        }
+    }
+
+    // Checking submodules means opening each of them, so it's only done when enabled
+    if config.show_submodules
+        && is_enabled("submodules")
+        && repo_root.join(".gitmodules").is_file()
+        && has_outdated_submodules(&repository)
+    {
+        module.create_segment("submodules", &config.submodules);
     }
 
     if module.is_empty() {
@@ -198,25 +242,13 @@ fn get_repo_status(repository: &Repository) -> Result<RepoStatus, git2::Error> {
     status_options.renames_head_to_index(true);
     status_options.renames_index_to_workdir(true);
 
-    let statuses: Vec<Status> = repository
-        .statuses(Some(&mut status_options))?
-        .iter()
-        .map(|s| s.status())
-        .collect();
-
-    if statuses.is_empty() {
-        return Err(git2::Error::from_str("Repo has no status"));
+    // Every file's status comes from a single scan of the repo, and is counted
+    // in one pass
+    let mut repo_status = RepoStatus::default();
+    for entry in repository.statuses(Some(&mut status_options))?.iter() {
+        repo_status.add(entry.status());
     }
 
-    let repo_status: RepoStatus = RepoStatus {
-        conflicted: statuses.iter().filter(|s| is_conflicted(**s)).count(),
-        deleted: statuses.iter().filter(|s| is_deleted(**s)).count(),
-        renamed: statuses.iter().filter(|s| is_renamed(**s)).count(),
-        modified: statuses.iter().filter(|s| is_modified(**s)).count(),
-        staged: statuses.iter().filter(|s| is_staged(**s)).count(),
-        untracked: statuses.iter().filter(|s| is_untracked(**s)).count(),
-    };
-
     Ok(repo_status)
 }
 
@@ -244,6 +276,82 @@ fn is_untracked(status: Status) -> bool {
     status.is_wt_new()
 }
 
+/// Checks whether the `.gitattributes` at the root of the repo uses the LFS filter
+fn uses_lfs(repo_root: &Path) -> bool {
+    match crate::utils::read_file(repo_root.join(".gitattributes")) {
+        Ok(gitattributes) => has_lfs_filter(&gitattributes),
+        Err(_) => false,
+    }
+}
+
+fn has_lfs_filter(gitattributes: &str) -> bool {
+    gitattributes.lines().any(|line| {
+        let line = line.trim();
+        !line.starts_with('#') && line.split_whitespace().any(|attr| attr == "filter=lfs")
+    })
+}
+
+/// Counts the LFS-tracked files in the working directory which are still
+/// pointer files, because their contents haven't been pulled yet
+fn count_lfs_pointers(repository: &Repository, repo_root: &Path) -> usize {
+    let index = match repository.index() {
+        Ok(index) => index,
+        Err(e) => {
+            log::debug!("Unable to read the git index: {}", e);
+            return 0;
+        }
+    };
+
+    index
+        .iter()
+        .filter_map(|entry| String::from_utf8(entry.path).ok())
+        .filter(|path| {
+            repository
+                .get_attr(Path::new(path), "filter", AttrCheckFlags::default())
+                .ok()
+                .and_then(|filter| filter)
+                == Some("lfs")
+        })
+        .filter(|path| is_lfs_pointer(&repo_root.join(path)))
+        .count()
+}
+
+fn is_lfs_pointer(path: &Path) -> bool {
+    const LFS_POINTER_HEADER: &[u8] = b"version https://git-lfs.github.com/spec/v1";
+
+    let mut header = [0; LFS_POINTER_HEADER.len()];
+    match File::open(path).and_then(|mut file| file.read_exact(&mut header)) {
+        Ok(()) => header == LFS_POINTER_HEADER,
+        Err(_) => false,
+    }
+}
+
+/// Checks whether any submodule hasn't been initialized, or has a different commit
+/// checked out than the one recorded in the index. Changes inside a submodule's
+/// working directory are ignored.
+fn has_outdated_submodules(repository: &Repository) -> bool {
+    let submodules = match repository.submodules() {
+        Ok(submodules) => submodules,
+        Err(e) => {
+            log::debug!("Unable to read the submodules: {}", e);
+            return false;
+        }
+    };
+
+    submodules
+        .iter()
+        .filter_map(|submodule| submodule.name())
+        .any(
+            |name| match repository.submodule_status(name, SubmoduleIgnore::Dirty) {
+                Ok(status) => status.is_wd_uninitialized() || status.is_wd_modified(),
+                Err(e) => {
+                    log::debug!("Unable to get the status of submodule {}: {}", name, e);
+                    false
+                }
+            },
+        )
+}
+
 /// Compares the current branch with the branch it is tracking to determine how
 /// far ahead or behind it is in relation
 fn get_ahead_behind(
@@ -269,3 +377,40 @@ struct RepoStatus {
     staged: usize,
     untracked: usize,
 }
+
+impl RepoStatus {
+    fn add(&mut self, status: Status) {
+        self.conflicted += is_conflicted(status) as usize;
+        self.deleted += is_deleted(status) as usize;
+        self.renamed += is_renamed(status) as usize;
+        self.modified += is_modified(status) as usize;
+        self.staged += is_staged(status) as usize;
+        self.untracked += is_untracked(status) as usize;
+    }
+
+    /// Whether there are no changes in the working directory or staging area
+    fn is_clean(&self) -> bool {
+        self.conflicted == 0
+            && self.deleted == 0
+            && self.renamed == 0
+            && self.modified == 0
+            && self.staged == 0
+            && self.untracked == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_lfs_filter() {
+        let gitattributes = "*.psd filter=lfs diff=lfs merge=lfs -text\n*.rs text\n";
+        assert!(has_lfs_filter(gitattributes));
+
+        let gitattributes = "# *.psd filter=lfs diff=lfs merge=lfs -text\n*.rs text\n";
+        assert!(!has_lfs_filter(gitattributes));
+
+        assert!(!has_lfs_filter(""));
+    }
+}
@@ -0,0 +1,61 @@
+use git2::Repository;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::git_tag::GitTagConfig;
+
+/// Creates a module with the Git tag pointing at HEAD
+///
+/// When several tags point at HEAD, the lexically latest one is shown, or all of
+/// them when `show_all` is enabled. Nothing is shown if HEAD isn't tagged.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("git_tag");
+    let config = GitTagConfig::try_load(module.config);
+
+    let repo = context.get_repo().ok()?;
+    let repo_root = repo.root.as_ref()?;
+    let repository = Repository::open(repo_root).ok()?;
+
+    let tags = get_head_tags(&repository);
+    let tag_name = if config.show_all {
+        tags.join(config.separator)
+    } else {
+        tags.last()?.to_string()
+    };
+    if tag_name.is_empty() {
+        return None;
+    }
+
+    module.set_style(config.style);
+    module.get_prefix().set_value("");
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("tag_name", &config.tag_name.with_value(&tag_name));
+
+    Some(module)
+}
+
+/// Get the names of the tags pointing at HEAD, sorted lexically
+///
+/// Annotated tags are peeled, so they're matched by the commit they point at.
+fn get_head_tags(repository: &Repository) -> Vec<String> {
+    let head_id = match repository.head().and_then(|head| head.peel_to_commit()) {
+        Ok(commit) => commit.id(),
+        Err(_) => return Vec::new(),
+    };
+    let references = match repository.references_glob("refs/tags/*") {
+        Ok(references) => references,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut tags: Vec<String> = references
+        .filter_map(|reference| {
+            let reference = reference.ok()?;
+            if reference.peel_to_commit().ok()?.id() != head_id {
+                return None;
+            }
+            reference.shorthand().map(str::to_string)
+        })
+        .collect();
+    tags.sort();
+    tags
+}
@@ -1,5 +1,3 @@
-use std::process::Command;
-
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::go::GoConfig;
@@ -10,14 +8,22 @@ use crate::configs::go::GoConfig;
 ///     - Current directory contains a `go.mod` file
 ///     - Current directory contains a `go.sum` file
 ///     - Current directory contains a `glide.yaml` file
-///     - Current directory contains a `Gopkg.yml` file
+///     - Current directory contains a `Gopkg.toml` or `Gopkg.yml` file
 ///     - Current directory contains a `Gopkg.lock` file
 ///     - Current directory contains a `Godeps` directory
 ///     - Current directory contains a file with the `.go` extension
+///     - `detect_always` is set to `true` in the module configuration
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_go_project = context
-        .try_begin_scan()?
-        .set_files(&["go.mod", "go.sum", "glide.yaml", "Gopkg.yml", "Gopkg.lock"])
+        .try_begin_scan("golang", Some(&["go", "version"]))?
+        .set_files(&[
+            "go.mod",
+            "go.sum",
+            "glide.yaml",
+            "Gopkg.toml",
+            "Gopkg.yml",
+            "Gopkg.lock",
+        ])
         .set_extensions(&["go"])
         .set_folders(&["Godeps"])
         .is_match();
@@ -29,23 +35,16 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("golang");
     let config: GoConfig = GoConfig::try_load(module.config);
 
+    let go_version = context.exec_cmd("go", &["version"])?;
+    let formatted_version = format_go_version(&go_version)?;
+
     module.set_style(config.style);
     module.create_segment("symbol", &config.symbol);
-
-    let formatted_version = format_go_version(&get_go_version()?)?;
     module.create_segment("version", &config.version.with_value(&formatted_version));
 
     Some(module)
 }
 
-fn get_go_version() -> Option<String> {
-    Command::new("go")
-        .arg("version")
-        .output()
-        .ok()
-        .and_then(|output| String::from_utf8(output.stdout).ok())
-}
-
 fn format_go_version(go_stdout: &str) -> Option<String> {
     // go version output looks like this:
     // go version go1.13.3 linux/amd64
@@ -71,5 +70,14 @@ mod tests {
     fn test_format_go_version() {
         let input = "go version go1.12 darwin/amd64";
         assert_eq!(format_go_version(input), Some("v1.12".to_string()));
+
+        let input = "go version go1.12.1 darwin/amd64\n";
+        assert_eq!(format_go_version(input), Some("v1.12.1".to_string()));
+    }
+
+    #[test]
+    fn test_format_missing_go_version() {
+        assert_eq!(format_go_version(""), None);
+        assert_eq!(format_go_version("go: command not found"), None);
     }
 }
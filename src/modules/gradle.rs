@@ -0,0 +1,80 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::gradle::GradleConfig;
+use crate::utils;
+
+const WRAPPER_PROPERTIES_FILE: &str = "gradle/wrapper/gradle-wrapper.properties";
+
+/// Creates a module with the current Gradle wrapper version
+///
+/// Will display the Gradle wrapper version if any of the following criteria are met:
+///     - Current directory contains a `gradlew` file
+///     - Current directory contains a `build.gradle` file
+///     - Current directory contains a `build.gradle.kts` file
+///
+/// The version is read from the `distributionUrl` in
+/// `gradle/wrapper/gradle-wrapper.properties`, without running Gradle.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_gradle_project = context
+        .try_begin_scan("gradle", None)?
+        .set_files(&["gradlew", "build.gradle", "build.gradle.kts"])
+        .is_match();
+
+    if !is_gradle_project {
+        return None;
+    }
+
+    let distribution_url = utils::read_file_value(
+        context.current_dir.join(WRAPPER_PROPERTIES_FILE),
+        Some("distributionUrl"),
+    )?;
+    let formatted_version = format_gradle_version(&distribution_url)?;
+
+    let mut module = context.new_module("gradle");
+    let config: GradleConfig = GradleConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("version", &config.version.with_value(&formatted_version));
+
+    Some(module)
+}
+
+fn format_gradle_version(distribution_url: &str) -> Option<String> {
+    // distributionUrl looks like this:
+    // https\://services.gradle.org/distributions/gradle-6.3-bin.zip
+
+    let version = distribution_url
+        // return "gradle-6.3-bin.zip"
+        .rsplit('/')
+        .next()?
+        // return "6.3-bin.zip"
+        .trim_start_matches("gradle-")
+        // return "6.3"
+        .trim_end_matches(".zip")
+        .trim_end_matches("-bin")
+        .trim_end_matches("-all");
+
+    if version.is_empty() {
+        return None;
+    }
+
+    Some(format!("v{}", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_gradle_version() {
+        let input = "https\\://services.gradle.org/distributions/gradle-6.3-bin.zip";
+        assert_eq!(format_gradle_version(input), Some("v6.3".to_string()));
+
+        let input = "https\\://services.gradle.org/distributions/gradle-6.4-rc-1-all.zip";
+        assert_eq!(format_gradle_version(input), Some("v6.4-rc-1".to_string()));
+
+        let input = "https\\://services.gradle.org/distributions/";
+        assert_eq!(format_gradle_version(input), None);
+    }
+}
@@ -0,0 +1,102 @@
+use yaml_rust::YamlLoader;
+
+use super::kubernetes::get_current_kube_context;
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::helm::HelmConfig;
+use crate::utils;
+
+/// The namespace kubectl and helm use when the kube context doesn't set one
+const DEFAULT_NAMESPACE: &str = "default";
+
+/// Creates a module with the Helm chart and the Kubernetes namespace it would be
+/// installed into
+///
+/// Will display the chart and namespace if all of the following criteria are met:
+///     - Current directory contains a `Chart.yaml` file
+///     - A kube context is set in `$KUBECONFIG` or `~/.kube/config`
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_helm_chart = context
+        .try_begin_scan("helm", None)?
+        .set_files(&["Chart.yaml"])
+        .is_match();
+
+    if !is_helm_chart {
+        return None;
+    }
+
+    let (kube_ctx, kube_ns) = get_current_kube_context()?;
+
+    let mut module = context.new_module("helm");
+    let config: HelmConfig = HelmConfig::try_load(module.config);
+    module.set_style(config.style);
+
+    let chart = get_chart_name(context)?;
+    let namespace = if kube_ns.is_empty() {
+        DEFAULT_NAMESPACE
+    } else {
+        &kube_ns
+    };
+    let namespace = if config.show_context {
+        format!("{}/{}", kube_ctx, namespace)
+    } else {
+        namespace.to_string()
+    };
+
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("chart", &config.chart.with_value(&chart));
+    module.create_segment(
+        "namespace",
+        &config
+            .namespace
+            .with_value(&format!("{}{}", config.separator, namespace)),
+    );
+
+    Some(module)
+}
+
+/// Get the chart's name from `Chart.yaml`, falling back to the name of the
+/// directory it's in
+fn get_chart_name(context: &Context) -> Option<String> {
+    let contents = utils::read_file(context.current_dir.join("Chart.yaml")).ok()?;
+
+    parse_chart_name(&contents).or_else(|| {
+        context
+            .current_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    })
+}
+
+fn parse_chart_name(contents: &str) -> Option<String> {
+    let yaml_docs = YamlLoader::load_from_str(contents).ok()?;
+    let chart = yaml_docs.first()?;
+
+    chart["name"]
+        .as_str()
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chart_name() {
+        let input = r#"
+apiVersion: v2
+name: rocket
+description: A Helm chart for launching rockets
+version: 0.1.0
+"#;
+        assert_eq!(parse_chart_name(input), Some("rocket".to_string()));
+    }
+
+    #[test]
+    fn test_parse_chart_name_missing() {
+        assert_eq!(parse_chart_name("apiVersion: v2\nversion: 0.1.0\n"), None);
+        assert_eq!(parse_chart_name("name: \"\"\n"), None);
+        assert_eq!(parse_chart_name(""), None);
+    }
+}
@@ -0,0 +1,34 @@
+use super::{Context, Module, RootModuleConfig, SkipReason};
+
+use crate::configs::history::HistoryConfig;
+
+/// Creates a module with the history number of the next command
+///
+/// The number is passed by the shell with `--history-number`, so nothing is
+/// shown in shells which don't provide it.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("history");
+    let config: HistoryConfig = HistoryConfig::try_load(module.config);
+
+    if config.disabled {
+        context.set_skip_reason("history", SkipReason::Disabled);
+        return None;
+    }
+
+    let history_number = context
+        .properties
+        .get("history_number")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment(
+        "number",
+        &config.number.with_value(&history_number.to_string()),
+    );
+    module.get_prefix().set_value("");
+
+    Some(module)
+}
@@ -1,9 +1,9 @@
 use crate::configs::java::JavaConfig;
-use std::process::Command;
-use std::process::Output;
 
 use super::{Context, Module, RootModuleConfig, SegmentConfig};
 
+use crate::command::CommandOutput;
+
 use crate::modules::utils::java_version_parser;
 
 /// Creates a module with the current Java version
@@ -11,9 +11,11 @@ use crate::modules::utils::java_version_parser;
 /// Will display the Java version if any of the following criteria are met:
 ///     - Current directory contains a file with a `.java`, `.class` or `.jar` extension
 ///     - Current directory contains a `pom.xml`, `build.gradle` or `build.sbt` file
+///     - `detect_always` is set to `true` in the module configuration
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let java_command = get_java_command();
     let is_java_project = context
-        .try_begin_scan()?
+        .try_begin_scan("java", Some(&[&java_command, "-Xinternalversion"]))?
         .set_files(&["pom.xml", "build.gradle", "build.sbt"])
         .set_extensions(&["java", "class", "jar"])
         .is_match();
@@ -22,7 +24,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    match get_java_version() {
+    match get_java_version(context, &java_command) {
         Some(java_version) => {
             let mut module = context.new_module("java");
             let config: JavaConfig = JavaConfig::try_load(module.config);
@@ -38,26 +40,24 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     }
 }
 
-fn get_java_version() -> Option<String> {
-    let java_command = match std::env::var("JAVA_HOME") {
+/// Get the `java` in `$JAVA_HOME`, or otherwise the one on the `PATH`
+fn get_java_command() -> String {
+    match std::env::var("JAVA_HOME") {
         Ok(java_home) => format!("{}/bin/java", java_home),
         Err(_) => String::from("java"),
-    };
-
-    match Command::new(java_command).arg("-Xinternalversion").output() {
-        Ok(output) => Some(combine_outputs(output)),
-        Err(_) => None,
     }
 }
 
+fn get_java_version(context: &Context, java_command: &str) -> Option<String> {
+    let output = context.exec_cmd_full(java_command, &["-Xinternalversion"])?;
+    Some(combine_outputs(output))
+}
+
 /// Combines the standard and error outputs.
 ///
 /// This is due some Java vendors using `STDERR` as the output.
-fn combine_outputs(output: Output) -> String {
-    let std_out = String::from_utf8(output.stdout).unwrap();
-    let std_err = String::from_utf8(output.stderr).unwrap();
-
-    format!("{}{}", std_out, std_err)
+fn combine_outputs(output: CommandOutput) -> String {
+    format!("{}{}", output.stdout, output.stderr)
 }
 
 /// Extract the java version from `java_out`.
@@ -0,0 +1,82 @@
+use std::env;
+use std::path::Path;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::k8s_pod::K8sPodConfig;
+use crate::utils;
+
+/// The directory Kubernetes mounts the pod's service account credentials in
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+
+/// Creates a module with the Kubernetes pod the shell is running in
+///
+/// Will display the pod iff the service account of the pod is mounted, as it is
+/// unless `automountServiceAccountToken` is disabled. The pod name is the hostname
+/// Kubernetes gives the container.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let hostname = env::var("HOSTNAME")
+        .ok()
+        .or_else(|| gethostname::gethostname().into_string().ok())?;
+    let (pod, namespace) = get_pod(Path::new(SERVICE_ACCOUNT_DIR), &hostname)?;
+
+    let mut module = context.new_module("k8s_pod");
+    let config: K8sPodConfig = K8sPodConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.get_prefix().set_value("in ");
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("pod", &config.pod.with_value(&pod));
+    module.create_segment(
+        "namespace",
+        &config.namespace.with_value(&format!(" ({})", namespace)),
+    );
+
+    Some(module)
+}
+
+/// Get the name and namespace of the pod, from its hostname and the namespace file
+/// of the service account mounted in `service_account_dir`
+fn get_pod(service_account_dir: &Path, hostname: &str) -> Option<(String, String)> {
+    let namespace = utils::read_file(service_account_dir.join("namespace")).ok()?;
+    let namespace = namespace.trim();
+    let pod = hostname.trim();
+    if namespace.is_empty() || pod.is_empty() {
+        return None;
+    }
+
+    Some((pod.to_string(), namespace.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture_path(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/k8s_pod")
+            .join(name)
+    }
+
+    #[test]
+    fn test_get_pod() {
+        assert_eq!(
+            get_pod(&fixture_path("serviceaccount"), "web-5d4f8b7c9-x2k4q"),
+            Some(("web-5d4f8b7c9-x2k4q".to_string(), "production".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_get_pod_without_service_account() {
+        assert_eq!(
+            get_pod(&fixture_path("missing"), "web-5d4f8b7c9-x2k4q"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_pod_without_hostname() {
+        assert_eq!(get_pod(&fixture_path("serviceaccount"), ""), None);
+    }
+}
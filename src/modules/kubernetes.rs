@@ -4,7 +4,7 @@ use yaml_rust::YamlLoader;
 use std::env;
 use std::path;
 
-use super::{Context, Module, RootModuleConfig};
+use super::{Context, Module, RootModuleConfig, SkipReason};
 
 use crate::configs::kubernetes::KubernetesConfig;
 use crate::utils;
@@ -43,16 +43,22 @@ fn parse_kubectl_file(filename: &path::PathBuf) -> Option<(String, String)> {
     get_kube_context(&contents)
 }
 
-pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
-    let kube_cfg = match env::var("KUBECONFIG") {
+/// Get the current context and its namespace from the kubeconfig file, which is
+/// `$KUBECONFIG` if it's set, or `~/.kube/config` otherwise
+pub fn get_current_kube_context() -> Option<(String, String)> {
+    match env::var("KUBECONFIG") {
         Ok(paths) => env::split_paths(&paths)
             .filter_map(|filename| parse_kubectl_file(&filename))
-            .nth(0),
+            .next(),
         Err(_) => {
             let filename = dirs::home_dir()?.join(".kube").join("config");
             parse_kubectl_file(&filename)
         }
-    };
+    }
+}
+
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let kube_cfg = get_current_kube_context();
 
     match kube_cfg {
         Some(kube_cfg) => {
@@ -61,6 +67,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             let mut module = context.new_module("kubernetes");
             let config: KubernetesConfig = KubernetesConfig::try_load(module.config);
             if config.disabled {
+                context.set_skip_reason("kubernetes", SkipReason::Disabled);
                 return None;
             };
 
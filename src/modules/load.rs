@@ -0,0 +1,173 @@
+use std::path::Path;
+
+use super::{Context, Module, RootModuleConfig, SkipReason};
+
+use crate::configs::load::LoadConfig;
+use crate::utils;
+
+const LOADAVG_FILE: &str = "/proc/loadavg";
+
+/// Creates a module with the 1-minute system load average
+///
+/// The load average is only displayed when it's at or above `threshold`, and
+/// is styled by the highest of the `display` thresholds it reaches.
+///
+/// The load average is read from `/proc/loadavg`, so this module is only
+/// shown on Linux.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    load_module(context, || read_load_average(Path::new(LOADAVG_FILE)))
+}
+
+/// The module is disabled by default, so the load average is only read once it's enabled
+fn load_module<'a>(
+    context: &'a Context,
+    read_load_average: impl FnOnce() -> Option<f64>,
+) -> Option<Module<'a>> {
+    let mut module = context.new_module("load");
+    let config = LoadConfig::try_load(module.config);
+
+    if config.disabled {
+        context.set_skip_reason("load", SkipReason::Disabled);
+        return None;
+    }
+
+    let load_average = read_load_average()?;
+    if load_average < config.threshold {
+        return None;
+    }
+
+    let style = config
+        .display
+        .iter()
+        .filter(|display| load_average >= display.threshold)
+        .max_by(|a, b| a.threshold.total_cmp(&b.threshold))
+        .map_or(config.style, |display| display.style);
+
+    module.set_style(style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment(
+        "load",
+        &config.load.with_value(&format!("{:.2}", load_average)),
+    );
+
+    Some(module)
+}
+
+/// Read the 1-minute load average, the first field of a `loadavg` file
+fn read_load_average(loadavg_file: &Path) -> Option<f64> {
+    match utils::read_file(loadavg_file) {
+        Ok(loadavg) => parse_load_average(&loadavg),
+        Err(e) => {
+            log::debug!("Unable to read the load average: {}", e);
+            None
+        }
+    }
+}
+
+fn parse_load_average(loadavg: &str) -> Option<f64> {
+    loadavg.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StarshipConfig;
+    use ansi_term::Color;
+    use clap::ArgMatches;
+
+    fn render_load(load_average: f64, config: toml::Value) -> Option<String> {
+        let mut context = Context::new_with_dir(ArgMatches::default(), "/");
+        context.config = StarshipConfig {
+            config: Some(config),
+        };
+
+        load_module(&context, || Some(load_average)).map(|module| module.to_string())
+    }
+
+    #[test]
+    fn test_parse_load_average() {
+        let loadavg = "0.52 0.58 0.59 1/467 12345\n";
+        assert_eq!(parse_load_average(loadavg), Some(0.52));
+        assert_eq!(parse_load_average(""), None);
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let config = toml::toml! {
+            [load]
+        };
+        assert_eq!(render_load(8.0, config), None);
+    }
+
+    #[test]
+    fn test_disabled_without_reading_load() {
+        let mut context = Context::new_with_dir(ArgMatches::default(), "/");
+        context.config = StarshipConfig {
+            config: Some(toml::toml! {
+                [load]
+            }),
+        };
+        let module = load_module(&context, || panic!("the load average was read"));
+        assert!(module.is_none());
+    }
+
+    #[test]
+    fn test_below_threshold() {
+        let config = toml::toml! {
+            [load]
+            disabled = false
+        };
+        assert_eq!(render_load(0.5, config), None);
+    }
+
+    #[test]
+    fn test_above_threshold() {
+        let config = toml::toml! {
+            [load]
+            disabled = false
+        };
+        let expected = format!("via {} ", Color::Yellow.bold().paint("⚖️  1.50"));
+        assert_eq!(render_load(1.5, config), Some(expected));
+    }
+
+    #[test]
+    fn test_display_tiers() {
+        let config = toml::toml! {
+            [load]
+            disabled = false
+            threshold = 0.5
+            display = [
+                { threshold = 2.0, style = "bold yellow" },
+                { threshold = 8.0, style = "bold red" },
+            ]
+            style = "green"
+        };
+
+        let expected = format!("via {} ", Color::Green.paint("⚖️  0.75"));
+        assert_eq!(render_load(0.75, config.clone()), Some(expected));
+
+        let expected = format!("via {} ", Color::Yellow.bold().paint("⚖️  2.00"));
+        assert_eq!(render_load(2.0, config.clone()), Some(expected));
+
+        let expected = format!("via {} ", Color::Red.bold().paint("⚖️  12.25"));
+        assert_eq!(render_load(12.25, config), Some(expected));
+    }
+
+    #[test]
+    fn test_nan_display_threshold() {
+        let config = "
+            [load]
+            disabled = false
+            display = [
+                { threshold = nan, style = \"bold red\" },
+                { threshold = 2.0, style = \"bold yellow\" },
+                { threshold = 1.0, style = \"green\" },
+            ]
+        "
+        .parse::<toml::Value>()
+        .unwrap();
+
+        let expected = format!("via {} ", Color::Yellow.bold().paint("⚖️  4.00"));
+        assert_eq!(render_load(4.0, config), Some(expected));
+    }
+}
@@ -1,7 +1,7 @@
 use byte_unit::{Byte, ByteUnit};
 use sysinfo::{RefreshKind, SystemExt};
 
-use super::{Context, Module, RootModuleConfig};
+use super::{Context, Module, RootModuleConfig, Shell, SkipReason};
 
 use crate::configs::memory_usage::MemoryConfig;
 
@@ -12,6 +12,13 @@ fn format_kib(n_kib: u64) -> String {
     display_bytes
 }
 
+/// Check whether the percentage of memory used, rounded to a whole percent, reaches
+/// `threshold`. A negative threshold always shows the module, unless no memory is
+/// reported at all.
+fn exceeds_threshold(percent_used: f64, threshold: i64) -> bool {
+    percent_used.round() >= threshold as f64
+}
+
 /// Creates a module with system memory usage information
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("memory_usage");
@@ -19,14 +26,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     // TODO: Update when v1.0 printing refactor is implemented to only
     // print escapes in a prompt context.
-    let shell = std::env::var("STARSHIP_SHELL").unwrap_or_default();
-    let percent_sign = match shell.as_str() {
-        "zsh" => "%%", // % is an escape in zsh, see PROMPT in `man zshmisc`
-        "powershell" => "`%",
+    let percent_sign = match context.shell {
+        Shell::Zsh => "%%", // % is an escape in zsh, see PROMPT in `man zshmisc`
+        Shell::Powershell => "`%",
         _ => "%",
     };
 
     if config.disabled {
+        context.set_skip_reason("memory_usage", SkipReason::Disabled);
         return None;
     }
 
@@ -40,9 +47,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     let percent_mem_used = (used_memory_kib as f64 / total_memory_kib as f64) * 100.;
 
-    let threshold = config.threshold;
-
-    if percent_mem_used.round() < threshold as f64 {
+    if !exceeds_threshold(percent_mem_used, config.threshold) {
         return None;
     }
 
@@ -81,3 +86,27 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     Some(module)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_kib() {
+        assert_eq!(format_kib(512), "512KiB");
+        assert_eq!(format_kib(2 * 1024), "2MiB");
+        assert_eq!(format_kib(16 * 1024 * 1024), "16GiB");
+        assert_eq!(format_kib(0), "0B");
+    }
+
+    #[test]
+    fn test_exceeds_threshold() {
+        assert!(exceeds_threshold(80.0, 75));
+        assert!(exceeds_threshold(74.6, 75));
+        assert!(!exceeds_threshold(74.4, 75));
+        assert!(!exceeds_threshold(10.0, 75));
+        assert!(exceeds_threshold(0.0, -1));
+        // The percentage is NaN when the total memory is unknown
+        assert!(!exceeds_threshold(f64::NAN, -1));
+    }
+}
@@ -1,69 +1,125 @@
 // While adding out new module add out module to src/module.rs ALL_MODULES const array also.
+mod android;
 mod aws;
+mod azure;
+mod bazel;
+mod bun;
 mod character;
 mod cmd_duration;
 mod conda;
+mod cpp;
+mod crystal;
+mod dir_size;
 mod directory;
 mod dotnet;
+mod elixir;
 mod env_var;
+mod flutter;
 mod git_branch;
 mod git_state;
 mod git_status;
+mod git_tag;
 mod golang;
+mod gradle;
+mod helm;
+mod history;
 mod hostname;
 mod java;
 mod jobs;
+mod k8s_pod;
 mod kubernetes;
 mod line_break;
+mod load;
 mod memory_usage;
+mod multiplexer;
+mod nix_flake;
 mod nix_shell;
 mod nodejs;
+mod ocaml;
+mod os;
 mod package;
+mod perl;
+mod podman;
+mod profile;
+mod pulumi;
 mod python;
 mod ruby;
 mod rust;
+mod sandbox;
+mod slurm;
+mod terraform;
 mod time;
 mod username;
 mod utils;
+mod vcs_branch;
+mod wsl;
 
 #[cfg(feature = "battery")]
-mod battery;
+pub mod battery;
 
 use crate::config::{RootModuleConfig, SegmentConfig};
-use crate::context::Context;
+use crate::context::{Context, Shell, SkipReason};
 use crate::module::Module;
 
 pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
     match module {
         // Keep these ordered alphabetically.
         // Default ordering is handled in configs/mod.rs
+        "android" => android::module(context),
         "aws" => aws::module(context),
+        "azure" => azure::module(context),
         #[cfg(feature = "battery")]
         "battery" => battery::module(context),
+        "bazel" => bazel::module(context),
+        "bun" => bun::module(context),
         "character" => character::module(context),
         "cmd_duration" => cmd_duration::module(context),
         "conda" => conda::module(context),
+        "cpp" => cpp::module(context),
+        "crystal" => crystal::module(context),
+        "dir_size" => dir_size::module(context),
         "directory" => directory::module(context),
         "dotnet" => dotnet::module(context),
+        "elixir" => elixir::module(context),
         "env_var" => env_var::module(context),
+        "flutter" => flutter::module(context),
         "git_branch" => git_branch::module(context),
         "git_state" => git_state::module(context),
         "git_status" => git_status::module(context),
+        "git_tag" => git_tag::module(context),
         "golang" => golang::module(context),
+        "gradle" => gradle::module(context),
+        "helm" => helm::module(context),
+        "history" => history::module(context),
         "hostname" => hostname::module(context),
         "java" => java::module(context),
         "jobs" => jobs::module(context),
+        "k8s_pod" => k8s_pod::module(context),
         "kubernetes" => kubernetes::module(context),
         "line_break" => line_break::module(context),
+        "load" => load::module(context),
         "memory_usage" => memory_usage::module(context),
+        "multiplexer" => multiplexer::module(context),
+        "nix_flake" => nix_flake::module(context),
         "nix_shell" => nix_shell::module(context),
         "nodejs" => nodejs::module(context),
+        "ocaml" => ocaml::module(context),
+        "os" => os::module(context),
         "package" => package::module(context),
+        "perl" => perl::module(context),
+        "podman" => podman::module(context),
+        "profile" => profile::module(context),
+        "pulumi" => pulumi::module(context),
         "python" => python::module(context),
         "ruby" => ruby::module(context),
         "rust" => rust::module(context),
+        "sandbox" => sandbox::module(context),
+        "slurm" => slurm::module(context),
+        "terraform" => terraform::module(context),
         "time" => time::module(context),
         "username" => username::module(context),
+        "vcs_branch" => vcs_branch::module(context),
+        "wsl" => wsl::module(context),
         _ => {
             eprintln!("Error: Unknown module {}. Use starship module --list to list out all supported modules.", module);
             None
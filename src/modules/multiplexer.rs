@@ -0,0 +1,51 @@
+use std::env;
+
+use super::{Context, Module, RootModuleConfig, SkipReason};
+
+use crate::configs::multiplexer::MultiplexerConfig;
+
+/// Creates a module with the terminal multiplexer the shell is running in
+///
+/// Will display the multiplexer if any of the following criteria are met:
+///     - `$TMUX` is set, which tmux sets to the path of its socket
+///     - `$STY` is set, which GNU screen sets to the name of its session
+///
+/// tmux is shown if both are set.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("multiplexer");
+    let config: MultiplexerConfig = MultiplexerConfig::try_load(module.config);
+
+    if config.disabled {
+        context.set_skip_reason("multiplexer", SkipReason::Disabled);
+        return None;
+    }
+
+    let multiplexer = match get_multiplexer()? {
+        Multiplexer::Tmux => &config.tmux,
+        Multiplexer::Screen => &config.screen,
+    };
+
+    module.set_style(config.style);
+    module.get_prefix().set_value("in ");
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("multiplexer", multiplexer);
+
+    Some(module)
+}
+
+fn get_multiplexer() -> Option<Multiplexer> {
+    let is_set = |name: &str| !env::var_os(name).unwrap_or_default().is_empty();
+
+    if is_set("TMUX") {
+        Some(Multiplexer::Tmux)
+    } else if is_set("STY") {
+        Some(Multiplexer::Screen)
+    } else {
+        None
+    }
+}
+
+enum Multiplexer {
+    Tmux,
+    Screen,
+}
@@ -0,0 +1,120 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::nix_flake::NixFlakeConfig;
+use crate::utils;
+
+/// Creates a module with the description of the Nix flake in the current directory
+///
+/// Will display the flake if the current directory contains a `flake.nix` file.
+///
+/// The description is parsed from `flake.nix`, which avoids evaluating the flake.
+/// `nix flake metadata` is only run when it can't be parsed, and the name of the
+/// directory is shown when the flake has no description.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_flake = context
+        .try_begin_scan("nix_flake", None)?
+        .set_files(&["flake.nix"])
+        .is_match();
+
+    if !is_flake {
+        return None;
+    }
+
+    let mut module = context.new_module("nix_flake");
+    let config: NixFlakeConfig = NixFlakeConfig::try_load(module.config);
+
+    let description = utils::read_file(context.current_dir.join("flake.nix"))
+        .ok()
+        .and_then(|flake_nix| parse_flake_description(&flake_nix))
+        .or_else(|| get_flake_metadata_description(context))
+        .or_else(|| {
+            context
+                .current_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })?;
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("description", &config.description.with_value(&description));
+
+    Some(module)
+}
+
+/// Find the `description = "...";` attribute of a `flake.nix` file, which is
+/// conventionally the first attribute of the flake
+fn parse_flake_description(flake_nix: &str) -> Option<String> {
+    let value = flake_nix.lines().find_map(|line| {
+        let line = line.trim_start().strip_prefix("description")?;
+        line.trim_start()
+            .strip_prefix('=')?
+            .trim_start()
+            .strip_prefix('"')
+    })?;
+
+    let mut description = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => description.push(chars.next()?),
+            c => description.push(c),
+        }
+    }
+
+    let description = description.trim();
+    if description.is_empty() {
+        return None;
+    }
+    Some(description.to_string())
+}
+
+/// Get the description of the flake from `nix flake metadata`, which evaluates it
+fn get_flake_metadata_description(context: &Context) -> Option<String> {
+    let flake_dir = context.current_dir.to_str()?;
+    let metadata = context.exec_cmd("nix", &["flake", "metadata", "--json", flake_dir])?;
+    let metadata: serde_json::Value = serde_json::from_str(&metadata).ok()?;
+    let description = metadata.get("description")?.as_str()?.trim();
+
+    if description.is_empty() {
+        return None;
+    }
+    Some(description.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flake_description() {
+        let flake_nix =
+            "{\n  description = \"A flake for rockets\";\n\n  outputs = { self }: { };\n}\n";
+        assert_eq!(
+            parse_flake_description(flake_nix),
+            Some("A flake for rockets".to_string())
+        );
+
+        let flake_nix = "{\n  description=\"Escaped \\\"quotes\\\"\";\n}\n";
+        assert_eq!(
+            parse_flake_description(flake_nix),
+            Some("Escaped \"quotes\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_flake_description() {
+        assert_eq!(
+            parse_flake_description("{\n  outputs = { self }: { };\n}\n"),
+            None
+        );
+        assert_eq!(
+            parse_flake_description("{\n  description = \"\";\n}\n"),
+            None
+        );
+        assert_eq!(
+            parse_flake_description("{\n  descriptions = \"Not it\";\n}\n"),
+            None
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use std::process::Command;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
 
 use super::{Context, Module, RootModuleConfig, SegmentConfig};
 
@@ -7,14 +9,19 @@ use crate::configs::nodejs::NodejsConfig;
 /// Creates a module with the current Node.js version
 ///
 /// Will display the Node.js version if any of the following criteria are met:
-///     - Current directory contains a `.js` file
-///     - Current directory contains a `package.json` file
+///     - Current directory contains a `.js` or `.ts` file
+///     - Current directory contains a `package.json` or `.node-version` file
 ///     - Current directory contains a `node_modules` directory
+///     - `detect_always` is set to `true` in the module configuration
+///
+/// When `check_lockfile` is enabled, a warning is shown if `package.json` has no
+/// lockfile, or was modified after its lockfile, suggesting the dependencies need to
+/// be installed again.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_js_project = context
-        .try_begin_scan()?
-        .set_files(&["package.json"])
-        .set_extensions(&["js"])
+        .try_begin_scan("nodejs", Some(&["node", "--version"]))?
+        .set_files(&["package.json", ".node-version"])
+        .set_extensions(&["js", "ts"])
         .set_folders(&["node_modules"])
         .is_match();
 
@@ -22,26 +29,79 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    match get_node_version() {
-        Some(node_version) => {
-            let mut module = context.new_module("nodejs");
-            let config: NodejsConfig = NodejsConfig::try_load(module.config);
+    let mut module = context.new_module("nodejs");
+    let config: NodejsConfig = NodejsConfig::try_load(module.config);
+
+    let node_version = context.exec_cmd("node", &["--version"])?;
+    let formatted_version = format_node_version(&node_version)?;
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("version", &SegmentConfig::new(&formatted_version));
+
+    if config.check_lockfile && is_lockfile_stale(&context.current_dir) {
+        module.create_segment("lockfile_stale", &config.lockfile_stale);
+    }
+
+    Some(module)
+}
+
+/// The lockfiles written by npm, yarn, pnpm and bun
+const LOCKFILES: &[&str] = &[
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+];
 
-            module.set_style(config.style);
+/// Check whether `package.json` has no lockfile, or was last modified after the most
+/// recently written lockfile
+///
+/// Directories without a `package.json` are never stale.
+fn is_lockfile_stale(current_dir: &Path) -> bool {
+    let package_json_modified = match get_modified(&current_dir.join("package.json")) {
+        Some(modified) => modified,
+        None => return false,
+    };
+
+    let lockfile_modified = LOCKFILES
+        .iter()
+        .filter_map(|lockfile| get_modified(&current_dir.join(lockfile)))
+        .max();
+    match lockfile_modified {
+        Some(lockfile_modified) => package_json_modified > lockfile_modified,
+        None => true,
+    }
+}
 
-            let formatted_version = node_version.trim();
-            module.create_segment("symbol", &config.symbol);
-            module.create_segment("version", &SegmentConfig::new(formatted_version));
+fn get_modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
 
-            Some(module)
-        }
-        None => None,
+/// Format the output of `node --version`, e.g. `v12.0.0`, with a single leading `v`
+fn format_node_version(node_stdout: &str) -> Option<String> {
+    let version = node_stdout.trim().trim_start_matches('v');
+    if version.is_empty() {
+        return None;
     }
+
+    Some(format!("v{}", version))
 }
 
-fn get_node_version() -> Option<String> {
-    match Command::new("node").arg("--version").output() {
-        Ok(output) => Some(String::from_utf8(output.stdout).unwrap()),
-        Err(_) => None,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_node_version() {
+        assert_eq!(
+            format_node_version("v12.0.0\n"),
+            Some("v12.0.0".to_string())
+        );
+        assert_eq!(format_node_version("12.0.0"), Some("v12.0.0".to_string()));
+        assert_eq!(format_node_version(""), None);
     }
 }
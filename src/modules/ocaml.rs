@@ -0,0 +1,58 @@
+use std::env;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::ocaml::OCamlConfig;
+
+/// The name of the switch created by `opam init`
+const DEFAULT_SWITCH: &str = "default";
+
+/// Creates a module with the active opam switch
+///
+/// Will display the opam switch if any of the following criteria are met:
+///     - Current directory contains a `dune`, `dune-project` or `.merlin` file
+///     - Current directory contains an `_opam` directory
+///     - Current directory contains a file with the `.ml`, `.mli` or `.opam` extension
+///     - `detect_always` is set to `true` in the module configuration
+///
+/// The switch is read from `$OPAMSWITCH`, or otherwise from `opam switch show`.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_ocaml_project = context
+        .try_begin_scan("ocaml", Some(&["opam", "switch", "show"]))?
+        .set_files(&["dune", "dune-project", ".merlin"])
+        .set_folders(&["_opam"])
+        .set_extensions(&["ml", "mli", "opam"])
+        .is_match();
+
+    if !is_ocaml_project {
+        return None;
+    }
+
+    let mut module = context.new_module("ocaml");
+    let config: OCamlConfig = OCamlConfig::try_load(module.config);
+
+    let switch = get_opam_switch(context)?;
+    if config.ignore_default_switch && switch == DEFAULT_SWITCH {
+        return None;
+    }
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("switch", &config.switch.with_value(&switch));
+
+    Some(module)
+}
+
+/// Get the active opam switch, preferring `$OPAMSWITCH` over running `opam`
+fn get_opam_switch(context: &Context) -> Option<String> {
+    let switch = match env::var("OPAMSWITCH") {
+        Ok(switch) if !switch.trim().is_empty() => switch,
+        _ => context.exec_cmd("opam", &["switch", "show"])?,
+    };
+
+    let switch = switch.trim();
+    if switch.is_empty() {
+        return None;
+    }
+    Some(switch.to_string())
+}
@@ -0,0 +1,78 @@
+use std::env;
+use std::path::Path;
+
+use super::{Context, Module, RootModuleConfig, SegmentConfig, SkipReason};
+
+use crate::configs::os::OsConfig;
+use crate::utils;
+
+const OS_RELEASE_FILE: &str = "/etc/os-release";
+
+/// Creates a module with a symbol for the current operating system
+///
+/// On Linux, the distribution is identified by the `ID` field of `/etc/os-release`.
+/// If no symbol is configured for the distribution, the symbol for the operating
+/// system itself is used.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("os");
+    let config: OsConfig = OsConfig::try_load(module.config);
+    if config.disabled {
+        context.set_skip_reason("os", SkipReason::Disabled);
+        return None;
+    }
+
+    let distro = get_distro_id(Path::new(OS_RELEASE_FILE));
+    let symbol = get_os_symbol(&config, distro.as_deref(), env::consts::OS)?;
+
+    module.set_style(config.style);
+    module.get_prefix().set_value("on ");
+    module.create_segment("symbol", &SegmentConfig::new(symbol));
+
+    Some(module)
+}
+
+/// Read the distribution ID (e.g. `ubuntu`) from an `os-release` file
+fn get_distro_id(os_release: &Path) -> Option<String> {
+    utils::read_file_value(os_release, Some("ID"))
+}
+
+fn get_os_symbol<'a>(config: &OsConfig<'a>, distro: Option<&str>, os: &str) -> Option<&'a str> {
+    distro
+        .and_then(|distro| config.symbols.get(distro))
+        .or_else(|| config.symbols.get(os))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture_path(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_get_distro_id() {
+        assert_eq!(
+            get_distro_id(&fixture_path("os-release")),
+            Some("ubuntu".to_string())
+        );
+        assert_eq!(get_distro_id(&fixture_path("does-not-exist")), None);
+    }
+
+    #[test]
+    fn test_get_os_symbol() {
+        let config = OsConfig::new();
+
+        assert_eq!(get_os_symbol(&config, Some("ubuntu"), "linux"), Some("🎯 "));
+        assert_eq!(
+            get_os_symbol(&config, Some("unknown"), "linux"),
+            Some("🐧 ")
+        );
+        assert_eq!(get_os_symbol(&config, None, "macos"), Some("🍎 "));
+        assert_eq!(get_os_symbol(&config, None, "unknown"), None);
+    }
+}
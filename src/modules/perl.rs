@@ -0,0 +1,50 @@
+use std::env;
+use std::path::Path;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::perl::PerlConfig;
+
+/// Creates a module showing the active Perl local library
+///
+/// Will display an indicator if any of the following criteria are met:
+///     - Current directory contains a `.carton` folder, or the `local/lib/perl5`
+///       folder Carton installs dependencies into
+///     - The `PERL5LIB` environment variable is set, as done by `local::lib`
+///
+/// When `detect_always` is set and `perl` is installed, the symbol is shown in every
+/// other directory, without an indicator.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_carton_project = is_carton_project(&context.current_dir);
+    let has_perl5lib = !env::var("PERL5LIB").unwrap_or_default().is_empty();
+
+    if !is_carton_project
+        && !has_perl5lib
+        && !context.is_module_detect_always("perl", Some(&["perl", "-v"]))
+    {
+        return None;
+    }
+
+    let mut module = context.new_module("perl");
+    let config: PerlConfig = PerlConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+
+    if is_carton_project {
+        module.create_segment("carton", &config.carton);
+    } else if has_perl5lib {
+        module.create_segment("local_lib", &config.local_lib);
+    }
+
+    Some(module)
+}
+
+/// Check for the `.carton` marker, or the local library Carton installs dependencies into
+fn is_carton_project(current_dir: &Path) -> bool {
+    current_dir.join(".carton").is_dir() || is_carton_local_lib(current_dir)
+}
+
+fn is_carton_local_lib(current_dir: &Path) -> bool {
+    current_dir.join("local/lib/perl5").is_dir()
+}
@@ -0,0 +1,108 @@
+use std::env;
+use std::path::PathBuf;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::podman::PodmanConfig;
+use crate::utils;
+
+/// Creates a module with the active Podman connection
+///
+/// The connection is read from `$CONTAINER_CONNECTION`, or otherwise from the
+/// `engine.active_service` option of `containers.conf`, which avoids running
+/// `podman system connection list`. Nothing is shown when the local service or
+/// the default Podman machine is used.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let connection = get_active_connection()?;
+    if is_default_connection(&connection) {
+        return None;
+    }
+
+    let mut module = context.new_module("podman");
+    let config: PodmanConfig = PodmanConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("connection", &config.connection.with_value(&connection));
+
+    Some(module)
+}
+
+fn get_active_connection() -> Option<String> {
+    match env::var("CONTAINER_CONNECTION") {
+        Ok(connection) if !connection.trim().is_empty() => Some(connection.trim().to_string()),
+        _ => {
+            let containers_conf = utils::read_file(get_containers_conf_path()?).ok()?;
+            parse_active_service(&containers_conf)
+        }
+    }
+}
+
+/// Get the path of the user's `containers.conf`, following the same lookup as Podman
+fn get_containers_conf_path() -> Option<PathBuf> {
+    if let Ok(containers_conf) = env::var("CONTAINERS_CONF") {
+        if !containers_conf.is_empty() {
+            return Some(PathBuf::from(containers_conf));
+        }
+    }
+
+    let config_dir = match env::var("XDG_CONFIG_HOME") {
+        Ok(config_dir) if !config_dir.is_empty() => PathBuf::from(config_dir),
+        _ => dirs::home_dir()?.join(".config"),
+    };
+    Some(config_dir.join("containers/containers.conf"))
+}
+
+/// Find the `engine.active_service` option in a `containers.conf` file
+fn parse_active_service(containers_conf: &str) -> Option<String> {
+    let containers_conf: toml::Value = toml::from_str(containers_conf).ok()?;
+    let active_service = containers_conf
+        .get("engine")?
+        .get("active_service")?
+        .as_str()?
+        .trim();
+
+    if active_service.is_empty() {
+        return None;
+    }
+    Some(active_service.to_string())
+}
+
+/// The connections `podman machine` creates are used by default, so they aren't shown
+fn is_default_connection(connection: &str) -> bool {
+    connection.starts_with("podman-machine-default")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_active_service() {
+        let containers_conf = "[engine]\nactive_service = \"build-server\"\n\n[engine.service_destinations.build-server]\nuri = \"ssh://core@build.example.com/run/podman/podman.sock\"\n";
+        assert_eq!(
+            parse_active_service(containers_conf),
+            Some("build-server".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_active_service() {
+        assert_eq!(
+            parse_active_service("[engine]\ncgroup_manager = \"systemd\"\n"),
+            None
+        );
+        assert_eq!(
+            parse_active_service("[engine]\nactive_service = \"\"\n"),
+            None
+        );
+        assert_eq!(parse_active_service("not toml ["), None);
+    }
+
+    #[test]
+    fn test_is_default_connection() {
+        assert!(is_default_connection("podman-machine-default"));
+        assert!(is_default_connection("podman-machine-default-root"));
+        assert!(!is_default_connection("build-server"));
+    }
+}
@@ -0,0 +1,112 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::profile::ProfileConfig;
+
+/// How many symlinks to follow when looking for the generation of a profile
+const MAX_LINK_DEPTH: usize = 8;
+
+/// Creates a module showing the active Guix or Nix profile
+///
+/// Will display the profile if any of the following criteria are met:
+///     - `$GUIX_ENVIRONMENT` is set, inside `guix environment` or `guix shell`
+///     - `$GUIX_PROFILE` is set to the active Guix profile
+///     - `$NIX_PROFILES` is set, in which case the last (user) profile is used
+///
+/// When `show_generation` is enabled, the generation the profile points to is
+/// also displayed, e.g. `guix #42`.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let (kind, profile) = get_active_profile()?;
+
+    let mut module = context.new_module("profile");
+    let config: ProfileConfig = ProfileConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    match kind {
+        ProfileKind::Guix => module.create_segment("guix", &config.guix),
+        ProfileKind::Nix => module.create_segment("nix", &config.nix),
+    };
+
+    if config.show_generation {
+        if let Some(generation) = get_profile_generation(&profile) {
+            module.create_segment(
+                "generation",
+                &config.generation.with_value(&format!(" #{}", generation)),
+            );
+        }
+    }
+
+    Some(module)
+}
+
+enum ProfileKind {
+    Guix,
+    Nix,
+}
+
+fn get_active_profile() -> Option<(ProfileKind, PathBuf)> {
+    let get_env = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+
+    if let Some(environment) = get_env("GUIX_ENVIRONMENT") {
+        return Some((ProfileKind::Guix, PathBuf::from(environment)));
+    }
+    if let Some(profile) = get_env("GUIX_PROFILE") {
+        return Some((ProfileKind::Guix, PathBuf::from(profile)));
+    }
+
+    // `$NIX_PROFILES` lists the profiles from the lowest to the highest priority
+    let profile = get_env("NIX_PROFILES")?
+        .split_whitespace()
+        .last()
+        .map(PathBuf::from)?;
+    Some((ProfileKind::Nix, profile))
+}
+
+/// Follow the symlinks of a profile to the link of its current generation,
+/// e.g. `~/.guix-profile` to `/var/guix/profiles/per-user/astronaut/guix-profile-42-link`
+fn get_profile_generation(profile: &Path) -> Option<u64> {
+    let mut path = profile.to_path_buf();
+    for _ in 0..MAX_LINK_DEPTH {
+        let file_name = path.file_name()?.to_str()?;
+        if let Some(generation) = parse_generation(file_name) {
+            return Some(generation);
+        }
+
+        let target = fs::read_link(&path).ok()?;
+        path = match path.parent() {
+            Some(parent) => parent.join(target),
+            None => target,
+        };
+    }
+    None
+}
+
+/// Parse the generation from the name of a generation link, e.g. `profile-42-link`
+fn parse_generation(file_name: &str) -> Option<u64> {
+    if !file_name.ends_with("-link") {
+        return None;
+    }
+    file_name
+        .trim_end_matches("-link")
+        .rsplit('-')
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_generation() {
+        assert_eq!(parse_generation("guix-profile-42-link"), Some(42));
+        assert_eq!(parse_generation("profile-7-link"), Some(7));
+        assert_eq!(parse_generation("guix-profile"), None);
+        assert_eq!(parse_generation("profile-link"), None);
+    }
+}
@@ -0,0 +1,142 @@
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use yaml_rust::YamlLoader;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::pulumi::PulumiConfig;
+use crate::utils;
+
+/// Creates a module with the currently selected Pulumi stack
+///
+/// Will display the Pulumi stack if any of the following criteria are met:
+///     - Current directory contains a `Pulumi.yaml` file
+///     - Current directory contains a `Pulumi.<stack>.yaml` file
+///
+/// The stack is read from the project's workspace file in `~/.pulumi/workspaces`
+/// (or `$PULUMI_HOME/workspaces`). `pulumi stack --show-name` is only run when
+/// no workspace file can be found.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_pulumi_project = context
+        .get_dir_files()
+        .ok()?
+        .iter()
+        .filter_map(|path| path.file_name().and_then(OsStr::to_str))
+        .any(is_pulumi_file);
+
+    if !is_pulumi_project {
+        return None;
+    }
+
+    let project_file = context.current_dir.join("Pulumi.yaml");
+    let stack = get_project_name(&project_file)
+        .and_then(|project| get_stack_from_workspace(&project, &project_file))
+        .or_else(|| get_stack_from_pulumi(context))?;
+
+    let mut module = context.new_module("pulumi");
+    let config: PulumiConfig = PulumiConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("stack", &config.stack.with_value(&stack));
+
+    Some(module)
+}
+
+/// Matches both `Pulumi.yaml` and stack configuration files like `Pulumi.dev.yaml`
+fn is_pulumi_file(file_name: &str) -> bool {
+    file_name.starts_with("Pulumi.") && file_name.ends_with(".yaml")
+}
+
+fn get_project_name(project_file: &Path) -> Option<String> {
+    let contents = utils::read_file(project_file).ok()?;
+    let yaml_docs = YamlLoader::load_from_str(&contents).ok()?;
+    let name = yaml_docs.first()?["name"].as_str()?;
+
+    Some(name.to_string())
+}
+
+fn get_pulumi_home() -> Option<PathBuf> {
+    match env::var("PULUMI_HOME") {
+        Ok(home) => Some(PathBuf::from(home)),
+        Err(_) => Some(dirs::home_dir()?.join(".pulumi")),
+    }
+}
+
+/// Read the selected stack from the workspace file Pulumi keeps for the project in
+/// `workspaces`
+fn get_stack_from_workspace(project: &str, project_file: &Path) -> Option<String> {
+    let workspace_file = get_pulumi_home()?
+        .join("workspaces")
+        .join(get_workspace_file_name(project, project_file)?);
+
+    let contents = utils::read_file(workspace_file).ok()?;
+    extract_stack_from_workspace(&contents)
+}
+
+/// Pulumi names the workspace file of a project `<project>-<hash>-workspace.json`,
+/// where the hash is the hex SHA-1 of the path to its `Pulumi.yaml`
+fn get_workspace_file_name(project: &str, project_file: &Path) -> Option<String> {
+    let hash = sha1_smol::Sha1::from(project_file.to_str()?)
+        .digest()
+        .to_string();
+    Some(format!("{}-{}-workspace.json", project, hash))
+}
+
+fn extract_stack_from_workspace(contents: &str) -> Option<String> {
+    let workspace: serde_json::Value = serde_json::from_str(contents).ok()?;
+    let stack = workspace.get("stack")?.as_str()?.trim();
+
+    if stack.is_empty() {
+        return None;
+    }
+
+    Some(stack.to_string())
+}
+
+fn get_stack_from_pulumi(context: &Context) -> Option<String> {
+    let stack = context.exec_cmd("pulumi", &["stack", "--show-name"])?;
+    let stack = stack.trim();
+
+    if stack.is_empty() {
+        return None;
+    }
+
+    Some(stack.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_pulumi_file() {
+        assert!(is_pulumi_file("Pulumi.yaml"));
+        assert!(is_pulumi_file("Pulumi.dev.yaml"));
+        assert!(!is_pulumi_file("Pulumi.json"));
+        assert!(!is_pulumi_file("package.yaml"));
+    }
+
+    #[test]
+    fn test_get_workspace_file_name() {
+        let project_file = Path::new("/home/astronaut/rocket/Pulumi.yaml");
+        assert_eq!(
+            get_workspace_file_name("rocket", project_file),
+            Some("rocket-aed1ca66ac2278b7626f8139969f89a7bf16c3ec-workspace.json".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_stack_from_workspace() {
+        let input = r#"{"stack": "dev"}"#;
+        assert_eq!(extract_stack_from_workspace(input), Some("dev".to_string()));
+
+        let input = r#"{"stack": ""}"#;
+        assert_eq!(extract_stack_from_workspace(input), None);
+
+        let input = "{}";
+        assert_eq!(extract_stack_from_workspace(input), None);
+    }
+}
@@ -1,18 +1,24 @@
-use std::process::Command;
+use std::env;
 
 use super::{Context, Module, RootModuleConfig, SegmentConfig};
 
 use crate::configs::ruby::RubyConfig;
 
+/// The environment Rails uses when none is set
+const DEFAULT_RAILS_ENV: &str = "development";
+
 /// Creates a module with the current Ruby version
 ///
 /// Will display the Ruby version if any of the following criteria are met:
 ///     - Current directory contains a `.rb` file
-///     - Current directory contains a `Gemfile` file
+///     - Current directory contains a `Gemfile` or `.ruby-version` file
+///     - `detect_always` is set to `true` in the module configuration
+///
+/// The Rails or Rack environment is also shown when `$RAILS_ENV` or `$RACK_ENV` is set.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_rb_project = context
-        .try_begin_scan()?
-        .set_files(&["Gemfile"])
+        .try_begin_scan("ruby", Some(&["ruby", "-v"]))?
+        .set_files(&["Gemfile", ".ruby-version"])
         .set_extensions(&["rb"])
         .is_match();
 
@@ -20,36 +26,77 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let ruby_version = get_ruby_version()?;
-    let formatted_version = format_ruby_version(&ruby_version)?;
-
     let mut module = context.new_module("ruby");
     let config: RubyConfig = RubyConfig::try_load(module.config);
+
+    let ruby_version = context.exec_cmd("ruby", &["-v"])?;
+    let formatted_version = format_ruby_version(&ruby_version)?;
+
     module.set_style(config.style);
 
     module.create_segment("symbol", &config.symbol);
     module.create_segment("version", &SegmentConfig::new(&formatted_version));
 
+    if let Some(rails_env) = get_rails_env() {
+        if !(config.ignore_default_env && rails_env == DEFAULT_RAILS_ENV) {
+            let rails_env_config = config
+                .rails_env
+                .with_value(&rails_env)
+                .with_style(config.env_styles.get(rails_env.as_str()).copied());
+            module.create_segment("rails_env_prefix", &SegmentConfig::new(" ("));
+            module.create_segment("rails_env", &rails_env_config);
+            module.create_segment("rails_env_suffix", &SegmentConfig::new(")"));
+        }
+    }
+
     Some(module)
 }
 
-fn get_ruby_version() -> Option<String> {
-    match Command::new("ruby").arg("-v").output() {
-        Ok(output) => Some(String::from_utf8(output.stdout).unwrap()),
-        Err(_) => None,
-    }
+/// Get the Rails environment, falling back to the Rack environment like Rails does
+fn get_rails_env() -> Option<String> {
+    ["RAILS_ENV", "RACK_ENV"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .map(|rails_env| rails_env.trim().to_string())
+        .find(|rails_env| !rails_env.is_empty())
 }
 
-fn format_ruby_version(ruby_version: &str) -> Option<String> {
-    let version = ruby_version
-        // split into ["ruby", "2.6.0p0", "linux/amd64"]
+/// Find the version in the output of `ruby -v`, e.g.
+/// `ruby 2.6.3p62 (2019-04-16 revision 67580) [x86_64-linux]`
+fn format_ruby_version(ruby_stdout: &str) -> Option<String> {
+    let version = ruby_stdout
+        // split into ["ruby", "2.6.3p62", "(2019-04-16", ...]
         .split_whitespace()
-        // return "2.6.0p0"
         .nth(1)?
-        .get(0..5)?;
+        // drop the patch level, "p62"
+        .split('p')
+        .next()?;
 
-    let mut formatted_version = String::with_capacity(version.len() + 1);
-    formatted_version.push('v');
-    formatted_version.push_str(version);
-    Some(formatted_version)
+    if version.is_empty() {
+        return None;
+    }
+    Some(format!("v{}", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_ruby_version() {
+        let input = "ruby 2.6.3p62 (2019-04-16 revision 67580) [x86_64-linux]\n";
+        assert_eq!(format_ruby_version(input), Some("v2.6.3".to_string()));
+
+        let input = "ruby 2.6.10p210 (2022-04-12 revision 67958) [x86_64-darwin19]";
+        assert_eq!(format_ruby_version(input), Some("v2.6.10".to_string()));
+
+        let input = "ruby 3.0.0dev (2020-10-01T12:00:00Z master 0123456789) [x86_64-linux]";
+        assert_eq!(format_ruby_version(input), Some("v3.0.0dev".to_string()));
+    }
+
+    #[test]
+    fn test_format_missing_ruby_version() {
+        assert_eq!(format_ruby_version(""), None);
+        assert_eq!(format_ruby_version("ruby"), None);
+    }
 }
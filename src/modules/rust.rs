@@ -1,20 +1,23 @@
+use std::env;
 use std::ffi::OsStr;
 use std::path::Path;
-use std::process::{Command, Output};
-use std::{env, fs};
 
 use super::{Context, Module, RootModuleConfig};
 
+use crate::command::CommandOutput;
+
 use crate::configs::rust::RustConfig;
+use crate::utils;
 
 /// Creates a module with the current Rust version
 ///
 /// Will display the Rust version if any of the following criteria are met:
 ///     - Current directory contains a file with a `.rs` extension
 ///     - Current directory contains a `Cargo.toml` file
+///     - `detect_always` is set to `true` in the module configuration
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_rs_project = context
-        .try_begin_scan()?
+        .try_begin_scan("rust", Some(&["rustc", "--version"]))?
         .set_files(&["Cargo.toml"])
         .set_extensions(&["rs"])
         .is_match();
@@ -40,21 +43,21 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     // - `rustup show active-toolchain`
     // - `rustup which`
     let module_version = if let Some(toolchain) = env_rustup_toolchain()
-        .or_else(|| execute_rustup_override_list(&context.current_dir))
+        .or_else(|| execute_rustup_override_list(context))
         .or_else(|| find_rust_toolchain_file(&context))
     {
-        match execute_rustup_run_rustc_version(&toolchain) {
+        match execute_rustup_run_rustc_version(context, &toolchain) {
             RustupRunRustcVersionOutcome::RustcVersion(stdout) => format_rustc_version(stdout),
             RustupRunRustcVersionOutcome::ToolchainName(toolchain) => toolchain,
             RustupRunRustcVersionOutcome::RustupNotWorking => {
                 // If `rustup` is not in `$PATH` or cannot be executed for other reasons, we can
                 // safely execute `rustc --version`.
-                format_rustc_version(execute_rustc_version()?)
+                format_rustc_version(execute_rustc_version(context)?)
             }
             RustupRunRustcVersionOutcome::Err => return None,
         }
     } else {
-        format_rustc_version(execute_rustc_version()?)
+        format_rustc_version(execute_rustc_version(context)?)
     };
 
     let mut module = context.new_module("rust");
@@ -72,13 +75,11 @@ fn env_rustup_toolchain() -> Option<String> {
     Some(val.trim().to_owned())
 }
 
-fn execute_rustup_override_list(cwd: &Path) -> Option<String> {
-    let Output { stdout, .. } = Command::new("rustup")
-        .args(&["override", "list"])
-        .output()
-        .ok()?;
-    let stdout = String::from_utf8(stdout).ok()?;
-    extract_toolchain_from_rustup_override_list(&stdout, cwd)
+fn execute_rustup_override_list(context: &Context) -> Option<String> {
+    let stdout = context
+        .exec_cmd_full("rustup", &["override", "list"])?
+        .stdout;
+    extract_toolchain_from_rustup_override_list(&stdout, &context.current_dir)
 }
 
 fn extract_toolchain_from_rustup_override_list(stdout: &str, cwd: &Path) -> Option<String> {
@@ -101,61 +102,55 @@ fn find_rust_toolchain_file(context: &Context) -> Option<String> {
     // Look for 'rust-toolchain' as rustup does.
     // https://github.com/rust-lang/rustup.rs/blob/d84e6e50126bccd84649e42482fc35a11d019401/src/config.rs#L320-L358
 
-    fn read_first_line(path: &Path) -> Option<String> {
-        let content = fs::read_to_string(path).ok()?;
-        let line = content.lines().next()?;
-        Some(line.trim().to_owned())
-    }
-
     if let Some(path) = context
         .get_dir_files()
         .ok()?
         .iter()
         .find(|p| p.file_name() == Some(OsStr::new("rust-toolchain")))
     {
-        if let Some(toolchain) = read_first_line(path) {
+        if let Some(toolchain) = utils::read_file_value(path, None) {
             return Some(toolchain);
         }
     }
 
     let mut dir = &*context.current_dir;
     loop {
-        if let Some(toolchain) = read_first_line(&dir.join("rust-toolchain")) {
+        if let Some(toolchain) = utils::read_file_value(dir.join("rust-toolchain"), None) {
             return Some(toolchain);
         }
         dir = dir.parent()?;
     }
 }
 
-fn execute_rustup_run_rustc_version(toolchain: &str) -> RustupRunRustcVersionOutcome {
-    Command::new("rustup")
-        .args(&["run", toolchain, "rustc", "--version"])
-        .output()
+fn execute_rustup_run_rustc_version(
+    context: &Context,
+    toolchain: &str,
+) -> RustupRunRustcVersionOutcome {
+    context
+        .exec_cmd_full("rustup", &["run", toolchain, "rustc", "--version"])
         .map(extract_toolchain_from_rustup_run_rustc_version)
         .unwrap_or(RustupRunRustcVersionOutcome::RustupNotWorking)
 }
 
-fn extract_toolchain_from_rustup_run_rustc_version(output: Output) -> RustupRunRustcVersionOutcome {
+fn extract_toolchain_from_rustup_run_rustc_version(
+    output: CommandOutput,
+) -> RustupRunRustcVersionOutcome {
     if output.status.success() {
-        if let Ok(output) = String::from_utf8(output.stdout) {
-            return RustupRunRustcVersionOutcome::RustcVersion(output);
-        }
-    } else if let Ok(stderr) = String::from_utf8(output.stderr) {
-        if stderr.starts_with("error: toolchain '") && stderr.ends_with("' is not installed\n") {
-            let stderr = stderr
-                ["error: toolchain '".len()..stderr.len() - "' is not installed\n".len()]
-                .to_owned();
-            return RustupRunRustcVersionOutcome::ToolchainName(stderr);
-        }
+        return RustupRunRustcVersionOutcome::RustcVersion(output.stdout);
+    }
+
+    let stderr = output.stderr;
+    if stderr.starts_with("error: toolchain '") && stderr.ends_with("' is not installed\n") {
+        let toolchain = stderr
+            ["error: toolchain '".len()..stderr.len() - "' is not installed\n".len()]
+            .to_owned();
+        return RustupRunRustcVersionOutcome::ToolchainName(toolchain);
     }
     RustupRunRustcVersionOutcome::Err
 }
 
-fn execute_rustc_version() -> Option<String> {
-    match Command::new("rustc").arg("--version").output() {
-        Ok(output) => Some(String::from_utf8(output.stdout).unwrap()),
-        Err(_) => None,
-    }
+fn execute_rustc_version(context: &Context) -> Option<String> {
+    context.exec_cmd("rustc", &["--version"])
 }
 
 fn format_rustc_version(mut rustc_stdout: String) -> String {
@@ -176,7 +171,7 @@ enum RustupRunRustcVersionOutcome {
 #[cfg(test)]
 mod tests {
     use once_cell::sync::Lazy;
-    use std::process::{ExitStatus, Output};
+    use std::process::ExitStatus;
 
     use super::*;
 
@@ -220,50 +215,30 @@ mod tests {
         #[cfg(windows)]
         use std::os::windows::process::ExitStatusExt as _;
 
-        static RUSTC_VERSION: Lazy<Output> = Lazy::new(|| Output {
+        static RUSTC_VERSION: Lazy<CommandOutput> = Lazy::new(|| CommandOutput {
             status: ExitStatus::from_raw(0),
-            stdout: b"rustc 1.34.0\n"[..].to_owned(),
-            stderr: vec![],
+            stdout: "rustc 1.34.0\n".to_owned(),
+            stderr: String::new(),
         });
         assert_eq!(
             extract_toolchain_from_rustup_run_rustc_version(RUSTC_VERSION.clone()),
             RustupRunRustcVersionOutcome::RustcVersion("rustc 1.34.0\n".to_owned()),
         );
 
-        static TOOLCHAIN_NAME: Lazy<Output> = Lazy::new(|| Output {
+        static TOOLCHAIN_NAME: Lazy<CommandOutput> = Lazy::new(|| CommandOutput {
             status: ExitStatus::from_raw(1),
-            stdout: vec![],
-            stderr: b"error: toolchain 'channel-triple' is not installed\n"[..].to_owned(),
+            stdout: String::new(),
+            stderr: "error: toolchain 'channel-triple' is not installed\n".to_owned(),
         });
         assert_eq!(
             extract_toolchain_from_rustup_run_rustc_version(TOOLCHAIN_NAME.clone()),
             RustupRunRustcVersionOutcome::ToolchainName("channel-triple".to_owned()),
         );
 
-        static INVALID_STDOUT: Lazy<Output> = Lazy::new(|| Output {
-            status: ExitStatus::from_raw(0),
-            stdout: b"\xc3\x28"[..].to_owned(),
-            stderr: vec![],
-        });
-        assert_eq!(
-            extract_toolchain_from_rustup_run_rustc_version(INVALID_STDOUT.clone()),
-            RustupRunRustcVersionOutcome::Err,
-        );
-
-        static INVALID_STDERR: Lazy<Output> = Lazy::new(|| Output {
-            status: ExitStatus::from_raw(1),
-            stdout: vec![],
-            stderr: b"\xc3\x28"[..].to_owned(),
-        });
-        assert_eq!(
-            extract_toolchain_from_rustup_run_rustc_version(INVALID_STDERR.clone()),
-            RustupRunRustcVersionOutcome::Err,
-        );
-
-        static UNEXPECTED_FORMAT_OF_ERROR: Lazy<Output> = Lazy::new(|| Output {
+        static UNEXPECTED_FORMAT_OF_ERROR: Lazy<CommandOutput> = Lazy::new(|| CommandOutput {
             status: ExitStatus::from_raw(1),
-            stdout: vec![],
-            stderr: b"error:"[..].to_owned(),
+            stdout: String::new(),
+            stderr: "error:".to_owned(),
         });
         assert_eq!(
             extract_toolchain_from_rustup_run_rustc_version(UNEXPECTED_FORMAT_OF_ERROR.clone()),
@@ -0,0 +1,65 @@
+use std::env;
+use std::path::Path;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::sandbox::SandboxConfig;
+
+/// Creates a module with the Flatpak or Snap app the shell is confined in
+///
+/// Will display the app if any of the following criteria are met:
+///     - `$FLATPAK_ID` is set, which Flatpak sets to the ID of the app (e.g. `org.gnome.Builder`)
+///     - `$SNAP` is set, which snapd sets to the directory the snap is mounted at
+///       (e.g. `/snap/code/123`), and the name is read from `$SNAP_NAME` or that directory
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let app = get_flatpak_app().or_else(get_snap_app)?;
+
+    let mut module = context.new_module("sandbox");
+    let config: SandboxConfig = SandboxConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.get_prefix().set_value("in ");
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("app", &config.app.with_value(&app));
+
+    Some(module)
+}
+
+fn get_flatpak_app() -> Option<String> {
+    get_env_value("FLATPAK_ID")
+}
+
+fn get_snap_app() -> Option<String> {
+    let snap = get_env_value("SNAP")?;
+    get_env_value("SNAP_NAME").or_else(|| parse_snap_name(&snap))
+}
+
+/// Get the name of a snap from the directory it's mounted at, `/snap/<name>/<revision>`
+fn parse_snap_name(snap: &str) -> Option<String> {
+    let name = Path::new(snap).parent()?.file_name()?.to_str()?;
+    Some(name.to_string())
+}
+
+fn get_env_value(name: &str) -> Option<String> {
+    let value = env::var(name).ok()?;
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    Some(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_snap_name() {
+        assert_eq!(parse_snap_name("/snap/code/123"), Some("code".to_string()));
+        assert_eq!(
+            parse_snap_name("/snap/code/current/"),
+            Some("code".to_string())
+        );
+        assert_eq!(parse_snap_name("/"), None);
+    }
+}
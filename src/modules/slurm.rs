@@ -0,0 +1,51 @@
+use std::env;
+
+use super::{Context, Module};
+
+use crate::config::RootModuleConfig;
+use crate::configs::slurm::SlurmConfig;
+
+/// Creates a module with the current SLURM job
+///
+/// Will display the job ID iff `$SLURM_JOB_ID` (or the older `$SLURM_JOBID`)
+/// is set, i.e. when running inside a SLURM allocation. The job name and
+/// partition are appended when they're available.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let job_id = get_env("SLURM_JOB_ID").or_else(|| get_env("SLURM_JOBID"))?;
+
+    let mut module = context.new_module("slurm");
+    let config = SlurmConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.get_prefix().set_value("in ");
+
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("job_id", &config.job_id.with_value(&job_id));
+
+    if config.show_job_name {
+        if let Some(job_name) = get_env("SLURM_JOB_NAME") {
+            module.create_segment(
+                "job_name",
+                &config.job_name.with_value(&format!(" {}", job_name)),
+            );
+        }
+    }
+
+    if config.show_partition {
+        if let Some(partition) = get_env("SLURM_JOB_PARTITION") {
+            module.create_segment(
+                "partition",
+                &config.partition.with_value(&format!("@{}", partition)),
+            );
+        }
+    }
+
+    Some(module)
+}
+
+fn get_env(key: &str) -> Option<String> {
+    env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
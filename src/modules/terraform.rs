@@ -0,0 +1,114 @@
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::terraform::TerraformConfig;
+use crate::utils;
+
+const LOCK_FILE: &str = ".terraform.lock.hcl";
+
+/// Creates a module with the current Terraform workspace
+///
+/// Will display the Terraform workspace if any of the following criteria are met:
+///     - Current directory contains a `.terraform` folder
+///     - Current directory contains a file with the `.tf` or `.tofu` extension
+///     - `detect_always` is set to `true` in the module configuration, and `terraform`
+///       is installed
+///
+/// When `check_lock_file` is enabled, a warning is shown if `.terraform.lock.hcl`
+/// is older than the configuration files, suggesting `terraform init` needs to be run.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let is_terraform_project = context
+        .try_begin_scan("terraform", Some(&["terraform", "version"]))?
+        .set_folders(&[".terraform"])
+        .set_extensions(&["tf", "tofu"])
+        .is_match();
+
+    if !is_terraform_project {
+        return None;
+    }
+
+    let mut module = context.new_module("terraform");
+    let config: TerraformConfig = TerraformConfig::try_load(module.config);
+
+    let workspace = get_terraform_workspace(&context.current_dir);
+
+    module.set_style(config.style);
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("workspace", &config.workspace.with_value(&workspace));
+
+    if config.check_lock_file && has_stale_lock_file(context) {
+        module.create_segment("lock_file_stale", &config.lock_file_stale);
+    }
+
+    Some(module)
+}
+
+fn get_terraform_workspace(current_dir: &Path) -> String {
+    // The workspace can be overridden with an environment variable,
+    // otherwise it's stored in `.terraform/environment` by `terraform workspace select`
+    env::var("TF_WORKSPACE")
+        .ok()
+        .or_else(|| utils::read_file_value(current_dir.join(".terraform/environment"), None))
+        .unwrap_or_else(|| "default".to_string())
+}
+
+/// Check whether the lock file was last written before a configuration file was modified
+fn has_stale_lock_file(context: &Context) -> bool {
+    let lock_file_modified = match get_modified(&context.current_dir.join(LOCK_FILE)) {
+        Some(modified) => modified,
+        None => return false,
+    };
+
+    let config_files_modified = match context.get_dir_files() {
+        Ok(dir_files) => dir_files
+            .iter()
+            .filter(|path| is_config_file(path))
+            .filter_map(|path| get_modified(path))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    is_lock_file_stale(lock_file_modified, &config_files_modified)
+}
+
+fn is_config_file(path: &Path) -> bool {
+    let extension = path.extension().and_then(OsStr::to_str);
+    path.is_file() && (extension == Some("tf") || extension == Some("tofu"))
+}
+
+fn get_modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn is_lock_file_stale(
+    lock_file_modified: SystemTime,
+    config_files_modified: &[SystemTime],
+) -> bool {
+    config_files_modified
+        .iter()
+        .any(|modified| *modified > lock_file_modified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_is_lock_file_stale() {
+        let lock_file_modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        let older = SystemTime::UNIX_EPOCH + Duration::from_secs(500);
+        let newer = SystemTime::UNIX_EPOCH + Duration::from_secs(1500);
+
+        assert!(!is_lock_file_stale(lock_file_modified, &[]));
+        assert!(!is_lock_file_stale(lock_file_modified, &[older]));
+        assert!(is_lock_file_stale(lock_file_modified, &[older, newer]));
+    }
+}
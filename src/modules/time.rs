@@ -1,17 +1,24 @@
-use chrono::{DateTime, FixedOffset, Local, Utc};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Duration, FixedOffset, Local, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use std::env;
 
-use super::{Context, Module};
+use super::{Context, Module, SkipReason};
 
 use crate::config::{RootModuleConfig, SegmentConfig};
 use crate::configs::time::TimeConfig;
 
 /// Outputs the current time
+///
+/// When `show_time_zone` is enabled, the abbreviation of the local time zone is
+/// appended, followed by a marker while daylight saving time is in effect.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     const TIME_PREFIX: &str = "at ";
 
     let mut module = context.new_module("time");
     let config: TimeConfig = TimeConfig::try_load(module.config);
     if config.disabled {
+        context.set_skip_reason("time", SkipReason::Disabled);
         return None;
     };
 
@@ -23,6 +30,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         time_format
     );
 
+    // chrono panics when formatting with an invalid format string
+    if !is_valid_format(time_format) {
+        log::warn!("Invalid time format string: {}", time_format);
+        return None;
+    }
+
     let formatted_time_string = if config.utc_time_offset != "local" {
         match create_offset_time_string(Utc::now(), &config.utc_time_offset, &time_format) {
             Ok(formatted_string) => formatted_string,
@@ -41,13 +54,20 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     module.get_prefix().set_value(TIME_PREFIX);
 
-    module.create_segment(
-        "time",
-        &SegmentConfig {
-            value: &formatted_time_string,
-            style: None,
-        },
-    );
+    module.create_segment("time", &SegmentConfig::new(&formatted_time_string));
+
+    // A fixed UTC offset has no time zone name to show
+    if config.show_time_zone && config.utc_time_offset == "local" {
+        if let Some((abbreviation, is_dst)) = get_local_time_zone() {
+            module.create_segment(
+                "time_zone",
+                &config.time_zone.with_value(&format!(" {}", abbreviation)),
+            );
+            if is_dst {
+                module.create_segment("dst", &config.dst);
+            }
+        }
+    }
 
     Some(module)
 }
@@ -77,6 +97,12 @@ fn create_offset_time_string(
     }
 }
 
+/// Check whether chrono can format a time with `time_format`, i.e. it has no unknown
+/// or incomplete specifiers
+fn is_valid_format(time_format: &str) -> bool {
+    StrftimeItems::new(time_format).all(|item| item != Item::Error)
+}
+
 /// Format a given time into the given string. This function should be referentially
 /// transparent, which makes it easy to test (unlike anything involving the actual time)
 fn format_time(time_format: &str, local_time: DateTime<Local>) -> String {
@@ -87,6 +113,48 @@ fn format_time_fixed_offset(time_format: &str, utc_time: DateTime<FixedOffset>)
     utc_time.format(time_format).to_string()
 }
 
+/// Get the abbreviation of the local time zone, and whether daylight saving time is in effect
+fn get_local_time_zone() -> Option<(String, bool)> {
+    let time_zone = get_local_tz()?;
+    Some(get_time_zone_at(Utc::now(), time_zone))
+}
+
+/// Find the local time zone, which is named by `$TZ` when it's set, and is otherwise
+/// the system's
+///
+/// `$TZ` may only name a zone from the time zone database, such as `Europe/Berlin`.
+fn get_local_tz() -> Option<Tz> {
+    let name = match env::var("TZ") {
+        Ok(tz) if !tz.is_empty() => tz.trim_start_matches(':').to_string(),
+        _ => match iana_time_zone::get_timezone() {
+            Ok(name) => name,
+            Err(e) => {
+                log::debug!("Unable to get the system time zone: {}", e);
+                return None;
+            }
+        },
+    };
+
+    match name.parse() {
+        Ok(time_zone) => Some(time_zone),
+        Err(e) => {
+            log::debug!("Unknown time zone {:?}: {}", name, e);
+            None
+        }
+    }
+}
+
+/// Get the abbreviation of a time zone at the given time, and whether daylight saving
+/// time is in effect then
+fn get_time_zone_at(utc_time: DateTime<Utc>, time_zone: Tz) -> (String, bool) {
+    let local_time = utc_time.with_timezone(&time_zone);
+    let offset = local_time.offset();
+    (
+        offset.abbreviation().to_string(),
+        offset.dst_offset() != Duration::zero(),
+    )
+}
+
 /* Because we cannot make acceptance tests for the time module, these unit
 tests become extra important */
 #[cfg(test)]
@@ -146,6 +214,14 @@ mod tests {
         assert_eq!(formatted, "[15:36:47]");
     }
 
+    #[test]
+    fn test_is_valid_format() {
+        assert!(is_valid_format(FMT_12));
+        assert!(is_valid_format("🕙[ %H:%M ]"));
+        assert!(!is_valid_format("%Q"));
+        assert!(!is_valid_format("%H:%"));
+    }
+
     #[test]
     fn test_midnight_12hr_fixed_offset() {
         let timezone_offset = FixedOffset::east(0);
@@ -308,4 +384,28 @@ mod tests {
             .err()
             .expect("Invalid timezone offset.");
     }
+
+    #[test]
+    fn test_time_zone_in_standard_time() {
+        let utc_time: DateTime<Utc> = Utc.ymd(2020, 1, 15).and_hms(12, 0, 0);
+
+        let actual = get_time_zone_at(utc_time, chrono_tz::Europe::Berlin);
+        assert_eq!(actual, ("CET".to_string(), false));
+    }
+
+    #[test]
+    fn test_time_zone_in_dst() {
+        let utc_time: DateTime<Utc> = Utc.ymd(2020, 7, 15).and_hms(12, 0, 0);
+
+        let actual = get_time_zone_at(utc_time, chrono_tz::Europe::Berlin);
+        assert_eq!(actual, ("CEST".to_string(), true));
+    }
+
+    #[test]
+    fn test_time_zone_without_dst() {
+        let utc_time: DateTime<Utc> = Utc.ymd(2020, 7, 15).and_hms(12, 0, 0);
+
+        let actual = get_time_zone_at(utc_time, chrono_tz::Asia::Tokyo);
+        assert_eq!(actual, ("JST".to_string(), false));
+    }
 }
@@ -1,47 +1,222 @@
 use std::env;
-use std::process::Command;
+
+use ansi_term::Style;
 
 use super::{Context, Module, RootModuleConfig, SegmentConfig};
 
 use crate::configs::username::UsernameConfig;
 
+const ROOT_UID: u32 = 0;
+
 /// Creates a module with the current user's username
 ///
 /// Will display the username if any of the following criteria are met:
-///     - The current user isn't the same as the one that is logged in (`$LOGNAME` != `$USER`)
+///     - The current user isn't the same as the one that is logged in (`$LOGNAME` != `$USER`),
+///       or isn't `default_user` when that is configured
 ///     - The current user is root (UID = 0)
 ///     - The user is currently connected as an SSH session (`$SSH_CONNECTION`)
+///
+/// The username is read from `$USER`, or `$LOGNAME` when that isn't set, and is
+/// otherwise looked up from the UID.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let user = env::var("USER").ok();
     let logname = env::var("LOGNAME").ok();
-    let ssh_connection = env::var("SSH_CONNECTION").ok();
-
-    const ROOT_UID: Option<u32> = Some(0);
+    let is_ssh_session = env::var("SSH_CONNECTION").is_ok();
     let user_uid = get_uid();
 
     let mut module = context.new_module("username");
     let config: UsernameConfig = UsernameConfig::try_load(module.config);
 
-    if user != logname || ssh_connection.is_some() || user_uid == ROOT_UID || config.show_always {
-        let module_style = match user_uid {
-            Some(0) => config.style_root,
-            _ => config.style_user,
-        };
+    let is_shown = is_shown(
+        user_uid,
+        user.as_deref(),
+        logname.as_deref(),
+        is_ssh_session,
+        &config,
+    );
+    if !is_shown {
+        return None;
+    }
 
-        module.set_style(module_style);
-        module.create_segment("username", &SegmentConfig::new(&user?));
+    let username = user.or(logname).or_else(get_username_from_uid)?;
 
-        Some(module)
-    } else {
-        None
+    module.set_style(get_style(&config, user_uid));
+    module.create_segment("username", &SegmentConfig::new(&username));
+
+    Some(module)
+}
+
+/// Check whether the username should be shown for the user with `uid`
+fn is_shown(
+    uid: Option<u32>,
+    user: Option<&str>,
+    logname: Option<&str>,
+    is_ssh_session: bool,
+    config: &UsernameConfig,
+) -> bool {
+    config.show_always
+        || is_ssh_session
+        || uid == Some(ROOT_UID)
+        || is_other_user(user, logname, config.default_user)
+}
+
+/// Check whether the current user isn't the one expected: `default_user` when it's
+/// configured, or otherwise the user that logged in
+fn is_other_user(user: Option<&str>, logname: Option<&str>, default_user: &str) -> bool {
+    if default_user.is_empty() {
+        return user != logname;
+    }
+
+    match user.or(logname) {
+        Some(user) => user != default_user,
+        None => false,
+    }
+}
+
+/// Root gets a style of its own, to stand out
+fn get_style(config: &UsernameConfig, uid: Option<u32>) -> Style {
+    match uid {
+        Some(ROOT_UID) => config.style_root,
+        _ => config.style_user,
     }
 }
 
+#[cfg(unix)]
 fn get_uid() -> Option<u32> {
-    match Command::new("id").arg("-u").output() {
-        Ok(output) => String::from_utf8(output.stdout)
-            .map(|uid| uid.trim().parse::<u32>().ok())
-            .ok()?,
-        Err(_) => None,
+    Some(unsafe { libc::getuid() })
+}
+
+#[cfg(not(unix))]
+const fn get_uid() -> Option<u32> {
+    None
+}
+
+/// Look up the name of the current user from their UID, for shells which don't set
+/// `$USER` or `$LOGNAME`
+#[cfg(unix)]
+fn get_username_from_uid() -> Option<String> {
+    use std::ffi::CStr;
+
+    let uid = unsafe { libc::getuid() };
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    let mut buffer = vec![0 as libc::c_char; 1024];
+
+    loop {
+        let status = unsafe {
+            libc::getpwuid_r(
+                uid,
+                &mut passwd,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut result,
+            )
+        };
+        match status {
+            0 => break,
+            // The entry doesn't fit in the buffer, so retry with a larger one
+            libc::ERANGE if buffer.len() < 1024 * 1024 => {
+                buffer.resize(buffer.len() * 2, 0);
+            }
+            _ => {
+                log::debug!("Unable to look up the name of UID {}", uid);
+                return None;
+            }
+        }
+    }
+
+    if result.is_null() || passwd.pw_name.is_null() {
+        return None;
+    }
+    let username = unsafe { CStr::from_ptr(passwd.pw_name) }.to_str().ok()?;
+    if username.is_empty() {
+        return None;
+    }
+    Some(username.to_string())
+}
+
+#[cfg(not(unix))]
+const fn get_username_from_uid() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color;
+
+    #[test]
+    fn test_root_style() {
+        let config = UsernameConfig::new();
+        assert_eq!(get_style(&config, Some(0)), Color::Red.bold());
+        assert_eq!(get_style(&config, Some(1000)), Color::Yellow.bold());
+        assert_eq!(get_style(&config, None), Color::Yellow.bold());
+    }
+
+    #[test]
+    fn test_is_shown_for_root() {
+        let config = UsernameConfig::new();
+        let astronaut = Some("astronaut");
+        assert!(is_shown(Some(0), astronaut, astronaut, false, &config));
+        assert!(!is_shown(Some(1000), astronaut, astronaut, false, &config));
+        assert!(!is_shown(None, astronaut, astronaut, false, &config));
+    }
+
+    #[test]
+    fn test_is_shown_for_ssh_session() {
+        let config = UsernameConfig::new();
+        assert!(is_shown(Some(1000), Some("astronaut"), None, true, &config));
+        assert!(is_shown(Some(1000), None, Some("astronaut"), true, &config));
+        assert!(is_shown(Some(0), None, None, true, &config));
+    }
+
+    #[test]
+    fn test_is_shown_with_default_user() {
+        let mut config = UsernameConfig::new();
+        config.default_user = "cosmonaut";
+        let astronaut = Some("astronaut");
+        assert!(is_shown(Some(1000), astronaut, astronaut, false, &config));
+
+        config.default_user = "astronaut";
+        assert!(!is_shown(Some(1000), astronaut, astronaut, false, &config));
+        assert!(is_shown(Some(1000), astronaut, astronaut, true, &config));
+        assert!(is_shown(Some(0), astronaut, astronaut, false, &config));
+    }
+
+    #[test]
+    fn test_is_shown_always() {
+        let mut config = UsernameConfig::new();
+        config.show_always = true;
+        let astronaut = Some("astronaut");
+        assert!(is_shown(Some(1000), astronaut, astronaut, false, &config));
+    }
+
+    #[test]
+    fn test_is_other_user() {
+        assert!(!is_other_user(Some("astronaut"), Some("astronaut"), ""));
+        assert!(is_other_user(Some("cosmonaut"), Some("astronaut"), ""));
+        assert!(!is_other_user(None, None, ""));
+    }
+
+    #[test]
+    fn test_is_other_user_than_default() {
+        assert!(!is_other_user(
+            Some("astronaut"),
+            Some("astronaut"),
+            "astronaut"
+        ));
+        assert!(is_other_user(
+            Some("astronaut"),
+            Some("astronaut"),
+            "cosmonaut"
+        ));
+        // `$USER` takes precedence, as it's the name the module shows
+        assert!(!is_other_user(
+            Some("astronaut"),
+            Some("cosmonaut"),
+            "astronaut"
+        ));
+        assert!(is_other_user(None, Some("cosmonaut"), "astronaut"));
+        assert!(!is_other_user(None, None, "astronaut"));
     }
 }
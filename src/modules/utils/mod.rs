@@ -1 +1,2 @@
 pub mod java_version_parser;
+pub mod vcs;
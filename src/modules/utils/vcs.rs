@@ -0,0 +1,74 @@
+use std::path::Path;
+
+/// A version control system, in the order they're preferred when one checkout has
+/// the markers of several
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Vcs {
+    Git,
+    Mercurial,
+    Fossil,
+    Subversion,
+}
+
+const ALL_VCS: [Vcs; 4] = [Vcs::Git, Vcs::Mercurial, Vcs::Fossil, Vcs::Subversion];
+
+impl Vcs {
+    /// The files or directories at the root of a checkout
+    fn markers(self) -> &'static [&'static str] {
+        match self {
+            Vcs::Git => &[".git"],
+            Vcs::Mercurial => &[".hg"],
+            Vcs::Fossil => &[".fslckout", "_FOSSIL_"],
+            Vcs::Subversion => &[".svn"],
+        }
+    }
+}
+
+/// Find the version control system of the checkout `dir` is in, along with the root
+/// of the checkout. When checkouts are nested, the innermost one is used.
+pub fn find_vcs(dir: &Path) -> Option<(Vcs, &Path)> {
+    dir.ancestors().find_map(|ancestor| {
+        ALL_VCS
+            .iter()
+            .find(|vcs| {
+                vcs.markers()
+                    .iter()
+                    .any(|marker| ancestor.join(marker).exists())
+            })
+            .map(|vcs| (*vcs, ancestor))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io;
+
+    #[test]
+    fn test_find_vcs() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let repo = dir.path().join("repo");
+        let checkout = repo.join("vendor/lib");
+        fs::create_dir_all(repo.join(".git"))?;
+        fs::create_dir_all(checkout.join(".svn"))?;
+        fs::create_dir_all(checkout.join("src"))?;
+
+        assert_eq!(find_vcs(&repo), Some((Vcs::Git, repo.as_path())));
+        assert_eq!(
+            find_vcs(&repo.join("vendor")),
+            Some((Vcs::Git, repo.as_path()))
+        );
+        assert_eq!(
+            find_vcs(&checkout.join("src")),
+            Some((Vcs::Subversion, checkout.as_path()))
+        );
+        assert_eq!(find_vcs(dir.path()), None);
+
+        // Fossil is preferred to Subversion in the same directory
+        fs::write(checkout.join(".fslckout"), "")?;
+        assert_eq!(find_vcs(&checkout), Some((Vcs::Fossil, checkout.as_path())));
+
+        dir.close()
+    }
+}
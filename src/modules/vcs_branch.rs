@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use super::utils::vcs::{self, Vcs};
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::vcs_branch::VcsBranchConfig;
+
+/// Creates a module with the branch of the Fossil or Subversion checkout in the
+/// current directory
+///
+/// Will display the branch if the current directory is in a checkout, found by:
+///     - A `.fslckout` or `_FOSSIL_` file for Fossil, which runs `fossil branch current`
+///     - A `.svn` directory for Subversion, which runs `svn info`
+///
+/// Only the innermost checkout is shown, so nothing is displayed inside a git or
+/// Mercurial checkout, even when that is nested in a Fossil or Subversion one.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let (vcs, root) = vcs::find_vcs(&context.current_dir)?;
+
+    let mut module = context.new_module("vcs_branch");
+    let config: VcsBranchConfig = VcsBranchConfig::try_load(module.config);
+
+    let (symbol, branch) = match vcs {
+        Vcs::Fossil => (&config.fossil_symbol, get_fossil_branch(context)?),
+        Vcs::Subversion => (&config.svn_symbol, get_svn_branch(context, root)?),
+        Vcs::Git | Vcs::Mercurial => return None,
+    };
+
+    module.set_style(config.style);
+    module.get_prefix().set_value("on ");
+    module.create_segment("symbol", symbol);
+    module.create_segment("name", &config.branch_name.with_value(&branch));
+
+    Some(module)
+}
+
+fn get_fossil_branch(context: &Context) -> Option<String> {
+    let branch = context.exec_cmd("fossil", &["branch", "current"])?;
+    let branch = branch.trim();
+    if branch.is_empty() {
+        return None;
+    }
+    Some(branch.to_string())
+}
+
+fn get_svn_branch(context: &Context, root: &Path) -> Option<String> {
+    let root = root.to_str()?;
+    let url = context.exec_cmd("svn", &["info", "--show-item", "relative-url", root])?;
+    parse_svn_branch(&url)
+}
+
+/// Get the branch from the URL of a checkout relative to the repository root, using
+/// the standard `trunk`, `branches` and `tags` layout. The whole URL is used for
+/// checkouts outside of that layout.
+fn parse_svn_branch(relative_url: &str) -> Option<String> {
+    let path = relative_url
+        .trim()
+        .trim_start_matches('^')
+        .trim_matches('/');
+    if path.is_empty() {
+        return None;
+    }
+
+    let mut segments = path.split('/');
+    while let Some(segment) = segments.next() {
+        match segment {
+            "trunk" => return Some(segment.to_string()),
+            "branches" | "tags" => {
+                if let Some(name) = segments.next() {
+                    return Some(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_svn_branch() {
+        assert_eq!(parse_svn_branch("^/trunk\n"), Some("trunk".to_string()));
+        assert_eq!(
+            parse_svn_branch("^/branches/feature-x"),
+            Some("feature-x".to_string())
+        );
+        assert_eq!(
+            parse_svn_branch("^/rocket/tags/v1.0/docs"),
+            Some("v1.0".to_string())
+        );
+        assert_eq!(
+            parse_svn_branch("^/vendor/lib"),
+            Some("vendor/lib".to_string())
+        );
+        assert_eq!(parse_svn_branch("^/"), None);
+    }
+}
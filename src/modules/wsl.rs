@@ -0,0 +1,27 @@
+use std::env;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::wsl::WslConfig;
+
+/// Creates a module with the WSL distribution the shell is running in
+///
+/// Will display the distribution iff `$WSL_DISTRO_NAME` is set, which WSL sets to the
+/// name the distribution is registered under (e.g. `Ubuntu-20.04`).
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let distro = env::var("WSL_DISTRO_NAME").unwrap_or_default();
+    let distro = distro.trim();
+    if distro.is_empty() {
+        return None;
+    }
+
+    let mut module = context.new_module("wsl");
+    let config: WslConfig = WslConfig::try_load(module.config);
+
+    module.set_style(config.style);
+    module.get_prefix().set_value("in ");
+    module.create_segment("symbol", &config.symbol);
+    module.create_segment("distro", &config.distro.with_value(distro));
+
+    Some(module)
+}
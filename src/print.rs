@@ -1,21 +1,56 @@
 use clap::ArgMatches;
 use rayon::prelude::*;
+use std::env;
 use std::fmt::Write as FmtWrite;
 use std::io::{self, Write};
+use std::time::{Duration, SystemTime};
+use unicode_width::UnicodeWidthStr;
 
-use crate::context::Context;
+use crate::configs::ModuleGroupConfig;
+use crate::context::{Context, Shell};
 use crate::module::Module;
 use crate::module::ALL_MODULES;
 use crate::modules;
+use crate::prompt_cache::PromptCache;
 
 pub fn prompt(args: ArgMatches) {
+    let explain = args.is_present("explain");
+    let refresh_cache = args.is_present("refresh_cache");
     let context = Context::new(args);
+    let config = context.config.get_root_config();
     let stdout = io::stdout();
     let mut handle = stdout.lock();
-    write!(handle, "{}", get_prompt(context)).unwrap();
+
+    // Explaining the prompt needs every module to be computed
+    if !config.prompt_cache || explain {
+        write!(handle, "{}", get_prompt(&context, explain)).unwrap();
+        return;
+    }
+
+    // A cached prompt is printed straight away, then rendered again in the background
+    let cache = PromptCache::new(&context, Duration::from_secs(config.prompt_cache_max_age));
+    if let (Some(cache), false) = (&cache, refresh_cache) {
+        let now = SystemTime::now();
+        if let Some(cached) = cache.read(now) {
+            write!(handle, "{}", cached.prompt).unwrap();
+            cache.spawn_refresh(&cached, now);
+            return;
+        }
+    }
+
+    let prompt = get_prompt(&context, false);
+    write!(handle, "{}", prompt).unwrap();
+    if let Some(cache) = cache {
+        cache.write(&prompt, SystemTime::now());
+        if refresh_cache {
+            cache.finish_refresh();
+        }
+    }
 }
 
-pub fn get_prompt(context: Context) -> String {
+/// Render the prompt, optionally writing to stderr whether each module in the
+/// prompt order was rendered or skipped, and why
+pub fn get_prompt(context: &Context, explain: bool) -> String {
     let config = context.config.get_root_config();
     let mut buf = String::new();
 
@@ -24,46 +59,250 @@ pub fn get_prompt(context: Context) -> String {
         writeln!(buf).unwrap();
     }
 
-    let mut prompt_order: Vec<&str> = Vec::new();
+    let mut prompt_items: Vec<PromptItem> = Vec::new();
 
     // Write out a custom prompt order
-    for module in config.prompt_order {
-        if ALL_MODULES.contains(&module) {
-            prompt_order.push(module);
+    for item in config.prompt_order {
+        if ALL_MODULES.contains(&item) {
+            prompt_items.push(PromptItem::Module(item));
+        } else if let Some(group) = config.groups.get(item) {
+            let modules = group
+                .modules
+                .iter()
+                .copied()
+                .filter(|module| ALL_MODULES.contains(module))
+                .collect();
+            prompt_items.push(PromptItem::Group(group.clone(), modules));
         } else {
             log::debug!(
-                "Expected prompt_order to contain value from {:?}. Instead received {}",
+                "Expected prompt_order to contain value from {:?} or a group. Instead received {}",
                 ALL_MODULES,
-                module,
+                item,
             );
         }
     }
 
-    let modules = &prompt_order
+    // Modules are computed in the order they're printed, including the members of groups
+    let prompt_order = prompt_items
+        .iter()
+        .flat_map(|item| match item {
+            PromptItem::Module(module) => vec![*module],
+            PromptItem::Group(_, modules) => modules.clone(),
+        })
+        .collect::<Vec<&str>>();
+
+    let computed_modules = prompt_order
         .par_iter()
-        .filter(|module| !context.is_module_disabled_in_config(module))
-        .map(|module| modules::handle(module, &context)) // Compute modules
-        .flatten()
-        .collect::<Vec<Module>>(); // Remove segments set to `None`
+        .map(|module| {
+            if context.is_module_disabled_in_config(module)
+                || !context.is_module_enabled_for_shell(module)
+            {
+                return (*module, None);
+            }
+            (*module, context.compute_module(module)) // Compute modules
+        })
+        .collect::<Vec<(&str, Option<Module>)>>();
 
-    let mut print_without_prefix = true;
-    let printable = modules.iter();
+    if explain {
+        explain_modules(context, &computed_modules);
+    }
+
+    let computed_modules = computed_modules
+        .into_iter()
+        .map(|(_, module)| module)
+        .collect::<Vec<Option<Module>>>();
+
+    // Whitespace is collapsed before the width of the prompt is measured
+    let (marker, collapse) = (config.max_width_marker, config.collapse_whitespace);
+    let render = |dropped: &[&str]| {
+        let prompt = render_prompt(&prompt_items, &computed_modules, dropped, marker);
+        if collapse {
+            collapse_whitespace(&prompt)
+        } else {
+            prompt
+        }
+    };
+
+    let mut dropped: Vec<&str> = Vec::new();
+    let mut prompt = render(&dropped);
 
-    for module in printable {
-        // Skip printing the prefix of a module after the line_break
-        if print_without_prefix {
-            let module_without_prefix = module.to_string_without_prefix();
-            write!(buf, "{}", module_without_prefix).unwrap()
+    let terminal_width = env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok());
+    if let Some(max_width) = config
+        .max_width
+        .and_then(|max_width| max_width.to_columns(terminal_width))
+    {
+        let drop_order = if config.max_width_drop_order.is_empty() {
+            // Drop modules from the end of the prompt first, keeping line breaks and the character
+            prompt_order
+                .iter()
+                .rev()
+                .copied()
+                .filter(|module| *module != "line_break" && *module != "character")
+                .collect()
         } else {
-            write!(buf, "{}", module).unwrap();
+            config.max_width_drop_order.clone()
+        };
+
+        // The line of the prompt each module is rendered on
+        let module_lines = prompt_order
+            .iter()
+            .scan(0, |line, module| {
+                let module_line = *line;
+                if *module == "line_break" {
+                    *line += 1;
+                }
+                Some(module_line)
+            })
+            .collect::<Vec<usize>>();
+
+        for module in drop_order {
+            let line_widths = prompt.split('\n').map(visible_len).collect::<Vec<usize>>();
+            if line_widths.iter().all(|width| *width <= max_width) {
+                break;
+            }
+
+            // Only drop modules which are shown on a line that's too wide
+            let line = prompt_order
+                .iter()
+                .zip(&computed_modules)
+                .zip(&module_lines)
+                .find(|((name, computed), _)| **name == module && computed.is_some())
+                .map(|(_, line)| *line);
+            let is_too_wide = match line {
+                Some(line) => line_widths.get(line).copied().unwrap_or(0) > max_width,
+                None => false,
+            };
+            if !is_too_wide || dropped.contains(&module) {
+                continue;
+            }
+
+            log::debug!(
+                "Dropping {} to fit the prompt within {} columns",
+                module,
+                max_width
+            );
+            dropped.push(module);
+            prompt = render(&dropped);
         }
+    }
+    buf.push_str(&prompt);
 
-        print_without_prefix = module.get_name() == "line_break"
+    wrap_colorseq_for_shell(buf, &context.shell)
+}
+
+/// An entry of the prompt order, which is either a module or a group of modules
+enum PromptItem<'a> {
+    Module(&'a str),
+    Group(ModuleGroupConfig<'a>, Vec<&'a str>),
+}
+
+/// Render the computed modules in the prompt order, replacing the `dropped` modules with
+/// `marker`. Consecutive dropped modules share a single marker.
+fn render_prompt(
+    prompt_items: &[PromptItem],
+    computed_modules: &[Option<Module>],
+    dropped: &[&str],
+    marker: &str,
+) -> String {
+    let mut buf = String::new();
+    let mut computed_modules = computed_modules.iter();
+    let mut print_without_prefix = true;
+    let mut follows_marker = false;
+
+    for item in prompt_items {
+        match item {
+            PromptItem::Module(name) => {
+                // Skip modules set to `None`
+                let module = match computed_modules.next().and_then(Option::as_ref) {
+                    Some(module) => module,
+                    None => continue,
+                };
+
+                if dropped.contains(name) {
+                    if !follows_marker {
+                        buf.push_str(marker);
+                        print_without_prefix &= marker.is_empty();
+                        follows_marker = true;
+                    }
+                    continue;
+                }
+
+                // Skip printing the prefix of a module after the line_break
+                if print_without_prefix {
+                    let module_without_prefix = module.to_string_without_prefix();
+                    write!(buf, "{}", module_without_prefix).unwrap()
+                } else {
+                    write!(buf, "{}", module).unwrap();
+                }
+
+                print_without_prefix = module.get_name() == "line_break";
+                follows_marker = false;
+            }
+            PromptItem::Group(group, modules) => {
+                let (dropped_members, members): (Vec<_>, Vec<_>) = modules
+                    .iter()
+                    .zip(computed_modules.by_ref().take(modules.len()))
+                    .filter_map(|(name, module)| Some((*name, module.as_ref()?)))
+                    .partition(|(name, _)| dropped.contains(name));
+
+                // Omit the delimiters when none of the members are shown
+                if !members.is_empty() {
+                    let members = members
+                        .into_iter()
+                        .map(|(_, module)| module)
+                        .collect::<Vec<&Module>>();
+                    write!(buf, "{}", render_group(group, &members)).unwrap();
+                    print_without_prefix = false;
+                    follows_marker = false;
+                }
+
+                if !dropped_members.is_empty() && !follows_marker {
+                    buf.push_str(marker);
+                    print_without_prefix &= marker.is_empty();
+                    follows_marker = true;
+                }
+            }
+        }
     }
 
     buf
 }
 
+/// Render the members of a group between its delimiters, without their prefixes and suffixes
+fn render_group(group: &ModuleGroupConfig, members: &[&Module]) -> String {
+    let members = members
+        .iter()
+        .map(|module| module.to_string_without_affixes())
+        .collect::<Vec<String>>();
+
+    format!(
+        "{}{}{}",
+        group.open,
+        members.join(group.separator),
+        group.close
+    )
+}
+
+fn explain_modules(context: &Context, computed_modules: &[(&str, Option<Module>)]) {
+    let stderr = io::stderr();
+    let mut handle = stderr.lock();
+
+    for (name, module) in computed_modules {
+        match module {
+            Some(_) => writeln!(handle, "{}: rendered", name).unwrap(),
+            None => writeln!(
+                handle,
+                "{}: skipped ({})",
+                name,
+                context.get_skip_reason(name)
+            )
+            .unwrap(),
+        }
+    }
+}
+
 pub fn module(module_name: &str, args: ArgMatches) {
     let context = Context::new(args);
 
@@ -72,5 +311,174 @@ pub fn module(module_name: &str, args: ArgMatches) {
         .map(|m| m.to_string())
         .unwrap_or_default();
 
-    print!("{}", module);
+    print!("{}", wrap_colorseq_for_shell(module, &context.shell));
+}
+
+/// Remove the unstyled spaces which follow another space, such as the suffix of a module
+/// whose styled text already ends with a space. Styled spaces are kept, so the spacing
+/// within a module isn't changed.
+fn collapse_whitespace(ansi: &str) -> String {
+    const ESCAPE_BEGIN: char = '\u{1b}';
+    const ESCAPE_END: char = 'm';
+    const RESET: &str = "\u{1b}[0m";
+
+    let mut collapsed = String::with_capacity(ansi.len());
+    let mut escape = String::new();
+    let mut is_styled = false;
+    let mut follows_space = false;
+
+    for c in ansi.chars() {
+        if !escape.is_empty() {
+            escape.push(c);
+            if c == ESCAPE_END {
+                is_styled = escape != RESET;
+                collapsed.push_str(&escape);
+                escape.clear();
+            }
+            continue;
+        }
+
+        match c {
+            ESCAPE_BEGIN => escape.push(c),
+            ' ' if follows_space && !is_styled => {}
+            _ => {
+                follows_space = c == ' ';
+                collapsed.push(c);
+            }
+        }
+    }
+
+    collapsed.push_str(&escape);
+    collapsed
+}
+
+/// The number of columns a line of the prompt takes up in the terminal, ignoring ANSI
+/// escape sequences
+fn visible_len(ansi: &str) -> usize {
+    const ESCAPE_BEGIN: char = '\u{1b}';
+    const ESCAPE_END: char = 'm';
+
+    let mut escaped = false;
+    let visible = ansi
+        .chars()
+        .filter(|c| match *c {
+            ESCAPE_BEGIN => {
+                escaped = true;
+                false
+            }
+            ESCAPE_END if escaped => {
+                escaped = false;
+                false
+            }
+            _ => !escaped,
+        })
+        .collect::<String>();
+
+    UnicodeWidthStr::width(visible.as_str())
+}
+
+/// Many shells cannot deal with raw unprintable characters (like ANSI escape sequences) and
+/// miscompute the cursor position as a result, leading to strange visual bugs. Here, we wrap these
+/// characters in shell-specific escape codes to indicate to the shell that they are zero-length.
+fn wrap_colorseq_for_shell(ansi: String, shell: &Shell) -> String {
+    const ESCAPE_BEGIN: char = '\u{1b}';
+    const ESCAPE_END: char = 'm';
+
+    let (beginning, ending) = match shell {
+        Shell::Bash => ("\u{5c}\u{5b}", "\u{5c}\u{5d}"), // => \[ and \]
+        Shell::Zsh => ("\u{25}\u{7b}", "\u{25}\u{7d}"),  // => %{ and %}
+        _ => return ansi,
+    };
+
+    let mut escaped = false;
+    let mut final_string = String::with_capacity(ansi.len());
+    for x in ansi.chars() {
+        match x {
+            ESCAPE_BEGIN if !escaped => {
+                escaped = true;
+                final_string.push_str(beginning);
+                final_string.push(x);
+            }
+            ESCAPE_END if escaped => {
+                escaped = false;
+                final_string.push(x);
+                final_string.push_str(ending);
+            }
+            _ => final_string.push(x),
+        }
+    }
+
+    final_string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapse_whitespace() {
+        use ansi_term::{ANSIStrings, Color, Style};
+
+        let prompt = ANSIStrings(&[
+            Style::default().paint("via "),
+            Color::Yellow.bold().paint("🐍 v3.8.0 "),
+            Style::default().paint(" via "),
+            Color::Green.bold().paint("⬢ v12.13.0 "),
+            Style::default().paint(" "),
+        ])
+        .to_string();
+        let expected = ANSIStrings(&[
+            Style::default().paint("via "),
+            Color::Yellow.bold().paint("🐍 v3.8.0 "),
+            Style::default().paint("via "),
+            Color::Green.bold().paint("⬢ v12.13.0 "),
+        ])
+        .to_string();
+        assert_eq!(collapse_whitespace(&prompt), expected);
+    }
+
+    #[test]
+    fn test_collapse_whitespace_keeps_styled_spaces() {
+        use ansi_term::Color;
+
+        let prompt = format!("a  {}", Color::Red.paint("b  c"));
+        let expected = format!("a {}", Color::Red.paint("b  c"));
+        assert_eq!(collapse_whitespace(&prompt), expected);
+    }
+
+    #[test]
+    fn test_visible_len() {
+        use ansi_term::Color;
+
+        assert_eq!(visible_len(""), 0);
+        assert_eq!(visible_len("via "), 4);
+        assert_eq!(
+            visible_len(&format!("via {} ", Color::Yellow.bold().paint("v3.8.0"))),
+            11
+        );
+        // Emoji take up two columns
+        assert_eq!(visible_len(&Color::Red.paint("🐍 ").to_string()), 3);
+    }
+
+    #[test]
+    fn test_wrap_colorseq_for_bash() {
+        let ansi = "\u{1b}[1;31mred\u{1b}[0m plain \u{1b}[34mblue\u{1b}[0m".to_string();
+        let expected =
+            "\\[\u{1b}[1;31m\\]red\\[\u{1b}[0m\\] plain \\[\u{1b}[34m\\]blue\\[\u{1b}[0m\\]";
+        assert_eq!(wrap_colorseq_for_shell(ansi, &Shell::Bash), expected);
+    }
+
+    #[test]
+    fn test_wrap_colorseq_for_zsh() {
+        let ansi = "\u{1b}[1;31mred\u{1b}[0m plain \u{1b}[34mblue\u{1b}[0m".to_string();
+        let expected = "%{\u{1b}[1;31m%}red%{\u{1b}[0m%} plain %{\u{1b}[34m%}blue%{\u{1b}[0m%}";
+        assert_eq!(wrap_colorseq_for_shell(ansi, &Shell::Zsh), expected);
+    }
+
+    #[test]
+    fn test_wrap_colorseq_for_other_shells() {
+        let ansi = "\u{1b}[1;31mred\u{1b}[0m".to_string();
+        assert_eq!(wrap_colorseq_for_shell(ansi.clone(), &Shell::Fish), ansi);
+        assert_eq!(wrap_colorseq_for_shell(ansi.clone(), &Shell::Unknown), ansi);
+    }
 }
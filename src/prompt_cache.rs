@@ -0,0 +1,309 @@
+use git2::Repository;
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::RootModuleConfig;
+use crate::configs::cmd_duration::CmdDurationConfig;
+use crate::configs::history::HistoryConfig;
+use crate::context::Context;
+use crate::utils;
+
+/// The directory in the user's cache directory in which rendered prompts are cached,
+/// shared by every shell
+const CACHE_DIR: &str = "prompt";
+
+/// The extension of the file marking that a cache entry is being refreshed
+const REFRESH_EXTENSION: &str = "refresh";
+
+/// How long after a prompt is cached it's printed again without refreshing it
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long a refresh may run before it's assumed to have died and is started again
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Environment variables which shells change on every command without affecting the prompt
+const VOLATILE_ENV: &[&str] = &["_", "OLDPWD", "PWD"];
+
+/// A rendered prompt cached on disk, keyed by everything the prompt is rendered from
+///
+/// The key covers the directory, the commit HEAD points to, the prompt arguments,
+/// the environment and the configuration. Anything else a module reads, such as the
+/// working tree of a repo or the time, is only updated by the refresh which follows
+/// a cache hit, so the prompt can lag behind by one prompt. Prompts cached in the last
+/// couple of seconds, or already being refreshed, aren't refreshed again.
+pub struct PromptCache {
+    cache_file: PathBuf,
+    max_age: Duration,
+}
+
+/// A prompt read from the cache, and how long ago it was rendered
+pub struct CachedPrompt {
+    pub prompt: String,
+    pub age: Duration,
+}
+
+impl PromptCache {
+    /// Find the cache entry for the prompt rendered from `context`, or `None` if
+    /// there's no cache directory to keep it in
+    pub fn new(context: &Context, max_age: Duration) -> Option<Self> {
+        let cache_dir = utils::get_cache_dir()?.join(CACHE_DIR);
+        if let Err(e) = fs::create_dir_all(&cache_dir) {
+            log::debug!("Unable to create the prompt cache {:?}: {}", cache_dir, e);
+            return None;
+        }
+
+        Some(PromptCache {
+            cache_file: cache_dir.join(format!("{:016x}", cache_key(context))),
+            max_age,
+        })
+    }
+
+    /// Read the cached prompt, unless it is older than `max_age`
+    pub fn read(&self, now: SystemTime) -> Option<CachedPrompt> {
+        let cache = utils::read_file(&self.cache_file).ok()?;
+        let mut parts = cache.splitn(2, '\n');
+
+        let read_at = UNIX_EPOCH + Duration::from_secs(parts.next()?.parse().ok()?);
+        // A prompt cached in the future means the clock changed, so it can't be trusted
+        let age = match now.duration_since(read_at) {
+            Ok(age) if age < self.max_age => age,
+            _ => return None,
+        };
+
+        Some(CachedPrompt {
+            prompt: parts.next()?.to_string(),
+            age,
+        })
+    }
+
+    /// Cache a newly rendered prompt, and remove the entries which have expired
+    pub fn write(&self, prompt: &str, now: SystemTime) {
+        let read_at = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let contents = format!("{}\n{}", read_at, prompt);
+
+        if let Err(e) = utils::write_file_atomically(&self.cache_file, &contents) {
+            log::debug!(
+                "Unable to write the prompt cache {:?}: {}",
+                self.cache_file,
+                e
+            );
+        }
+
+        if let Some(cache_dir) = self.cache_file.parent() {
+            prune_cache(cache_dir, self.max_age.max(REFRESH_TIMEOUT), now);
+        }
+    }
+
+    /// Render the prompt again in the background with `--refresh-cache`, so the next
+    /// cache hit is up to date
+    ///
+    /// Nothing is done when the prompt was only just cached, or is already being refreshed.
+    pub fn spawn_refresh(&self, cached: &CachedPrompt, now: SystemTime) {
+        if cached.age < REFRESH_INTERVAL || !self.start_refresh(now) {
+            return;
+        }
+
+        let exe = match env::current_exe() {
+            Ok(exe) => exe,
+            Err(e) => {
+                log::debug!("Unable to find the starship executable: {}", e);
+                self.finish_refresh();
+                return;
+            }
+        };
+
+        let result = Command::new(exe)
+            .args(env::args_os().skip(1))
+            .arg("--refresh-cache")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Err(e) = result {
+            log::debug!("Unable to refresh the prompt cache: {}", e);
+            self.finish_refresh();
+        }
+    }
+
+    /// Mark the entry as being refreshed, unless another refresh is already running
+    fn start_refresh(&self, now: SystemTime) -> bool {
+        let refresh_file = self.refresh_file();
+        if is_older_than(&refresh_file, REFRESH_TIMEOUT, now) {
+            let _ = fs::remove_file(&refresh_file);
+        }
+
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(refresh_file)
+            .is_ok()
+    }
+
+    /// Allow the entry to be refreshed again
+    pub fn finish_refresh(&self) {
+        let _ = fs::remove_file(self.refresh_file());
+    }
+
+    fn refresh_file(&self) -> PathBuf {
+        self.cache_file.with_extension(REFRESH_EXTENSION)
+    }
+}
+
+/// Remove the files in the prompt cache which haven't been written for `max_age`
+fn prune_cache(cache_dir: &Path, max_age: Duration, now: SystemTime) {
+    let entries = match fs::read_dir(cache_dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::debug!("Unable to read the prompt cache {:?}: {}", cache_dir, e);
+            return;
+        }
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| is_older_than(path, max_age, now))
+        .for_each(|path| {
+            let _ = fs::remove_file(path);
+        });
+}
+
+/// Whether a file was last modified longer than `max_age` before `now`
+fn is_older_than(path: &Path, max_age: Duration, now: SystemTime) -> bool {
+    let age = fs::symlink_metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok());
+
+    age.map(|age| age >= max_age) == Some(true)
+}
+
+/// Hash everything the prompt is rendered from into the key of its cache entry
+fn cache_key(context: &Context) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    context.current_dir.hash(&mut hasher);
+    get_head_oid(context).hash(&mut hasher);
+
+    let mut properties = context
+        .properties
+        .iter()
+        .filter(|(name, value)| is_relevant_property(context, name, value))
+        .collect::<Vec<_>>();
+    properties.sort();
+    properties.hash(&mut hasher);
+
+    let mut env_vars = env::vars_os()
+        .filter(|(name, _)| !VOLATILE_ENV.iter().any(|volatile| name == volatile))
+        .collect::<Vec<(OsString, OsString)>>();
+    env_vars.sort();
+    env_vars.hash(&mut hasher);
+
+    context
+        .config
+        .config
+        .as_ref()
+        .map(|config| config.to_string())
+        .hash(&mut hasher);
+
+    hasher.finish()
+}
+
+/// The commit HEAD points to, when `current_dir` is in a git repo
+fn get_head_oid(context: &Context) -> Option<String> {
+    let repo_root = context.get_repo().ok()?.root.as_ref()?;
+    let repository = Repository::open(repo_root).ok()?;
+    let head = repository.head().ok()?;
+    head.target().map(|oid| oid.to_string())
+}
+
+/// Whether a prompt argument changes the rendered prompt. The command duration and
+/// history number change after every command, so they're only part of the key when
+/// they're shown.
+fn is_relevant_property(context: &Context, name: &str, value: &str) -> bool {
+    match name {
+        "cmd_duration" => {
+            let config = CmdDurationConfig::try_load(context.config.get_module_config(name));
+            let elapsed = value.parse::<i64>().unwrap_or(0);
+            !config.disabled && elapsed >= config.min_time
+        }
+        "history_number" => {
+            let config = HistoryConfig::try_load(context.config.get_module_config("history"));
+            !config.disabled
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_cache_age() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = PromptCache {
+            cache_file: dir.path().join("0123456789abcdef"),
+            max_age: Duration::from_secs(60),
+        };
+        let start = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        assert!(cache.read(start).is_none());
+
+        cache.write("\n~/rocket on master\n❯ ", start);
+        let cached = cache.read(start + Duration::from_secs(59)).unwrap();
+        assert_eq!(cached.prompt, "\n~/rocket on master\n❯ ");
+        assert_eq!(cached.age, Duration::from_secs(59));
+        assert!(cache.read(start + Duration::from_secs(60)).is_none());
+        // The clock went backwards since the prompt was cached
+        assert!(cache.read(start - Duration::from_secs(1)).is_none());
+
+        dir.close()
+    }
+
+    #[test]
+    fn test_prompt_cache_single_refresh() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = PromptCache {
+            cache_file: dir.path().join("0123456789abcdef"),
+            max_age: Duration::from_secs(60),
+        };
+        let now = SystemTime::now();
+
+        assert!(cache.start_refresh(now));
+        assert!(!cache.start_refresh(now));
+        // A refresh which never finished is given up on
+        assert!(cache.start_refresh(now + REFRESH_TIMEOUT));
+
+        cache.finish_refresh();
+        assert!(cache.start_refresh(now));
+
+        dir.close()
+    }
+
+    #[test]
+    fn test_prune_cache() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let old_entry = dir.path().join("0123456789abcdef");
+        fs::write(&old_entry, "0\nold prompt")?;
+        let now = SystemTime::now();
+
+        prune_cache(dir.path(), Duration::from_secs(60), now);
+        assert!(old_entry.exists());
+
+        prune_cache(
+            dir.path(),
+            Duration::from_secs(60),
+            now + Duration::from_secs(60),
+        );
+        assert!(!old_entry.exists());
+
+        dir.close()
+    }
+}
@@ -13,6 +13,9 @@ pub struct Segment {
 
     /// The string value of the current segment.
     value: String,
+
+    /// Whether the segment is dropped when rendering if its value is empty.
+    hide_when_empty: bool,
 }
 
 impl Segment {
@@ -22,6 +25,7 @@ impl Segment {
             _name: name.to_string(),
             style: None,
             value: "".to_string(),
+            hide_when_empty: false,
         }
     }
 
@@ -45,6 +49,12 @@ impl Segment {
         self
     }
 
+    /// Sets whether the segment is dropped when rendering if its value is empty.
+    pub fn set_hide_when_empty(&mut self, hide_when_empty: bool) -> &mut Self {
+        self.hide_when_empty = hide_when_empty;
+        self
+    }
+
     // Returns the ANSIString of the segment value, not including its prefix and suffix
     pub fn ansi_string(&self) -> ANSIString {
         match self.style {
@@ -53,10 +63,25 @@ impl Segment {
         }
     }
 
+    /// Get the value of the segment
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+
+    /// Get the style of the segment, which is `None` when it inherits the module's style
+    pub fn get_style(&self) -> Option<Style> {
+        self.style
+    }
+
     /// Determines if the segment contains a value.
     pub fn is_empty(&self) -> bool {
         self.value.trim().is_empty()
     }
+
+    /// Determines if the segment should be dropped when rendering.
+    pub fn is_hidden(&self) -> bool {
+        self.hide_when_empty && self.is_empty()
+    }
 }
 
 impl fmt::Display for Segment {
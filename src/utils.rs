@@ -1,6 +1,7 @@
-use std::fs::File;
-use std::io::{Read, Result};
-use std::path::Path;
+use std::env;
+use std::fs::{self, DirBuilder, File, OpenOptions};
+use std::io::{Read, Result, Write};
+use std::path::{Path, PathBuf};
 
 /// Return the string contents of a file
 pub fn read_file<P: AsRef<Path>>(file_name: P) -> Result<String> {
@@ -10,3 +11,160 @@ pub fn read_file<P: AsRef<Path>>(file_name: P) -> Result<String> {
     file.read_to_string(&mut data)?;
     Ok(data)
 }
+
+/// Return a single value stored in a file
+///
+/// If `key` is `None`, the file is treated as a plain-value file (e.g. `rust-toolchain`
+/// or `.python-version`) and its first non-empty line is returned.
+/// Otherwise the file is treated as a simple `key = value` file (e.g. `pyvenv.cfg`)
+/// and the value of the first line matching `key` is returned, without any quotes.
+pub fn read_file_value<P: AsRef<Path>>(file_name: P, key: Option<&str>) -> Option<String> {
+    let contents = read_file(file_name).ok()?;
+    let mut lines = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let value = match key {
+        None => lines.next()?,
+        Some(key) => lines.find_map(|line| {
+            let mut pair = line.splitn(2, '=');
+            if pair.next()?.trim() == key {
+                pair.next()
+            } else {
+                None
+            }
+        })?,
+    };
+
+    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+    if value.is_empty() {
+        return None;
+    }
+
+    Some(value.to_string())
+}
+
+/// Return the directory starship keeps its cache in, creating it if needed
+///
+/// This is `$STARSHIP_CACHE` when set, or `starship` in the user's cache directory.
+/// A newly created directory is only accessible by the user, so that nobody else can
+/// read or plant entries in it.
+pub fn get_cache_dir() -> Option<PathBuf> {
+    let cache_dir = match env::var_os("STARSHIP_CACHE") {
+        Some(cache_dir) => PathBuf::from(cache_dir),
+        None => dirs::cache_dir()?.join("starship"),
+    };
+
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+    match builder.create(&cache_dir) {
+        Ok(()) => Some(cache_dir),
+        Err(e) => {
+            log::debug!(
+                "Unable to create the cache directory {:?}: {}",
+                cache_dir,
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Replace the contents of a file, so that it's never read half written
+///
+/// The contents are written to a new file of this process first, which is then renamed
+/// over `file_name`, replacing rather than following a symlink in its place.
+pub fn write_file_atomically<P: AsRef<Path>>(file_name: P, contents: &str) -> Result<()> {
+    let file_name = file_name.as_ref();
+    let mut temp_name = file_name.as_os_str().to_owned();
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_file = PathBuf::from(temp_name);
+
+    // Left over by an earlier process with the same id
+    let _ = fs::remove_file(&temp_file);
+
+    let result = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&temp_file)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .and_then(|_| fs::rename(&temp_file, file_name));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_file);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_temp_file(contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "{}", contents).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_read_file_value_bare_value() {
+        let file = write_temp_file("\n3.8.1\n3.7.5\n");
+        assert_eq!(
+            read_file_value(file.path(), None),
+            Some("3.8.1".to_string())
+        );
+
+        let file = write_temp_file("   \n");
+        assert_eq!(read_file_value(file.path(), None), None);
+    }
+
+    #[test]
+    fn test_read_file_value_key_value() {
+        let file = write_temp_file(
+            "home = /usr/bin\ninclude-system-site-packages = false\nversion = 3.8.1\n",
+        );
+        assert_eq!(
+            read_file_value(file.path(), Some("version")),
+            Some("3.8.1".to_string())
+        );
+        assert_eq!(
+            read_file_value(file.path(), Some("home")),
+            Some("/usr/bin".to_string())
+        );
+        assert_eq!(read_file_value(file.path(), Some("prompt")), None);
+
+        let file = write_temp_file("# comment\nname=\"rocket\"\n");
+        assert_eq!(
+            read_file_value(file.path(), Some("name")),
+            Some("rocket".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read_file_value_missing_file() {
+        assert_eq!(read_file_value("/does/not/exist", None), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_write_file_atomically_replaces_symlink() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let target = dir.path().join("target");
+        let link = dir.path().join("link");
+        fs::write(&target, "untouched")?;
+        std::os::unix::fs::symlink(&target, &link)?;
+
+        write_file_atomically(&link, "written")?;
+        assert_eq!(read_file(&target)?, "untouched");
+        assert_eq!(read_file(&link)?, "written");
+        assert!(!fs::symlink_metadata(&link)?.file_type().is_symlink());
+        assert_eq!(fs::read_dir(dir.path())?.count(), 2);
+
+        dir.close()
+    }
+}
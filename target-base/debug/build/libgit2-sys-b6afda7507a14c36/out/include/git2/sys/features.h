@@ -0,0 +1,8 @@
+#ifndef INCLUDE_features_h
+#define INCLUDE_features_h
+#define GIT_THREADS 1
+#define GIT_USE_NSEC 1
+#define GIT_USE_STAT_MTIM 1
+#define GIT_ARCH_64 1
+#define GIT_SHA1_COLLISIONDETECT 1
+#endif
@@ -0,0 +1,10 @@
+{
+  description = "Rocket launch \"control\" tools";
+
+  inputs.nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+
+  outputs = { self, nixpkgs }: {
+    packages.x86_64-linux.default = nixpkgs.legacyPackages.x86_64-linux.hello;
+    # description = "not the flake's description";
+  };
+}
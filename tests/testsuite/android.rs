@@ -0,0 +1,81 @@
+use ansi_term::Color;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use crate::common::{self, TestCommand};
+
+fn copy_build_gradle(dir: &Path) -> io::Result<()> {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/android/build.gradle");
+    fs::copy(fixture, dir.join("build.gradle"))?;
+    Ok(())
+}
+
+#[test]
+fn folder_without_android_files() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("build.gradle"))?.sync_all()?;
+
+    let output = common::render_module("android")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_android_build_gradle() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    copy_build_gradle(dir.path())?;
+
+    let output = common::render_module("android")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Green.bold().paint("🤖 API 29"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_android_app_build_gradle() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::create_dir(dir.path().join("app"))?;
+    copy_build_gradle(&dir.path().join("app"))?;
+
+    let output = common::render_module("android")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Green.bold().paint("🤖 API 29"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_android_target_sdk() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    copy_build_gradle(dir.path())?;
+
+    let output = common::render_module("android")
+        .arg("--path")
+        .arg(dir.path())
+        .use_config(toml::toml! {
+            [android]
+            show_target_sdk = true
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Green.bold().paint("🤖 API 28"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -1,7 +1,9 @@
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, Write};
+use std::path::Path;
 
-use ansi_term::Color;
+use ansi_term::{ANSIStrings, Color};
+use chrono::{Duration, Utc};
 use tempfile;
 
 use crate::common::{self, TestCommand};
@@ -232,3 +234,88 @@ fn region_not_set_with_display_region() -> io::Result<()> {
     assert_eq!(expected, actual);
     Ok(())
 }
+
+/// Create a home directory holding the given AWS SSO token cache files
+fn create_sso_cache(files: &[(&str, &str)]) -> io::Result<tempfile::TempDir> {
+    let home_dir = tempfile::tempdir()?;
+    let cache_dir = home_dir.path().join(".aws/sso/cache");
+    fs::create_dir_all(&cache_dir)?;
+    for (name, contents) in files {
+        fs::write(cache_dir.join(name), contents)?;
+    }
+    Ok(home_dir)
+}
+
+#[test]
+fn sso_session_active() -> io::Result<()> {
+    let expires_at = (Utc::now() + Duration::seconds(2 * 3600 + 30)).to_rfc3339();
+    let token = format!(
+        r#"{{"accessToken": "secret", "expiresAt": "{}"}}"#,
+        expires_at
+    );
+    let home_dir = create_sso_cache(&[("token.json", &token)])?;
+
+    let output = common::render_module("aws")
+        .env("HOME", home_dir.path())
+        .env("AWS_REGION", "ap-northeast-2")
+        .use_config(toml::toml! {
+            [aws]
+            show_sso_expiry = true
+        })
+        .output()?;
+    let expected = format!(
+        "on {} ",
+        Color::Yellow.bold().paint("☁️  ap-northeast-2 [2h0m]")
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    home_dir.close()
+}
+
+#[test]
+fn sso_session_expired() -> io::Result<()> {
+    let fixtures_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/aws-sso-cache");
+    let home_dir = create_sso_cache(&[])?;
+    let cache_dir = home_dir.path().join(".aws/sso/cache");
+    for entry in fs::read_dir(fixtures_dir)? {
+        let entry = entry?;
+        fs::copy(entry.path(), cache_dir.join(entry.file_name()))?;
+    }
+
+    let output = common::render_module("aws")
+        .env("HOME", home_dir.path())
+        .env("AWS_REGION", "ap-northeast-2")
+        .use_config(toml::toml! {
+            [aws]
+            show_sso_expiry = true
+        })
+        .output()?;
+    let expected = format!(
+        "on {} ",
+        ANSIStrings(&[
+            Color::Yellow.bold().paint("☁️  ap-northeast-2"),
+            Color::Red.bold().paint(" [expired]"),
+        ])
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    home_dir.close()
+}
+
+#[test]
+fn sso_session_without_cache() -> io::Result<()> {
+    let home_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("aws")
+        .env("HOME", home_dir.path())
+        .env("AWS_REGION", "ap-northeast-2")
+        .use_config(toml::toml! {
+            [aws]
+            show_sso_expiry = true
+        })
+        .output()?;
+    let expected = format!("on {} ", Color::Yellow.bold().paint("☁️  ap-northeast-2"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    home_dir.close()
+}
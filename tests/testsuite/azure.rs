@@ -0,0 +1,68 @@
+use ansi_term::Color;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::common::{self, TestCommand};
+
+fn fixture_config_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/azure")
+}
+
+#[test]
+fn disabled_by_default() -> io::Result<()> {
+    let output = common::render_module("azure")
+        .env("AZURE_CONFIG_DIR", fixture_config_dir())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+    Ok(())
+}
+
+#[test]
+fn no_azure_profile() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("azure")
+        .env("AZURE_CONFIG_DIR", dir.path())
+        .use_config(toml::toml! {
+            [azure]
+            disabled = false
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+    dir.close()
+}
+
+#[test]
+fn default_subscription() -> io::Result<()> {
+    let output = common::render_module("azure")
+        .env("AZURE_CONFIG_DIR", fixture_config_dir())
+        .use_config(toml::toml! {
+            [azure]
+            disabled = false
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("on {} ", Color::Blue.bold().paint("🔷 Starship Production"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn default_subscription_with_alias() -> io::Result<()> {
+    let output = common::render_module("azure")
+        .env("AZURE_CONFIG_DIR", fixture_config_dir())
+        .use_config(toml::toml! {
+            [azure]
+            disabled = false
+
+            [azure.subscription_aliases]
+            "Starship Production" = "prod"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("on {} ", Color::Blue.bold().paint("🔷 prod"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use ansi_term::Color;
+use std::fs::File;
+use std::io;
+
+use crate::common;
+
+#[test]
+fn folder_without_bazel_files() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("bazel")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_workspace_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("WORKSPACE"))?.sync_all()?;
+
+    let output = common::render_module("bazel")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Green.bold().paint("🌿 "));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_build_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("BUILD"))?.sync_all()?;
+
+    let output = common::render_module("bazel")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Green.bold().paint("🌿 "));
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use ansi_term::Color;
+use std::fs::File;
+use std::io;
+
+use crate::common;
+
+/// A `bun` which prints a version
+#[cfg(not(windows))]
+const BUN_STUB: &str = "echo '1.0.0'";
+
+#[test]
+fn folder_without_bun_files() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("bun")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_only_package_json() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let bin_dir = tempfile::tempdir()?;
+    let path = common::create_stub_command(bin_dir.path(), "bun", BUN_STUB)?;
+    File::create(dir.path().join("package.json"))?.sync_all()?;
+
+    let output = common::render_module("bun")
+        .env("PATH", path)
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_bunfig_toml() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let bin_dir = tempfile::tempdir()?;
+    let path = common::create_stub_command(bin_dir.path(), "bun", BUN_STUB)?;
+    File::create(dir.path().join("bunfig.toml"))?.sync_all()?;
+
+    let output = common::render_module("bun")
+        .env("PATH", path)
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("🍞 v1.0.0"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_bun_lockb() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let bin_dir = tempfile::tempdir()?;
+    let path = common::create_stub_command(bin_dir.path(), "bun", BUN_STUB)?;
+    File::create(dir.path().join("bun.lockb"))?.sync_all()?;
+
+    let output = common::render_module("bun")
+        .env("PATH", path)
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("🍞 v1.0.0"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_bunfig_toml_without_bun() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("bunfig.toml"))?.sync_all()?;
+
+    let output = common::render_module("bun")
+        .env("PATH", dir.path())
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
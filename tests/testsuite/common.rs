@@ -42,6 +42,30 @@ pub fn render_module(module_name: &str) -> process::Command {
     command
 }
 
+/// Create an executable called `name` in `dir` which runs the shell `script`, returning
+/// a `PATH` which finds it first
+///
+/// Tests use these stubs in place of the tools modules run, such as `node --version`.
+#[cfg(not(windows))]
+pub fn create_stub_command(dir: &Path, name: &str, script: &str) -> io::Result<String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut file = fs::File::create(dir.join(name))?;
+    write!(file, "#!/bin/sh\n{}\n", script)?;
+    file.set_permissions(fs::Permissions::from_mode(0o755))?;
+    file.sync_all()?;
+    Ok(format!("{}:{}", dir.display(), env!("PATH")))
+}
+
+/// Check whether the tests are run as root, who is allowed to write anywhere
+pub fn is_root() -> bool {
+    #[cfg(unix)]
+    return unsafe { libc::getuid() } == 0;
+
+    #[cfg(not(unix))]
+    false
+}
+
 /// Create a repo from the fixture to be used in git module tests
 pub fn create_fixture_repo() -> io::Result<PathBuf> {
     let fixture_repo_path = tempfile::tempdir()?.path().join("fixture");
@@ -39,3 +39,293 @@ fn add_newline_configuration() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn only_on_shells_configuration() -> io::Result<()> {
+    let config = toml::toml! {
+        add_newline = false
+        prompt_order = ["character"]
+
+        [character]
+        only_on_shells = ["zsh"]
+    };
+
+    // The module is skipped on shells which aren't listed
+    let output = common::render_prompt()
+        .use_config(config.clone())
+        .env("STARSHIP_SHELL", "fish")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+
+    // The module is shown on listed shells
+    let output = common::render_prompt()
+        .use_config(config)
+        .env("STARSHIP_SHELL", "zsh")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains("❯"));
+
+    Ok(())
+}
+
+fn module_group_config() -> toml::Value {
+    toml::toml! {
+        add_newline = false
+        prompt_order = ["environments"]
+
+        [groups.environments]
+        modules = ["nix_shell", "profile"]
+    }
+}
+
+#[test]
+fn module_group_without_members() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(module_group_config())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    // The delimiters are omitted when no member is shown
+    assert_eq!("", actual);
+    Ok(())
+}
+
+#[test]
+fn module_group_with_one_member() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(module_group_config())
+        .env("IN_NIX_SHELL", "pure")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("[{}] ", Color::Red.bold().paint("pure"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn module_group_with_many_members() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(module_group_config())
+        .env("IN_NIX_SHELL", "pure")
+        .env(
+            "GUIX_ENVIRONMENT",
+            "/gnu/store/7mh5ldnz4mi8q1fb9ahmgyz1zx0bvy7k-profile",
+        )
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "[{} {}] ",
+        Color::Red.bold().paint("pure"),
+        Color::Yellow.bold().paint("λ guix")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn module_group_with_custom_delimiters() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            add_newline = false
+            prompt_order = ["environments"]
+
+            [groups.environments]
+            modules = ["nix_shell", "profile"]
+            open = "("
+            close = ") "
+            separator = ", "
+        })
+        .env("IN_NIX_SHELL", "pure")
+        .env(
+            "GUIX_ENVIRONMENT",
+            "/gnu/store/7mh5ldnz4mi8q1fb9ahmgyz1zx0bvy7k-profile",
+        )
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "({}, {}) ",
+        Color::Red.bold().paint("pure"),
+        Color::Yellow.bold().paint("λ guix")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn collapse_whitespace_configuration() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            add_newline = false
+            prompt_order = ["nix_shell", "profile"]
+            collapse_whitespace = true
+
+            [nix_shell]
+            pure_msg = "pure "
+        })
+        .env("IN_NIX_SHELL", "pure")
+        .env(
+            "GUIX_ENVIRONMENT",
+            "/gnu/store/7mh5ldnz4mi8q1fb9ahmgyz1zx0bvy7k-profile",
+        )
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    // The suffix of `nix_shell` is dropped, as its message already ends with a space
+    let expected = format!(
+        "{}via {} ",
+        Color::Red.bold().paint("pure "),
+        Color::Yellow.bold().paint("λ guix")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn max_width_measures_collapsed_whitespace() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            add_newline = false
+            prompt_order = ["nix_shell", "profile"]
+            collapse_whitespace = true
+            max_width = 16
+
+            [nix_shell]
+            pure_msg = "pure "
+        })
+        .env("IN_NIX_SHELL", "pure")
+        .env(
+            "GUIX_ENVIRONMENT",
+            "/gnu/store/7mh5ldnz4mi8q1fb9ahmgyz1zx0bvy7k-profile",
+        )
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    // Only fits within 16 columns once the suffix of `nix_shell` is dropped
+    let expected = format!(
+        "{}via {} ",
+        Color::Red.bold().paint("pure "),
+        Color::Yellow.bold().paint("λ guix")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn invalid_style_falls_back_to_default() -> io::Result<()> {
+    let expected = format!("{} ", Color::Green.bold().paint("❯"));
+
+    let output = common::render_module("character")
+        .use_config(toml::toml! {
+            [character]
+            symbol = "❯"
+            style_success = "bold #1é"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn max_width_drops_modules_from_the_end() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            add_newline = false
+            prompt_order = ["conda", "slurm", "nix_shell", "character"]
+            max_width = 30
+        })
+        .env("CONDA_DEFAULT_ENV", "astronauts")
+        .env("SLURM_JOB_ID", "123456")
+        .env("IN_NIX_SHELL", "pure")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "{} in {} … {} ",
+        Color::Green.bold().paint("C astronauts"),
+        Color::Cyan.bold().paint("🖥 123456"),
+        Color::Green.bold().paint("❯")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn max_width_follows_drop_order() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            add_newline = false
+            prompt_order = ["conda", "slurm", "nix_shell", "character"]
+            max_width = 20
+            max_width_drop_order = ["conda", "slurm", "nix_shell"]
+            max_width_marker = "+"
+        })
+        .env("CONDA_DEFAULT_ENV", "astronauts")
+        .env("SLURM_JOB_ID", "123456")
+        .env("IN_NIX_SHELL", "pure")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    // Consecutive dropped modules share a marker
+    let expected = format!(
+        "+via {} {} ",
+        Color::Red.bold().paint("pure"),
+        Color::Green.bold().paint("❯")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn max_width_percentage_of_columns() -> io::Result<()> {
+    let config = toml::toml! {
+        add_newline = false
+        prompt_order = ["conda", "slurm", "character"]
+        max_width = "50%"
+    };
+    let unlimited = format!(
+        "{} in {} {} ",
+        Color::Green.bold().paint("C astronauts"),
+        Color::Cyan.bold().paint("🖥 123456"),
+        Color::Green.bold().paint("❯")
+    );
+
+    // Wide enough for every module
+    let output = common::render_prompt()
+        .use_config(config.clone())
+        .env("CONDA_DEFAULT_ENV", "astronauts")
+        .env("SLURM_JOB_ID", "123456")
+        .env("COLUMNS", "80")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(unlimited, actual);
+
+    let output = common::render_prompt()
+        .use_config(config.clone())
+        .env("CONDA_DEFAULT_ENV", "astronauts")
+        .env("SLURM_JOB_ID", "123456")
+        .env("COLUMNS", "40")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!(
+        "{} … {} ",
+        Color::Green.bold().paint("C astronauts"),
+        Color::Green.bold().paint("❯")
+    );
+    assert_eq!(expected, actual);
+
+    // Without `COLUMNS` a percentage can't be applied
+    let output = common::render_prompt()
+        .use_config(config)
+        .env("CONDA_DEFAULT_ENV", "astronauts")
+        .env("SLURM_JOB_ID", "123456")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(unlimited, actual);
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use ansi_term::Color;
+use std::fs::File;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn folder_without_cpp_files() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("cpp")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_cpp_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("main.cpp"))?.sync_all()?;
+    let compiler_dir = tempfile::tempdir()?;
+    let path = common::create_stub_command(
+        compiler_dir.path(),
+        "stub-cc",
+        "printf 'g++ (GCC) 9.2.0\\nCopyright (C) 2019 Free Software Foundation, Inc.\\n'",
+    )?;
+
+    let output = common::render_module("cpp")
+        .use_config(toml::toml! {
+            [cpp]
+            compiler = "stub-cc"
+        })
+        .env("PATH", path)
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("Ⓒ v9.2.0"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_cmake_lists() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("CMakeLists.txt"))?.sync_all()?;
+    let compiler_dir = tempfile::tempdir()?;
+    let path = common::create_stub_command(
+        compiler_dir.path(),
+        "stub-cc",
+        "printf 'clang version 10.0.0-4ubuntu1\\nTarget: x86_64-pc-linux-gnu\\n'",
+    )?;
+
+    let output = common::render_module("cpp")
+        .use_config(toml::toml! {
+            [cpp]
+            compiler = "stub-cc"
+        })
+        .env("PATH", path)
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("Ⓒ v10.0.0"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
@@ -0,0 +1,176 @@
+use ansi_term::Color;
+use std::fs::File;
+use std::io;
+use std::time::{Duration, SystemTime};
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn folder_without_crystal_files() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("crystal")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+/// A `crystal` which prints a version
+#[cfg(not(windows))]
+const CRYSTAL_STUB: &str = "echo 'Crystal 0.31.1 (2019-10-02)'\necho\necho 'LLVM: 8.0.1'";
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_shard_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("shard.yml"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("crystal")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "crystal", CRYSTAL_STUB)?,
+        )
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("🔮 v0.31.1"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_without_crystal_files_detect_always() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("crystal")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "crystal", CRYSTAL_STUB)?,
+        )
+        .use_config(toml::toml! {
+            [crystal]
+            detect_always = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("🔮 v0.31.1"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_without_crystal_files_detect_always_not_installed() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("crystal")
+        .env("PATH", bin_dir.path())
+        .use_config(toml::toml! {
+            [crystal]
+            detect_always = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_stale_shard_lock() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let shard_lock = File::create(dir.path().join("shard.lock"))?;
+    shard_lock.set_modified(SystemTime::now() - Duration::from_secs(60))?;
+    shard_lock.sync_all()?;
+    File::create(dir.path().join("shard.yml"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("crystal")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "crystal", CRYSTAL_STUB)?,
+        )
+        .use_config(toml::toml! {
+            [crystal]
+            check_shard_lock = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("🔮 v0.31.1 ⚠"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_up_to_date_shard_lock() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let shard_yml = File::create(dir.path().join("shard.yml"))?;
+    shard_yml.set_modified(SystemTime::now() - Duration::from_secs(60))?;
+    shard_yml.sync_all()?;
+    File::create(dir.path().join("shard.lock"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("crystal")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "crystal", CRYSTAL_STUB)?,
+        )
+        .use_config(toml::toml! {
+            [crystal]
+            check_shard_lock = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("🔮 v0.31.1"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn stale_shard_lock_hidden_by_default() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let shard_lock = File::create(dir.path().join("shard.lock"))?;
+    shard_lock.set_modified(SystemTime::now() - Duration::from_secs(60))?;
+    shard_lock.sync_all()?;
+    File::create(dir.path().join("shard.yml"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("crystal")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "crystal", CRYSTAL_STUB)?,
+        )
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("🔮 v0.31.1"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
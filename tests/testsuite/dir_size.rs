@@ -0,0 +1,86 @@
+use ansi_term::Color;
+use std::fs::File;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn disabled_by_default() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("data.bin"))?.set_len(4096)?;
+
+    let output = common::render_module("dir_size")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!("", actual);
+    dir.close()
+}
+
+#[test]
+fn below_threshold() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("data.bin"))?.set_len(4096)?;
+
+    let output = common::render_module("dir_size")
+        .use_config(toml::toml! {
+            [dir_size]
+            disabled = false
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!("", actual);
+    dir.close()
+}
+
+#[test]
+fn above_threshold() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("data.bin"))?.set_len(4096)?;
+    std::fs::create_dir(dir.path().join("nested"))?;
+    File::create(dir.path().join("nested/more.bin"))?.set_len(2048)?;
+
+    let output = common::render_module("dir_size")
+        .use_config(toml::toml! {
+            [dir_size]
+            disabled = false
+            threshold = "1 KiB"
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Cyan.bold().paint("💾 4.0KiB"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn recursive_above_threshold() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("data.bin"))?.set_len(4096)?;
+    std::fs::create_dir(dir.path().join("nested"))?;
+    File::create(dir.path().join("nested/more.bin"))?.set_len(2048)?;
+
+    let output = common::render_module("dir_size")
+        .use_config(toml::toml! {
+            [dir_size]
+            disabled = false
+            threshold = "1 KiB"
+            recursive = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Cyan.bold().paint("💾 6.0KiB"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
@@ -83,6 +83,15 @@ fn fish_directory_in_home() -> io::Result<()> {
     Ok(())
 }
 
+/// System directories belong to root, so they're read-only for other users on Unix
+fn system_directory_lock() -> String {
+    if cfg!(unix) && !common::is_root() {
+        Color::Red.paint(" 🔒").to_string()
+    } else {
+        String::new()
+    }
+}
+
 #[test]
 fn root_directory() -> io::Result<()> {
     let output = common::render_module("directory")
@@ -90,7 +99,11 @@ fn root_directory() -> io::Result<()> {
         .output()?;
     let actual = String::from_utf8(output.stdout).unwrap();
 
-    let expected = format!("in {} ", Color::Cyan.bold().paint("/"));
+    let expected = format!(
+        "in {}{} ",
+        Color::Cyan.bold().paint("/"),
+        system_directory_lock()
+    );
     assert_eq!(expected, actual);
     Ok(())
 }
@@ -103,7 +116,11 @@ fn directory_in_root() -> io::Result<()> {
         .output()?;
     let actual = String::from_utf8(output.stdout).unwrap();
 
-    let expected = format!("in {} ", Color::Cyan.bold().paint("/etc"));
+    let expected = format!(
+        "in {}{} ",
+        Color::Cyan.bold().paint("/etc"),
+        system_directory_lock()
+    );
     assert_eq!(expected, actual);
     Ok(())
 }
@@ -451,3 +468,215 @@ fn git_repo_in_home_directory_truncate_to_repo_true() -> io::Result<()> {
     assert_eq!(expected, actual);
     Ok(())
 }
+
+#[test]
+fn read_only_directory() -> io::Result<()> {
+    // Root can write to the directory anyway, so it isn't shown as read-only
+    if common::is_root() {
+        return Ok(());
+    }
+
+    let tmp_dir = TempDir::new()?;
+    let dir = tmp_dir.path().join("readonly");
+    fs::create_dir(&dir)?;
+
+    let original_permissions = fs::metadata(&dir)?.permissions();
+    let mut permissions = original_permissions.clone();
+    permissions.set_readonly(true);
+    fs::set_permissions(&dir, permissions)?;
+
+    let output = common::render_module("directory")
+        .use_config(toml::toml! {
+            [directory]
+            truncation_length = 1
+        })
+        .arg("--path")
+        .arg(&dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    // Restore the permissions so that the temp dir can be cleaned up
+    fs::set_permissions(&dir, original_permissions)?;
+
+    let expected = format!(
+        "in {}{} ",
+        Color::Cyan.bold().paint("readonly"),
+        Color::Red.paint(" 🔒")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn git_ignored_directory() -> io::Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let repo_dir = tmp_dir.path().join("rocket-controls");
+    let dir = repo_dir.join("target/debug");
+    fs::create_dir_all(&dir)?;
+    fs::write(repo_dir.join(".gitignore"), "target/\n")?;
+    Repository::init(&repo_dir).unwrap();
+
+    let output = common::render_module("directory")
+        .use_config(toml::toml! {
+            [directory]
+            show_git_ignored = true
+        })
+        .arg("--path")
+        .arg(&dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "in {}{} ",
+        Color::Cyan.bold().paint("rocket-controls/target/debug"),
+        Color::Fixed(242).paint(" (ignored)")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn git_ignored_directory_through_symlink() -> io::Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let repo_dir = tmp_dir.path().join("rocket-controls");
+    fs::create_dir_all(repo_dir.join("target/debug"))?;
+    fs::write(repo_dir.join(".gitignore"), "target/\n")?;
+    Repository::init(&repo_dir).unwrap();
+    let link = tmp_dir.path().join("controls");
+    std::os::unix::fs::symlink(&repo_dir, &link)?;
+
+    let output = common::render_module("directory")
+        .use_config(toml::toml! {
+            [directory]
+            show_git_ignored = true
+        })
+        .arg("--path")
+        .arg(link.join("target/debug"))
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "in {}{} ",
+        Color::Cyan.bold().paint("controls/target/debug"),
+        Color::Fixed(242).paint(" (ignored)")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn git_not_ignored_directory() -> io::Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let repo_dir = tmp_dir.path().join("rocket-controls");
+    let dir = repo_dir.join("src/meters");
+    fs::create_dir_all(&dir)?;
+    fs::write(repo_dir.join(".gitignore"), "target/\n")?;
+    Repository::init(&repo_dir).unwrap();
+
+    let output = common::render_module("directory")
+        .use_config(toml::toml! {
+            [directory]
+            show_git_ignored = true
+        })
+        .arg("--path")
+        .arg(&dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "in {} ",
+        Color::Cyan.bold().paint("rocket-controls/src/meters")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn symlinked_directory() -> io::Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let dir = tmp_dir.path().join("launchpad");
+    fs::create_dir(&dir)?;
+    let link = tmp_dir.path().join("pad");
+    std::os::unix::fs::symlink("launchpad", &link)?;
+
+    let config = toml::toml! {
+        [directory]
+        truncation_length = 1
+        show_symlink_target = true
+    };
+
+    let output = common::render_module("directory")
+        .use_config(config.clone())
+        .arg("--path")
+        .arg(&link)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!(
+        "in {}{} ",
+        Color::Cyan.bold().paint("pad"),
+        Color::Fixed(242).paint(" → launchpad")
+    );
+    assert_eq!(expected, actual);
+
+    // The target isn't shown for the directory itself
+    let output = common::render_module("directory")
+        .use_config(config)
+        .arg("--path")
+        .arg(&dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("in {} ", Color::Cyan.bold().paint("launchpad"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn broken_symlinked_directory() -> io::Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let target = tmp_dir.path().join("launchpad");
+    let link = tmp_dir.path().join("pad");
+    std::os::unix::fs::symlink(&target, &link)?;
+
+    let output = common::render_module("directory")
+        .use_config(toml::toml! {
+            [directory]
+            truncation_length = 1
+            show_symlink_target = true
+        })
+        .arg("--path")
+        .arg(&link)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!(
+        "in {}{} ",
+        Color::Cyan.bold().paint("pad"),
+        Color::Fixed(242).paint(format!(" → {}", target.to_string_lossy()))
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn symlinked_directory_target_hidden_by_default() -> io::Result<()> {
+    let tmp_dir = TempDir::new()?;
+    fs::create_dir(tmp_dir.path().join("launchpad"))?;
+    let link = tmp_dir.path().join("pad");
+    std::os::unix::fs::symlink("launchpad", &link)?;
+
+    let output = common::render_module("directory")
+        .use_config(toml::toml! {
+            [directory]
+            truncation_length = 1
+        })
+        .arg("--path")
+        .arg(&link)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("in {} ", Color::Cyan.bold().paint("pad"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -1,18 +1,41 @@
-use super::common;
-use std::fs::{DirBuilder, OpenOptions};
+use super::common::{self, TestCommand};
+use ansi_term::Color;
+use std::fs::{self, DirBuilder, OpenOptions};
 use std::io::{self, Error, ErrorKind, Write};
+use std::path::Path;
 use std::process::{Command, Stdio};
 use tempfile::{self, TempDir};
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn shows_nothing_in_directory_with_zero_relevant_files() -> io::Result<()> {
     let workspace = create_workspace(false)?;
     expect_output(&workspace, ".", None)
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
+fn shows_latest_in_directory_with_zero_relevant_files_detect_always() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    let path = create_stub_dotnet(&workspace, "2.2.402")?;
+
+    let output = common::render_module("dotnet")
+        .env("PATH", path)
+        .use_config(toml::toml! {
+            [dotnet]
+            detect_always = true
+        })
+        .current_dir(workspace.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("•NET v2.2.402"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
 fn shows_latest_in_directory_with_solution() -> io::Result<()> {
     let workspace = create_workspace(false)?;
     touch_path(&workspace, "solution.sln", None)?;
@@ -20,7 +43,7 @@ fn shows_latest_in_directory_with_solution() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn shows_latest_in_directory_with_csproj() -> io::Result<()> {
     let workspace = create_workspace(false)?;
     touch_path(&workspace, "project.csproj", None)?;
@@ -28,7 +51,7 @@ fn shows_latest_in_directory_with_csproj() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn shows_latest_in_directory_with_fsproj() -> io::Result<()> {
     let workspace = create_workspace(false)?;
     touch_path(&workspace, "project.fsproj", None)?;
@@ -36,7 +59,7 @@ fn shows_latest_in_directory_with_fsproj() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn shows_latest_in_directory_with_xproj() -> io::Result<()> {
     let workspace = create_workspace(false)?;
     touch_path(&workspace, "project.xproj", None)?;
@@ -44,7 +67,7 @@ fn shows_latest_in_directory_with_xproj() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn shows_latest_in_directory_with_project_json() -> io::Result<()> {
     let workspace = create_workspace(false)?;
     touch_path(&workspace, "project.json", None)?;
@@ -52,7 +75,15 @@ fn shows_latest_in_directory_with_project_json() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
+fn shows_latest_in_directory_with_directory_build_props() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    touch_path(&workspace, "Directory.Build.props", None)?;
+    expect_output(&workspace, ".", Some("•NET v2.2.402"))
+}
+
+#[test]
+#[cfg(not(windows))]
 fn shows_pinned_in_directory_with_global_json() -> io::Result<()> {
     let workspace = create_workspace(false)?;
     let global_json = make_pinned_sdk_json("1.2.3");
@@ -61,7 +92,7 @@ fn shows_pinned_in_directory_with_global_json() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn shows_pinned_in_project_below_root_with_global_json() -> io::Result<()> {
     let workspace = create_workspace(false)?;
     let global_json = make_pinned_sdk_json("1.2.3");
@@ -71,7 +102,7 @@ fn shows_pinned_in_project_below_root_with_global_json() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn shows_pinned_in_deeply_nested_project_within_repository() -> io::Result<()> {
     let workspace = create_workspace(true)?;
     let global_json = make_pinned_sdk_json("1.2.3");
@@ -80,6 +111,108 @@ fn shows_pinned_in_deeply_nested_project_within_repository() -> io::Result<()> {
     expect_output(&workspace, "deep/path/to/project", Some("•NET v1.2.3"))
 }
 
+#[test]
+#[cfg(not(windows))]
+fn shows_warning_when_pinned_sdk_is_not_installed() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    let global_json = make_pinned_sdk_json("1.2.3");
+    touch_path(&workspace, "global.json", Some(&global_json))?;
+    let path = create_stub_dotnet(&workspace, "3.1.100")?;
+
+    let output = common::render_module("dotnet")
+        .env("PATH", path)
+        .use_config(toml::toml! {
+            [dotnet]
+            check_sdk_mismatch = true
+        })
+        .current_dir(workspace.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("•NET v1.2.3 ⚠"));
+    assert_eq!(expected, actual);
+    workspace.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn shows_no_warning_when_pinned_sdk_is_installed() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    let global_json = make_pinned_sdk_json("1.2.3");
+    touch_path(&workspace, "global.json", Some(&global_json))?;
+    let path = create_stub_dotnet(&workspace, "1.2.3")?;
+
+    let output = common::render_module("dotnet")
+        .env("PATH", path)
+        .use_config(toml::toml! {
+            [dotnet]
+            check_sdk_mismatch = true
+        })
+        .current_dir(workspace.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("•NET v1.2.3"));
+    assert_eq!(expected, actual);
+    workspace.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn shows_target_framework_from_csproj() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    copy_fixture(&workspace, "Rocket.csproj")?;
+    expect_tfm_output(&workspace, "•NET v2.2.402 [net6.0]")
+}
+
+#[test]
+#[cfg(not(windows))]
+fn shows_multiple_target_frameworks_from_csproj() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    copy_fixture(&workspace, "Rocket.Core.csproj")?;
+    expect_tfm_output(&workspace, "•NET v2.2.402 [netstandard2.0, net6.0]")
+}
+
+#[test]
+#[cfg(not(windows))]
+fn shows_no_target_framework_when_not_set() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    touch_path(&workspace, "project.csproj", Some("<Project></Project>"))?;
+    expect_tfm_output(&workspace, "•NET v2.2.402")
+}
+
+#[test]
+#[cfg(not(windows))]
+fn shows_no_target_framework_by_default() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    copy_fixture(&workspace, "Rocket.csproj")?;
+    let path = create_stub_dotnet(&workspace, "2.2.402")?;
+
+    let output = common::render_module("dotnet")
+        .env("PATH", path)
+        .current_dir(workspace.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("•NET v2.2.402"));
+    assert_eq!(expected, actual);
+    workspace.close()
+}
+
+/// Create a `dotnet` reporting the given SDK version in the workspace's `bin` directory,
+/// returning a `PATH` which finds it first
+#[cfg(not(windows))]
+fn create_stub_dotnet(workspace: &TempDir, version: &str) -> io::Result<String> {
+    let bin_dir = workspace.path().join("bin");
+    DirBuilder::new().recursive(true).create(&bin_dir)?;
+
+    let script = format!(
+        "if [ \"$1\" = --list-sdks ]; then\n    echo '{0} [/usr/share/dotnet/sdk]'\nelse\n    echo {0}\nfi",
+        version
+    );
+    common::create_stub_command(&bin_dir, "dotnet", &script)
+}
+
 fn create_workspace(is_repo: bool) -> io::Result<TempDir> {
     let repo_dir = tempfile::tempdir()?;
 
@@ -117,6 +250,14 @@ fn touch_path(workspace: &TempDir, relative_path: &str, contents: Option<&str>)
     file.sync_data()
 }
 
+fn copy_fixture(workspace: &TempDir, name: &str) -> io::Result<()> {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/dotnet")
+        .join(name);
+    fs::copy(fixture, workspace.path().join(name))?;
+    Ok(())
+}
+
 fn make_pinned_sdk_json(version: &str) -> String {
     let json_text = r#"
         {
@@ -128,9 +269,12 @@ fn make_pinned_sdk_json(version: &str) -> String {
     json_text.replace("INSERT_VERSION", version)
 }
 
+#[cfg(not(windows))]
 fn expect_output(workspace: &TempDir, run_from: &str, contains: Option<&str>) -> io::Result<()> {
     let run_path = workspace.path().join(run_from);
+    let path = create_stub_dotnet(workspace, "2.2.402")?;
     let output = common::render_module("dotnet")
+        .env("PATH", path)
         .current_dir(run_path)
         .output()?;
     let text = String::from_utf8(output.stdout).unwrap();
@@ -145,3 +289,21 @@ fn expect_output(workspace: &TempDir, run_from: &str, contains: Option<&str>) ->
 
     Ok(())
 }
+
+#[cfg(not(windows))]
+fn expect_tfm_output(workspace: &TempDir, expected: &str) -> io::Result<()> {
+    let path = create_stub_dotnet(workspace, "2.2.402")?;
+    let output = common::render_module("dotnet")
+        .env("PATH", path)
+        .use_config(toml::toml! {
+            [dotnet]
+            show_tfm = true
+        })
+        .current_dir(workspace.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint(expected));
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use ansi_term::Color;
+use std::fs::File;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn folder_without_mix_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("elixir")
+        .env("MIX_ENV", "prod")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn mix_env_not_set() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("mix.exs"))?.sync_all()?;
+
+    let output = common::render_module("elixir")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn mix_env_prod() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("mix.exs"))?.sync_all()?;
+
+    let output = common::render_module("elixir")
+        .env("MIX_ENV", "prod")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("💧 prod"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn mix_env_test() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("mix.exs"))?.sync_all()?;
+
+    let output = common::render_module("elixir")
+        .env("MIX_ENV", "test")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Purple.bold().paint("💧 test"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn mix_env_test_with_custom_style() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("mix.exs"))?.sync_all()?;
+
+    let output = common::render_module("elixir")
+        .env("MIX_ENV", "test")
+        .use_config(toml::toml! {
+            [elixir.env_styles]
+            test = "yellow"
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Yellow.paint("💧 test"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn mix_env_dev_ignored() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("mix.exs"))?.sync_all()?;
+
+    let output = common::render_module("elixir")
+        .env("MIX_ENV", "dev")
+        .use_config(toml::toml! {
+            [elixir]
+            ignore_default_env = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
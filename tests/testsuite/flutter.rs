@@ -0,0 +1,57 @@
+use ansi_term::Color;
+use std::fs::File;
+use std::io::{self, Write};
+
+use crate::common;
+
+#[test]
+fn folder_without_pubspec() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("flutter")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_pubspec_without_metadata() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("pubspec.yaml"))?.sync_all()?;
+
+    let output = common::render_module("flutter")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_metadata() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("pubspec.yaml"))?.sync_all()?;
+    let mut metadata = File::create(dir.path().join(".metadata"))?;
+    metadata.write_all(
+        b"version:\n  revision: 27321ebbad34b0a3fafe99fac037102196d655ff\n  channel: stable\n\nproject_type: app\n",
+    )?;
+    metadata.sync_all()?;
+
+    let output = common::render_module("flutter")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("🐦 (stable)"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -97,6 +97,93 @@ fn test_truncate_length(
     )
 }
 
+#[test]
+fn test_named_branch() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    Command::new("git")
+        .args(&["checkout", "-b", "feature/rocket-boosters"])
+        .current_dir(repo_dir.as_path())
+        .output()?;
+
+    let output = common::render_module("git_branch")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "on {} ",
+        Color::Purple
+            .bold()
+            .paint("\u{e0a0} feature/rocket-boosters")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn test_detached_head() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    Command::new("git")
+        .args(&["checkout", "--detach", "HEAD~1"])
+        .current_dir(repo_dir.as_path())
+        .output()?;
+
+    let short_hash = Command::new("git")
+        .args(&["rev-parse", "--short", "HEAD"])
+        .current_dir(repo_dir.as_path())
+        .output()?
+        .stdout;
+    let short_hash = String::from_utf8(short_hash).unwrap();
+
+    let output = common::render_module("git_branch")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "on {} ",
+        Color::Purple
+            .bold()
+            .paint(format!("\u{e0a0} {}", short_hash.trim()))
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn test_not_in_repo() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("git_branch")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!("", actual);
+    dir.close()
+}
+
+#[test]
+fn test_nested_svn_checkout() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    let checkout_dir = repo_dir.join("vendor");
+    std::fs::create_dir_all(checkout_dir.join(".svn"))?;
+
+    let output = common::render_module("git_branch")
+        .arg("--path")
+        .arg(&checkout_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!("", actual);
+    Ok(())
+}
+
 fn test_truncate_length_with_config(
     branch_name: &str,
     truncate_length: i64,
@@ -137,3 +224,159 @@ fn test_truncate_length_with_config(
     assert_eq!(expected, actual);
     Ok(())
 }
+
+#[test]
+fn test_worktree_name() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    let worktree_parent = tempfile::tempdir()?;
+    let worktree_dir = worktree_parent.path().join("rocket-fix");
+
+    Command::new("git")
+        .arg("worktree")
+        .arg("add")
+        .arg("-b")
+        .arg("fix-engines")
+        .arg(&worktree_dir)
+        .current_dir(repo_dir.as_path())
+        .output()?;
+
+    let config = toml::toml! {
+        [git_branch]
+        show_worktree = true
+    };
+
+    let output = common::render_module("git_branch")
+        .use_config(config.clone())
+        .arg("--path")
+        .arg(&worktree_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!(
+        "on {} ",
+        Color::Purple
+            .bold()
+            .paint("\u{e0a0} fix-engines (rocket-fix)"),
+    );
+    assert_eq!(expected, actual);
+
+    // The main worktree isn't named
+    let output = common::render_module("git_branch")
+        .use_config(config)
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("on {} ", Color::Purple.bold().paint("\u{e0a0} master"));
+    assert_eq!(expected, actual);
+
+    worktree_parent.close()
+}
+
+#[test]
+fn test_substitutions_rewrite_branch_prefix() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    Command::new("git")
+        .arg("checkout")
+        .arg("-b")
+        .arg("feature/rocket-engines")
+        .current_dir(repo_dir.as_path())
+        .output()?;
+
+    let output = common::render_module("git_branch")
+        .use_config(toml::toml! {
+            [git_branch]
+            substitutions = [
+                { pattern = "\u{e0a0} feature/", replacement = "\u{e0a0} f/" },
+                { pattern = "(unclosed", replacement = "ignored" },
+            ]
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!(
+        "on {} ",
+        Color::Purple.bold().paint("\u{e0a0} f/rocket-engines")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+/// Make the `master` branch of a fixture repo track the `master` branch of an `upstream` remote
+fn track_upstream_remote(repo_dir: &std::path::Path) -> io::Result<()> {
+    let fixture_path = std::env::current_dir()?.join("tests/fixtures/rocket.bundle");
+
+    Command::new("git")
+        .args(&["remote", "add", "upstream"])
+        .arg(fixture_path)
+        .current_dir(repo_dir)
+        .output()?;
+
+    Command::new("git")
+        .args(&["fetch", "upstream"])
+        .current_dir(repo_dir)
+        .output()?;
+
+    Command::new("git")
+        .args(&["branch", "--set-upstream-to=upstream/master"])
+        .current_dir(repo_dir)
+        .output()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_upstream_remote() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    track_upstream_remote(&repo_dir)?;
+
+    let output = common::render_module("git_branch")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "on {} ",
+        Color::Purple.bold().paint("\u{e0a0} master:upstream")
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn test_origin_remote_hidden() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    // The fixture repo's `master` branch tracks `origin/master`
+    let output = common::render_module("git_branch")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("on {} ", Color::Purple.bold().paint("\u{e0a0} master"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn test_show_remote_disabled() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    track_upstream_remote(&repo_dir)?;
+
+    let output = common::render_module("git_branch")
+        .use_config(toml::toml! {
+            [git_branch]
+            show_remote = false
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("on {} ", Color::Purple.bold().paint("\u{e0a0} master"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
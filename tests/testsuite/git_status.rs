@@ -1,7 +1,8 @@
 use ansi_term::{ANSIStrings, Color};
+use std::env;
 use std::fs::{self, File};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::common::{self, TestCommand};
@@ -510,6 +511,344 @@ fn suffix() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+#[ignore]
+fn shows_lfs() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_lfs(&repo_dir)?;
+
+    let output = common::render_module("git_status")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = Color::Red
+        .bold()
+        .paint(format!("[{}] ", "⇡LFS"))
+        .to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn shows_lfs_with_count() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_lfs(&repo_dir)?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            lfs_count.enabled = true
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = Color::Red
+        .bold()
+        .paint(format!("[{}] ", "⇡LFS1"))
+        .to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+fn render_submodules(repo_dir: &Path) -> io::Result<String> {
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            show_submodules = true
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    Ok(String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+#[ignore]
+fn shows_uninitialized_submodule() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_submodule(&repo_dir)?;
+    Command::new("git")
+        .args(&["submodule", "deinit", "--force", "rocket-sub"])
+        .current_dir(&repo_dir)
+        .output()?;
+    barrier();
+
+    let actual = render_submodules(&repo_dir)?;
+    let expected = Color::Red.bold().paint(format!("[{}] ", "⇡⊂")).to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn shows_outdated_submodule() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_submodule(&repo_dir)?;
+    Command::new("git")
+        .args(&["checkout", "HEAD~1"])
+        .current_dir(repo_dir.join("rocket-sub"))
+        .output()?;
+    barrier();
+
+    let actual = render_submodules(&repo_dir)?;
+    let expected = Color::Red.bold().paint(format!("[{}] ", "⇡!⊂")).to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn doesnt_show_up_to_date_submodule() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_submodule(&repo_dir)?;
+
+    let actual = render_submodules(&repo_dir)?;
+    let expected = Color::Red.bold().paint(format!("[{}] ", "⇡")).to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn doesnt_show_submodules_by_default() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_submodule(&repo_dir)?;
+    Command::new("git")
+        .args(&["submodule", "deinit", "--force", "rocket-sub"])
+        .current_dir(&repo_dir)
+        .output()?;
+    barrier();
+
+    let output = common::render_module("git_status")
+        .arg("--path")
+        .arg(&repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = Color::Red.bold().paint(format!("[{}] ", "⇡")).to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn shows_staged_and_untracked_files() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_staged(&repo_dir)?;
+    File::create(repo_dir.join("changelog"))?.sync_all()?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            staged_count.enabled = true
+            untracked_count.enabled = true
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = Color::Red
+        .bold()
+        .paint(format!("[{}] ", "+1?1"))
+        .to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn shows_indicators_with_their_own_styles() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_staged(&repo_dir)?;
+    create_modified(&repo_dir)?;
+    File::create(repo_dir.join("changelog"))?.sync_all()?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            staged = { value = "+", style = "green" }
+            modified = { value = "!", style = "yellow" }
+            untracked = { value = "?", style = "blue" }
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = ANSIStrings(&[
+        Color::Red.bold().paint("["),
+        Color::Yellow.paint("!"),
+        Color::Green.paint("+"),
+        Color::Blue.paint("?"),
+        Color::Red.bold().paint("] "),
+    ])
+    .to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn shows_indicators_without_style_in_module_style() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_staged(&repo_dir)?;
+    File::create(repo_dir.join("changelog"))?.sync_all()?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            staged = { value = "+", style = "green" }
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = ANSIStrings(&[
+        Color::Red.bold().paint("["),
+        Color::Green.paint("+"),
+        Color::Red.bold().paint("?] "),
+    ])
+    .to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn doesnt_show_disabled_indicators() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_staged(&repo_dir)?;
+    File::create(repo_dir.join("changelog"))?.sync_all()?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            disabled_indicators = ["untracked"]
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = Color::Red.bold().paint(format!("[{}] ", "+")).to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn doesnt_show_anything_if_all_indicators_disabled() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_untracked(&repo_dir)?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            disabled_indicators = ["untracked"]
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = "";
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn doesnt_show_clean_repo() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    let output = common::render_module("git_status")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = "";
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn shows_clean_repo() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            show_clean = true
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = Color::Red.bold().paint(format!("[{}] ", "✓")).to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn doesnt_show_clean_with_changes() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_modified(&repo_dir)?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            show_clean = true
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = Color::Red.bold().paint(format!("[{}] ", "!")).to_string();
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
 fn ahead(repo_dir: &PathBuf) -> io::Result<()> {
     File::create(repo_dir.join("readme.md"))?.sync_all()?;
 
@@ -580,6 +919,60 @@ fn create_conflict(repo_dir: &PathBuf) -> io::Result<()> {
     Ok(())
 }
 
+/// Commit LFS attributes, along with a file that is still an LFS pointer
+fn create_lfs(repo_dir: &Path) -> io::Result<()> {
+    fs::copy(
+        env::current_dir()?.join("tests/fixtures/lfs.gitattributes"),
+        repo_dir.join(".gitattributes"),
+    )?;
+    fs::write(
+        repo_dir.join("logo.png"),
+        "version https://git-lfs.github.com/spec/v1\n\
+         oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+         size 12345\n",
+    )?;
+
+    Command::new("git")
+        .arg("add")
+        .arg(".")
+        .current_dir(repo_dir)
+        .output()?;
+    barrier();
+
+    Command::new("git")
+        .arg("commit")
+        .arg("-m")
+        .arg("Add LFS attributes")
+        .current_dir(repo_dir)
+        .output()?;
+    barrier();
+
+    Ok(())
+}
+
+/// Commit another copy of the fixture repo as the submodule `rocket-sub`
+fn create_submodule(repo_dir: &Path) -> io::Result<()> {
+    let submodule_repo_dir = common::create_fixture_repo()?;
+
+    Command::new("git")
+        .args(&["-c", "protocol.file.allow=always", "submodule", "add"])
+        .arg(&submodule_repo_dir)
+        .arg("rocket-sub")
+        .current_dir(repo_dir)
+        .output()?;
+    barrier();
+
+    Command::new("git")
+        .arg("commit")
+        .arg("-m")
+        .arg("Add submodule")
+        .current_dir(repo_dir)
+        .output()?;
+    barrier();
+
+    Ok(())
+}
+
 fn create_untracked(repo_dir: &PathBuf) -> io::Result<()> {
     File::create(repo_dir.join("license"))?.sync_all()?;
 
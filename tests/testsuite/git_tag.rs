@@ -0,0 +1,116 @@
+use ansi_term::Color;
+use std::io;
+use std::process::Command;
+
+use crate::common::{self, TestCommand};
+
+fn tag_head(repo_dir: &std::path::Path, args: &[&str]) -> io::Result<()> {
+    Command::new("git")
+        .arg("tag")
+        .args(args)
+        .current_dir(repo_dir)
+        .output()?;
+    Ok(())
+}
+
+#[test]
+fn shows_nothing_when_untagged() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    let output = common::render_module("git_tag")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn shows_tag_at_head() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    tag_head(&repo_dir, &["v1.0.0"])?;
+
+    let output = common::render_module("git_tag")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::Cyan.bold().paint("🏷 v1.0.0"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn shows_annotated_tag_at_head() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    tag_head(&repo_dir, &["-a", "v2.0.0", "-m", "Release 2.0.0"])?;
+
+    let output = common::render_module("git_tag")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::Cyan.bold().paint("🏷 v2.0.0"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn ignores_tags_on_other_commits() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    tag_head(&repo_dir, &["v0.9.0", "HEAD~1"])?;
+
+    let output = common::render_module("git_tag")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn shows_latest_of_multiple_tags() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    tag_head(&repo_dir, &["v1.0.0"])?;
+    tag_head(&repo_dir, &["v1.0.1"])?;
+
+    let output = common::render_module("git_tag")
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::Cyan.bold().paint("🏷 v1.0.1"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn config_show_all() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    tag_head(&repo_dir, &["v1.0.1"])?;
+    tag_head(&repo_dir, &["v1.0.0"])?;
+
+    let output = common::render_module("git_tag")
+        .use_config(toml::toml! {
+            [git_tag]
+            show_all = true
+            separator = " | "
+        })
+        .arg("--path")
+        .arg(repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::Cyan.bold().paint("🏷 v1.0.0 | v1.0.1"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -5,6 +5,10 @@ use tempfile;
 
 use crate::common;
 
+/// A `go` which prints a version
+#[cfg(not(windows))]
+const GO_STUB: &str = "echo 'go version go1.12.1 darwin/amd64'";
+
 #[test]
 fn folder_without_go_files() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
@@ -21,12 +25,17 @@ fn folder_without_go_files() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_go_file() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("main.go"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
 
     let output = common::render_module("golang")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "go", GO_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -38,12 +47,17 @@ fn folder_with_go_file() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_go_mod() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("go.mod"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
 
     let output = common::render_module("golang")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "go", GO_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -55,12 +69,17 @@ fn folder_with_go_mod() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_go_sum() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("go.sum"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
 
     let output = common::render_module("golang")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "go", GO_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -72,13 +91,18 @@ fn folder_with_go_sum() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_godeps() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     let godeps = dir.path().join("Godeps");
     fs::create_dir_all(&godeps)?;
+    let bin_dir = tempfile::tempdir()?;
 
     let output = common::render_module("golang")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "go", GO_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -90,12 +114,17 @@ fn folder_with_godeps() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_glide_yaml() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("glide.yaml"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
 
     let output = common::render_module("golang")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "go", GO_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -107,12 +136,17 @@ fn folder_with_glide_yaml() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_gopkg_yml() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("Gopkg.yml"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
 
     let output = common::render_module("golang")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "go", GO_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -124,12 +158,39 @@ fn folder_with_gopkg_yml() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_gopkg_lock() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("Gopkg.lock"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("golang")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "go", GO_STUB)?,
+        )
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Cyan.bold().paint("🐹 v1.12.1"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_gopkg_toml() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("Gopkg.toml"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
 
     let output = common::render_module("golang")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "go", GO_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -0,0 +1,48 @@
+use ansi_term::Color;
+use std::fs::{self, File};
+use std::io::{self, Write};
+
+use crate::common;
+
+#[test]
+fn folder_without_gradle_files() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("gradle")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_gradle_wrapper() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("gradlew"))?.sync_all()?;
+
+    let wrapper_dir = dir.path().join("gradle/wrapper");
+    fs::create_dir_all(&wrapper_dir)?;
+    let mut properties = File::create(wrapper_dir.join("gradle-wrapper.properties"))?;
+    properties.write_all(
+        b"distributionBase=GRADLE_USER_HOME\n\
+          distributionPath=wrapper/dists\n\
+          distributionUrl=https\\://services.gradle.org/distributions/gradle-6.3-bin.zip\n\
+          zipStoreBase=GRADLE_USER_HOME\n\
+          zipStorePath=wrapper/dists\n",
+    )?;
+    properties.sync_all()?;
+
+    let output = common::render_module("gradle")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Fixed(14).bold().paint("gradle v6.3"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
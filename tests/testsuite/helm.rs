@@ -0,0 +1,130 @@
+use ansi_term::Color;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::common::{self, TestCommand};
+
+fn fixture_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/helm")
+        .join(name)
+}
+
+fn copy_chart_yaml(dir: &Path) -> io::Result<()> {
+    fs::copy(fixture_path("Chart.yaml"), dir.join("Chart.yaml"))?;
+    Ok(())
+}
+
+#[test]
+fn folder_without_chart() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("helm")
+        .env("KUBECONFIG", fixture_path("kubeconfig"))
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_chart_without_kube_context() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    copy_chart_yaml(dir.path())?;
+
+    let output = common::render_module("helm")
+        .env("KUBECONFIG", dir.path().join("missing-kubeconfig"))
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_chart() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    copy_chart_yaml(dir.path())?;
+
+    let output = common::render_module("helm")
+        .env("KUBECONFIG", fixture_path("kubeconfig"))
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "via {} ",
+        Color::Blue.bold().paint("⎈ rocket@mission-control")
+    );
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_chart_default_namespace() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    copy_chart_yaml(dir.path())?;
+
+    let kubeconfig = dir.path().join("kubeconfig");
+    let mut file = File::create(&kubeconfig)?;
+    file.write_all(
+        b"
+apiVersion: v1
+contexts:
+- context:
+    cluster: launchpad
+    user: astronaut
+  name: launchpad
+current-context: launchpad
+kind: Config
+",
+    )?;
+    file.sync_all()?;
+
+    let output = common::render_module("helm")
+        .env("KUBECONFIG", kubeconfig)
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("⎈ rocket@default"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_chart_show_context() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    copy_chart_yaml(dir.path())?;
+
+    let output = common::render_module("helm")
+        .env("KUBECONFIG", fixture_path("kubeconfig"))
+        .use_config(toml::toml! {
+            [helm]
+            separator = " → "
+            show_context = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "via {} ",
+        Color::Blue
+            .bold()
+            .paint("⎈ rocket → launchpad/mission-control")
+    );
+    assert_eq!(expected, actual);
+    dir.close()
+}
@@ -0,0 +1,80 @@
+use ansi_term::Color;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn disabled_by_default() -> io::Result<()> {
+    let output = common::render_module("history")
+        .arg("--history-number=42")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn history_number_set() -> io::Result<()> {
+    let output = common::render_module("history")
+        .use_config(toml::toml! {
+            [history]
+            disabled = false
+        })
+        .arg("--history-number=42")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::White.dimmed().paint("!42"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn history_number_missing() -> io::Result<()> {
+    let output = common::render_module("history")
+        .use_config(toml::toml! {
+            [history]
+            disabled = false
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn history_number_invalid() -> io::Result<()> {
+    let output = common::render_module("history")
+        .use_config(toml::toml! {
+            [history]
+            disabled = false
+        })
+        .arg("--history-number=")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn config_symbol() -> io::Result<()> {
+    let output = common::render_module("history")
+        .use_config(toml::toml! {
+            [history]
+            disabled = false
+            symbol = "#"
+        })
+        .arg("--history-number=7")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::White.dimmed().paint("#7"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -59,6 +59,24 @@ fn ssh() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn ssh_with_default_config() -> io::Result<()> {
+    let hostname = match get_hostname() {
+        Some(h) => h,
+        None => return hostname_not_tested(),
+    };
+    // The domain is trimmed off by default
+    let host = hostname.split('.').next().unwrap_or_default();
+    let output = common::render_module("hostname")
+        .env_clear()
+        .env("SSH_CONNECTION", "something")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("on {} ", style().paint(host));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
 #[test]
 fn prefix() -> io::Result<()> {
     let hostname = match get_hostname() {
@@ -3,6 +3,29 @@ use std::io;
 
 use crate::common::{self, TestCommand};
 
+#[test]
+fn config_blank_no_jobs_arg() -> io::Result<()> {
+    let output = common::render_module("jobs").output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn config_blank_job_padded() -> io::Result<()> {
+    // `wc -l` pads the count with spaces on some platforms
+    let output = common::render_module("jobs")
+        .arg("--jobs=      2")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::Blue.bold().paint("✦2"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
 #[test]
 fn config_blank_job_0() -> io::Result<()> {
     let output = common::render_module("jobs").arg("--jobs=0").output()?;
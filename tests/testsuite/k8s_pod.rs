@@ -0,0 +1,21 @@
+use std::io;
+use std::path::Path;
+
+use crate::common;
+
+#[test]
+fn not_in_pod() -> io::Result<()> {
+    // The pod is found from the mounted service account, which can't be faked here
+    if Path::new("/var/run/secrets/kubernetes.io/serviceaccount").exists() {
+        return Ok(());
+    }
+
+    let output = common::render_module("k8s_pod")
+        .env("HOSTNAME", "web-5d4f8b7c9-x2k4q")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
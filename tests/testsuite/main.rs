@@ -1,23 +1,50 @@
+mod android;
 mod aws;
+mod azure;
+mod bazel;
+mod bun;
 mod character;
 mod cmd_duration;
 mod common;
 mod conda;
 mod configuration;
+mod cpp;
+mod crystal;
+mod dir_size;
 mod directory;
 mod dotnet;
+mod elixir;
 mod env_var;
+mod flutter;
 mod git_branch;
 mod git_state;
 mod git_status;
+mod git_tag;
 mod golang;
+mod gradle;
+mod helm;
+mod history;
 mod hostname;
 mod jobs;
+mod k8s_pod;
 mod line_break;
 mod modules;
+mod multiplexer;
+mod nix_flake;
 mod nix_shell;
 mod nodejs;
+mod ocaml;
+mod perl;
+mod podman;
+mod profile;
+mod prompt_cache;
+mod pulumi;
 mod python;
 mod ruby;
+mod sandbox;
+mod slurm;
+mod terraform;
 mod time;
 mod username;
+mod vcs_branch;
+mod wsl;
@@ -1,6 +1,7 @@
 use std::io;
 
 use crate::common;
+use crate::common::TestCommand;
 
 #[test]
 fn unknown_module_name() -> io::Result<()> {
@@ -29,3 +30,91 @@ fn known_module_name() -> io::Result<()> {
     assert_eq!(expected_stderr, actual_stderr);
     Ok(())
 }
+
+#[test]
+fn explain_skipped_modules() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let output = common::render_prompt()
+        .arg("--explain")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual_stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(actual_stderr.contains("directory: rendered\n"));
+    assert!(actual_stderr.contains("python: skipped (not applicable)\n"));
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn explain_timed_out_command() -> io::Result<()> {
+    use std::fs::File;
+
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("package.json"))?.sync_all()?;
+    File::create(dir.path().join("main.go"))?.sync_all()?;
+
+    // `node` hangs, while `go` isn't installed
+    let bin_dir = tempfile::tempdir()?;
+    common::create_stub_command(bin_dir.path(), "node", "sleep 10")?;
+
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            command_timeout = 100
+        })
+        .env(
+            "PATH",
+            format!("{}:/bin:/usr/bin", bin_dir.path().display()),
+        )
+        .arg("--explain")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual_stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        actual_stderr.contains("nodejs: skipped (timed out)\n"),
+        "{}",
+        actual_stderr
+    );
+    assert!(
+        actual_stderr.contains("golang: skipped (command failed)\n"),
+        "{}",
+        actual_stderr
+    );
+    assert!(
+        actual_stderr.contains("python: skipped (not applicable)\n"),
+        "{}",
+        actual_stderr
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn explain_failed_command() -> io::Result<()> {
+    use std::fs::File;
+
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("rocket.csproj"))?.sync_all()?;
+
+    // `dotnet` exits with an error, without the module recording why it was skipped
+    let bin_dir = tempfile::tempdir()?;
+    common::create_stub_command(bin_dir.path(), "dotnet", "exit 1")?;
+
+    let output = common::render_prompt()
+        .env(
+            "PATH",
+            format!("{}:/bin:/usr/bin", bin_dir.path().display()),
+        )
+        .arg("--explain")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual_stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        actual_stderr.contains("dotnet: skipped (command failed)\n"),
+        "{}",
+        actual_stderr
+    );
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use ansi_term::Color;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+fn render_multiplexer(env: &[(&str, &str)]) -> io::Result<String> {
+    let output = common::render_module("multiplexer")
+        .envs(env.iter().cloned())
+        .use_config(toml::toml! {
+            [multiplexer]
+            disabled = false
+        })
+        .output()?;
+    Ok(String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn disabled_by_default() -> io::Result<()> {
+    let output = common::render_module("multiplexer")
+        .env("TMUX", "/tmp/tmux-1000/default,1234,0")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn not_in_multiplexer() -> io::Result<()> {
+    let actual = render_multiplexer(&[])?;
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn in_tmux() -> io::Result<()> {
+    let actual = render_multiplexer(&[("TMUX", "/tmp/tmux-1000/default,1234,0")])?;
+
+    let expected = format!("in {} ", Color::Green.bold().paint("⧉ tmux"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn in_screen() -> io::Result<()> {
+    let actual = render_multiplexer(&[("STY", "1234.pts-0.rocket")])?;
+
+    let expected = format!("in {} ", Color::Green.bold().paint("⧉ screen"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn in_screen_inside_tmux() -> io::Result<()> {
+    let actual = render_multiplexer(&[
+        ("TMUX", "/tmp/tmux-1000/default,1234,0"),
+        ("STY", "1234.pts-0.rocket"),
+    ])?;
+
+    let expected = format!("in {} ", Color::Green.bold().paint("⧉ tmux"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn empty_tmux_variable() -> io::Result<()> {
+    let actual = render_multiplexer(&[("TMUX", "")])?;
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn custom_symbols() -> io::Result<()> {
+    let output = common::render_module("multiplexer")
+        .env("STY", "1234.pts-0.rocket")
+        .use_config(toml::toml! {
+            [multiplexer]
+            symbol = ""
+            screen = "🖥 "
+            style = "bold blue"
+            disabled = false
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("in {} ", Color::Blue.bold().paint("🖥 "));
+    assert_eq!(expected, actual);
+    Ok(())
+}
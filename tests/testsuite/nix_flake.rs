@@ -0,0 +1,101 @@
+use ansi_term::Color;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::common;
+
+fn copy_flake_nix(dir: &Path) -> io::Result<()> {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/nix_flake/flake.nix");
+    fs::copy(fixture, dir.join("flake.nix"))?;
+    Ok(())
+}
+
+#[test]
+fn folder_without_flake() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("nix_flake")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_flake() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    copy_flake_nix(dir.path())?;
+
+    let output = common::render_module("nix_flake")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "via {} ",
+        Color::Blue
+            .bold()
+            .paint("❄️ Rocket launch \"control\" tools")
+    );
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn description_from_flake_metadata() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::write(dir.path().join("flake.nix"), "import ./flake-outputs.nix\n")?;
+    let bin_dir = tempfile::tempdir()?;
+    let path = common::create_stub_command(
+        bin_dir.path(),
+        "nix",
+        r#"echo '{"description":"Imported rocket flake","path":"/nix/store/rocket"}'"#,
+    )?;
+
+    let output = common::render_module("nix_flake")
+        .env("PATH", path)
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "via {} ",
+        Color::Blue.bold().paint("❄️ Imported rocket flake")
+    );
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn flake_without_description() -> io::Result<()> {
+    let parent = tempfile::tempdir()?;
+    let dir = parent.path().join("rocket");
+    fs::create_dir(&dir)?;
+    fs::write(dir.join("flake.nix"), "{\n  outputs = { self }: { };\n}\n")?;
+    let bin_dir = tempfile::tempdir()?;
+    let path = common::create_stub_command(
+        bin_dir.path(),
+        "nix",
+        r#"echo '{"path":"/nix/store/rocket"}'"#,
+    )?;
+
+    let output = common::render_module("nix_flake")
+        .env("PATH", path)
+        .arg("--path")
+        .arg(&dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("❄️ rocket"));
+    assert_eq!(expected, actual);
+    parent.close()
+}
@@ -1,9 +1,11 @@
 use ansi_term::Color;
 use std::fs::{self, File};
 use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
 use tempfile;
 
-use crate::common;
+use crate::common::{self, TestCommand};
 
 /// Wrapper around common::render_module("nodejs") to work around platform quirks
 fn render_node_module() -> std::process::Command {
@@ -25,6 +27,10 @@ fn render_node_module() -> std::process::Command {
     command
 }
 
+/// A `node` which prints a version
+#[cfg(not(windows))]
+const NODE_STUB: &str = "echo 'v12.0.0'";
+
 #[test]
 fn folder_without_node_files() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
@@ -41,12 +47,18 @@ fn folder_without_node_files() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_package_json() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("package.json"))?.sync_all()?;
 
+    let bin_dir = tempfile::tempdir()?;
+
     let output = render_node_module()
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "node", NODE_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -58,12 +70,18 @@ fn folder_with_package_json() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_js_file() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("index.js"))?.sync_all()?;
 
+    let bin_dir = tempfile::tempdir()?;
+
     let output = render_node_module()
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "node", NODE_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -75,13 +93,41 @@ fn folder_with_js_file() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_node_modules() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     let node_modules = dir.path().join("node_modules");
     fs::create_dir_all(&node_modules)?;
 
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = render_node_module()
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "node", NODE_STUB)?,
+        )
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_node_version() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join(".node-version"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
     let output = render_node_module()
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "node", NODE_STUB)?,
+        )
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -91,3 +137,102 @@ fn folder_with_node_modules() -> io::Result<()> {
     assert_eq!(expected, actual);
     Ok(())
 }
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_ts_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("index.ts"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = render_node_module()
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "node", NODE_STUB)?,
+        )
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn render_lockfile_check(dir: &Path) -> io::Result<String> {
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = render_node_module()
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "node", NODE_STUB)?,
+        )
+        .use_config(toml::toml! {
+            [nodejs]
+            check_lockfile = true
+        })
+        .arg("--path")
+        .arg(dir)
+        .output()?;
+    Ok(String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_without_lockfile() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("package.json"))?.sync_all()?;
+
+    let actual = render_lockfile_check(dir.path())?;
+
+    let expected = format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0 ⚠"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_stale_lockfile() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let lockfile = File::create(dir.path().join("package-lock.json"))?;
+    lockfile.set_modified(SystemTime::now() - Duration::from_secs(60))?;
+    lockfile.sync_all()?;
+    File::create(dir.path().join("package.json"))?.sync_all()?;
+
+    let actual = render_lockfile_check(dir.path())?;
+
+    let expected = format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0 ⚠"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_up_to_date_lockfile() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let package_json = File::create(dir.path().join("package.json"))?;
+    package_json.set_modified(SystemTime::now() - Duration::from_secs(60))?;
+    package_json.sync_all()?;
+    File::create(dir.path().join("yarn.lock"))?.sync_all()?;
+
+    let actual = render_lockfile_check(dir.path())?;
+
+    let expected = format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_js_file_and_no_package_json() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("index.js"))?.sync_all()?;
+
+    let actual = render_lockfile_check(dir.path())?;
+
+    let expected = format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
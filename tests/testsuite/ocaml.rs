@@ -0,0 +1,123 @@
+use ansi_term::Color;
+use std::fs::File;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn folder_without_ocaml_files() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("ocaml")
+        .env("OPAMSWITCH", "4.10.0")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_dune_project() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("dune-project"))?.sync_all()?;
+
+    let output = common::render_module("ocaml")
+        .env("OPAMSWITCH", "4.10.0")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Yellow.bold().paint("🐫 4.10.0"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_ml_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("main.ml"))?.sync_all()?;
+
+    let output = common::render_module("ocaml")
+        .env("OPAMSWITCH", "rocket")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Yellow.bold().paint("🐫 rocket"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn default_switch_shown() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("dune"))?.sync_all()?;
+
+    let output = common::render_module("ocaml")
+        .env("OPAMSWITCH", "default")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Yellow.bold().paint("🐫 default"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn config_ignore_default_switch() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("dune"))?.sync_all()?;
+
+    let output = common::render_module("ocaml")
+        .env("OPAMSWITCH", "default")
+        .use_config(toml::toml! {
+            [ocaml]
+            ignore_default_switch = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+/// An `opam` which prints a switch
+#[cfg(not(windows))]
+const OPAM_STUB: &str = "echo '/home/starship/rocket'";
+
+#[test]
+#[cfg(not(windows))]
+fn switch_from_opam() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("rocket.opam"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("ocaml")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "opam", OPAM_STUB)?,
+        )
+        .env_remove("OPAMSWITCH")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "via {} ",
+        Color::Yellow.bold().paint("🐫 /home/starship/rocket")
+    );
+    assert_eq!(expected, actual);
+    dir.close()
+}
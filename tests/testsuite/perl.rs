@@ -0,0 +1,131 @@
+use ansi_term::Color;
+use std::fs;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn folder_without_local_lib() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("perl")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_carton_marker() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::create_dir(dir.path().join(".carton"))?;
+
+    let output = common::render_module("perl")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Fixed(149).bold().paint("🐪 carton"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_with_carton_local_lib() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::create_dir_all(dir.path().join("local/lib/perl5"))?;
+
+    let output = common::render_module("perl")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Fixed(149).bold().paint("🐪 carton"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn perl5lib_set() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("perl")
+        .env("PERL5LIB", "/home/astronaut/perl5/lib/perl5")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Fixed(149).bold().paint("🐪 local::lib"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn perl5lib_empty() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("perl")
+        .env("PERL5LIB", "")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn folder_without_local_lib_detect_always_not_installed() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("perl")
+        .env("PATH", bin_dir.path())
+        .use_config(toml::toml! {
+            [perl]
+            detect_always = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_without_local_lib_detect_always() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("perl")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "perl", "echo 'This is perl 5'")?,
+        )
+        .use_config(toml::toml! {
+            [perl]
+            detect_always = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    // A Carton project isn't claimed just because the module is always shown
+    let expected = format!("via {} ", Color::Fixed(149).bold().paint("🐪 "));
+    assert_eq!(expected, actual);
+    dir.close()
+}
@@ -0,0 +1,77 @@
+use ansi_term::Color;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::common;
+
+fn fixture_containers_conf() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/podman/containers.conf")
+}
+
+#[test]
+fn no_containers_conf() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("podman")
+        .env("XDG_CONFIG_HOME", dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+    dir.close()
+}
+
+#[test]
+fn active_service_from_containers_conf() -> io::Result<()> {
+    let output = common::render_module("podman")
+        .env("CONTAINERS_CONF", fixture_containers_conf())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Purple.bold().paint("🦭 build-server"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn active_service_from_xdg_config_home() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::create_dir(dir.path().join("containers"))?;
+    fs::copy(
+        fixture_containers_conf(),
+        dir.path().join("containers/containers.conf"),
+    )?;
+
+    let output = common::render_module("podman")
+        .env("XDG_CONFIG_HOME", dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Purple.bold().paint("🦭 build-server"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn connection_env_var_overrides_containers_conf() -> io::Result<()> {
+    let output = common::render_module("podman")
+        .env("CONTAINERS_CONF", fixture_containers_conf())
+        .env("CONTAINER_CONNECTION", "staging")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Purple.bold().paint("🦭 staging"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn default_machine_connection() -> io::Result<()> {
+    let output = common::render_module("podman")
+        .env("CONTAINERS_CONF", fixture_containers_conf())
+        .env("CONTAINER_CONNECTION", "podman-machine-default")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+    Ok(())
+}
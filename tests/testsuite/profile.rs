@@ -0,0 +1,66 @@
+use ansi_term::Color;
+use std::fs;
+use std::io;
+
+use crate::common;
+
+#[test]
+fn no_env_variables() -> io::Result<()> {
+    let output = common::render_module("profile").output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+    Ok(())
+}
+
+#[test]
+fn guix_environment() -> io::Result<()> {
+    let output = common::render_module("profile")
+        .env(
+            "GUIX_ENVIRONMENT",
+            "/gnu/store/7mh5ldnz4mi8q1fb9ahmgyz1zx0bvy7k-profile",
+        )
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("via {} ", Color::Yellow.bold().paint("λ guix"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn guix_profile_with_generation() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let generation = dir.path().join("guix-profile-42-link");
+    fs::create_dir(&generation)?;
+    let profile = dir.path().join("guix-profile");
+    std::os::unix::fs::symlink(&generation, &profile)?;
+
+    let output = common::render_module("profile")
+        .env("GUIX_PROFILE", &profile)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("via {} ", Color::Yellow.bold().paint("λ guix #42"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn nix_profile_with_generation() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let generation = dir.path().join("profile-7-link");
+    fs::create_dir(&generation)?;
+    let profile = dir.path().join("nix-profile");
+    std::os::unix::fs::symlink("profile-7-link", &profile)?;
+
+    let output = common::render_module("profile")
+        .env(
+            "NIX_PROFILES",
+            format!("/nix/var/nix/profiles/default {}", profile.display()),
+        )
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("via {} ", Color::Yellow.bold().paint("λ nix #7"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+
+use crate::common;
+
+/// Render the prompt of `repo_dir` with the prompt cache kept in `cache_dir`
+#[cfg(not(windows))]
+fn render_cached_prompt(repo_dir: &Path, cache_dir: &Path, config: &Path) -> io::Result<String> {
+    let output = common::render_prompt()
+        .arg("--path")
+        .arg(repo_dir)
+        .env("STARSHIP_CONFIG", config)
+        .env("STARSHIP_CACHE", cache_dir)
+        .output()?;
+    Ok(String::from_utf8(output.stdout).unwrap())
+}
+
+/// Replace the prompt in the single cache entry, so a cache hit can be told apart
+#[cfg(not(windows))]
+fn tamper_with_cache(cache_dir: &Path) -> io::Result<()> {
+    let entries = fs::read_dir(cache_dir.join("prompt"))?.collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(entries.len(), 1);
+
+    let cache_file = entries[0].path();
+    let contents = fs::read_to_string(&cache_file)?;
+    let read_at = contents.lines().next().unwrap();
+    fs::write(cache_file, format!("{}\ncached prompt", read_at))
+}
+
+#[test]
+#[cfg(not(windows))]
+fn test_prompt_cache_hit_and_miss_on_changed_head() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    let cache_dir = tempfile::tempdir()?;
+    let mut config_file = tempfile::NamedTempFile::new()?;
+    writeln!(config_file, "prompt_cache = true\nadd_newline = false")?;
+    let config = config_file.path();
+
+    // Nothing is cached yet
+    let actual = render_cached_prompt(&repo_dir, cache_dir.path(), config)?;
+    assert!(actual.contains("master"), "{}", actual);
+
+    tamper_with_cache(cache_dir.path())?;
+    let actual = render_cached_prompt(&repo_dir, cache_dir.path(), config)?;
+    assert_eq!(actual, "cached prompt");
+
+    // Committing moves HEAD, so the cached prompt no longer applies
+    Command::new("git")
+        .args(&["commit", "--allow-empty", "-m", "Move HEAD"])
+        .current_dir(&repo_dir)
+        .output()?;
+    let actual = render_cached_prompt(&repo_dir, cache_dir.path(), config)?;
+    assert_ne!(actual, "cached prompt");
+    assert!(actual.contains("master"), "{}", actual);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn test_prompt_cache_private_to_user() -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let repo_dir = common::create_fixture_repo()?;
+    let cache_dir = tempfile::tempdir()?;
+    let cache_dir = cache_dir.path().join("starship");
+    let mut config_file = tempfile::NamedTempFile::new()?;
+    writeln!(config_file, "prompt_cache = true")?;
+
+    render_cached_prompt(&repo_dir, &cache_dir, config_file.path())?;
+
+    let mode = fs::metadata(&cache_dir)?.permissions().mode();
+    assert_eq!(mode & 0o777, 0o700);
+    assert_eq!(fs::read_dir(cache_dir.join("prompt"))?.count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_prompt_cache_disabled_by_default() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+    let cache_dir = tempfile::tempdir()?;
+
+    common::render_prompt()
+        .arg("--path")
+        .arg(&repo_dir)
+        .env("STARSHIP_CACHE", cache_dir.path())
+        .output()?;
+
+    assert!(!cache_dir.path().join("prompt").exists());
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use ansi_term::Color;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::common;
+
+/// Write the workspace file Pulumi keeps for the project in `dir`, which selects `stack`
+fn create_workspace(pulumi_home: &Path, project: &str, dir: &Path, stack: &str) -> io::Result<()> {
+    let project_file = dir.join("Pulumi.yaml");
+    let hash = sha1_smol::Sha1::from(project_file.to_str().unwrap())
+        .digest()
+        .to_string();
+
+    let workspaces = pulumi_home.join("workspaces");
+    fs::create_dir_all(&workspaces)?;
+    let mut file = File::create(workspaces.join(format!("{}-{}-workspace.json", project, hash)))?;
+    write!(file, "{{\n    \"stack\": \"{}\"\n}}\n", stack)?;
+    file.sync_all()
+}
+
+#[test]
+fn folder_without_pulumi_files() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("pulumi")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_pulumi_yaml() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mut file = File::create(dir.path().join("Pulumi.yaml"))?;
+    file.write_all(b"name: rocket\nruntime: nodejs\n")?;
+    file.sync_all()?;
+
+    let pulumi_home = tempfile::tempdir()?;
+    create_workspace(pulumi_home.path(), "rocket", dir.path(), "dev")?;
+    // A project elsewhere whose name starts with the same prefix
+    let other_dir = tempfile::tempdir()?;
+    create_workspace(
+        pulumi_home.path(),
+        "rocket-engines",
+        other_dir.path(),
+        "prod",
+    )?;
+
+    let output = common::render_module("pulumi")
+        .env("PULUMI_HOME", pulumi_home.path())
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Purple.bold().paint("🧊 dev"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_workspace_of_another_project() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mut file = File::create(dir.path().join("Pulumi.yaml"))?;
+    file.write_all(b"name: rocket\nruntime: nodejs\n")?;
+    file.sync_all()?;
+
+    // The same project name, checked out in another directory
+    let pulumi_home = tempfile::tempdir()?;
+    let other_dir = tempfile::tempdir()?;
+    create_workspace(pulumi_home.path(), "rocket", other_dir.path(), "prod")?;
+
+    let output = common::render_module("pulumi")
+        .env("PULUMI_HOME", pulumi_home.path())
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -1,9 +1,13 @@
-use ansi_term::Color;
+use ansi_term::{ANSIStrings, Color};
 use std::fs::File;
 use std::io;
 use tempfile;
 
-use crate::common;
+use crate::common::{self, TestCommand};
+
+/// A `ruby` which prints a version
+#[cfg(not(windows))]
+const RUBY_STUB: &str = "echo 'ruby 2.6.3p62 (2019-04-16 revision 67580) [x86_64-linux]'";
 
 #[test]
 fn folder_without_ruby_files() -> io::Result<()> {
@@ -21,12 +25,19 @@ fn folder_without_ruby_files() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_gemfile() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("Gemfile"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
 
     let output = common::render_module("ruby")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "ruby", RUBY_STUB)?,
+        )
+        .env_remove("RAILS_ENV")
+        .env_remove("RACK_ENV")
         .arg("--path")
         .arg(dir.path())
         .output()?;
@@ -38,12 +49,149 @@ fn folder_with_gemfile() -> io::Result<()> {
 }
 
 #[test]
-#[ignore]
+#[cfg(not(windows))]
 fn folder_with_rb_file() -> io::Result<()> {
     let dir = tempfile::tempdir()?;
     File::create(dir.path().join("any.rb"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("ruby")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "ruby", RUBY_STUB)?,
+        )
+        .env_remove("RAILS_ENV")
+        .env_remove("RACK_ENV")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("💎 v2.6.3"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_with_ruby_version() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join(".ruby-version"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("ruby")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "ruby", RUBY_STUB)?,
+        )
+        .env_remove("RAILS_ENV")
+        .env_remove("RACK_ENV")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("💎 v2.6.3"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn rails_env_production() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("Gemfile"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("ruby")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "ruby", RUBY_STUB)?,
+        )
+        .env("RAILS_ENV", "production")
+        .env_remove("RACK_ENV")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "via {} ",
+        ANSIStrings(&[
+            Color::Red.bold().paint("💎 v2.6.3 ("),
+            Color::Red.bold().underline().paint("production"),
+            Color::Red.bold().paint(")"),
+        ])
+    );
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn rack_env_staging() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("Gemfile"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("ruby")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "ruby", RUBY_STUB)?,
+        )
+        .env_remove("RAILS_ENV")
+        .env("RACK_ENV", "staging")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("💎 v2.6.3 (staging)"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn rails_env_unset() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("Gemfile"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("ruby")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "ruby", RUBY_STUB)?,
+        )
+        .env_remove("RAILS_ENV")
+        .env_remove("RACK_ENV")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.bold().paint("💎 v2.6.3"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn config_ignore_default_env() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("Gemfile"))?.sync_all()?;
+    let bin_dir = tempfile::tempdir()?;
 
     let output = common::render_module("ruby")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "ruby", RUBY_STUB)?,
+        )
+        .env("RAILS_ENV", "development")
+        .use_config(toml::toml! {
+            [ruby]
+            ignore_default_env = true
+        })
         .arg("--path")
         .arg(dir.path())
         .output()?;
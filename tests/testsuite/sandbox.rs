@@ -0,0 +1,56 @@
+use ansi_term::Color;
+use std::io;
+
+use crate::common;
+
+fn render_sandbox(env: &[(&str, &str)]) -> io::Result<String> {
+    let output = common::render_module("sandbox")
+        .envs(env.iter().cloned())
+        .output()?;
+    Ok(String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn on_host() -> io::Result<()> {
+    let actual = render_sandbox(&[])?;
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn in_flatpak() -> io::Result<()> {
+    let actual = render_sandbox(&[("FLATPAK_ID", "org.gnome.Builder")])?;
+
+    let expected = format!("in {} ", Color::Purple.bold().paint("📦 org.gnome.Builder"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn in_snap() -> io::Result<()> {
+    let actual = render_sandbox(&[("SNAP", "/snap/code/123"), ("SNAP_NAME", "code")])?;
+
+    let expected = format!("in {} ", Color::Purple.bold().paint("📦 code"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn in_snap_without_name() -> io::Result<()> {
+    let actual = render_sandbox(&[("SNAP", "/snap/alacritty/46")])?;
+
+    let expected = format!("in {} ", Color::Purple.bold().paint("📦 alacritty"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn empty_flatpak_id() -> io::Result<()> {
+    let actual = render_sandbox(&[("FLATPAK_ID", "")])?;
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use ansi_term::Color;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn not_in_allocation() -> io::Result<()> {
+    let output = common::render_module("slurm")
+        .env_clear()
+        .env("PATH", env!("PATH"))
+        .output()?;
+
+    let expected = "";
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn job_id_only() -> io::Result<()> {
+    let output = common::render_module("slurm")
+        .env_clear()
+        .env("SLURM_JOB_ID", "123456")
+        .output()?;
+
+    let expected = format!("in {} ", Color::Cyan.bold().paint("🖥 123456"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn legacy_job_id() -> io::Result<()> {
+    let output = common::render_module("slurm")
+        .env_clear()
+        .env("SLURM_JOBID", "42")
+        .output()?;
+
+    let expected = format!("in {} ", Color::Cyan.bold().paint("🖥 42"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn job_with_name_and_partition() -> io::Result<()> {
+    let output = common::render_module("slurm")
+        .env_clear()
+        .env("SLURM_JOB_ID", "123456")
+        .env("SLURM_JOB_NAME", "train")
+        .env("SLURM_JOB_PARTITION", "gpu")
+        .output()?;
+
+    let expected = format!("in {} ", Color::Cyan.bold().paint("🖥 123456 train@gpu"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn hide_name_and_partition() -> io::Result<()> {
+    let output = common::render_module("slurm")
+        .env_clear()
+        .env("SLURM_JOB_ID", "123456")
+        .env("SLURM_JOB_NAME", "train")
+        .env("SLURM_JOB_PARTITION", "gpu")
+        .use_config(toml::toml! {
+            [slurm]
+            show_job_name = false
+            show_partition = false
+        })
+        .output()?;
+
+    let expected = format!("in {} ", Color::Cyan.bold().paint("🖥 123456"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use ansi_term::Color;
+use std::fs::{self, File};
+use std::io;
+use std::time::{Duration, SystemTime};
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn folder_without_terraform_files() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("terraform")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_tf_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("main.tf"))?.sync_all()?;
+
+    let output = common::render_module("terraform")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Fixed(105).bold().paint("💠 default"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_without_terraform_files_detect_always_not_installed() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("terraform")
+        .env("PATH", bin_dir.path())
+        .use_config(toml::toml! {
+            [terraform]
+            detect_always = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn folder_without_terraform_files_detect_always() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let bin_dir = tempfile::tempdir()?;
+
+    let output = common::render_module("terraform")
+        .env(
+            "PATH",
+            common::create_stub_command(bin_dir.path(), "terraform", "echo 'Terraform v1.5.7'")?,
+        )
+        .use_config(toml::toml! {
+            [terraform]
+            detect_always = true
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Fixed(105).bold().paint("💠 default"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_selected_workspace() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::create_dir(dir.path().join(".terraform"))?;
+    fs::write(dir.path().join(".terraform/environment"), "staging")?;
+
+    let output = common::render_module("terraform")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Fixed(105).bold().paint("💠 staging"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_stale_lock_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let lock_file = File::create(dir.path().join(".terraform.lock.hcl"))?;
+    lock_file.set_modified(SystemTime::now() - Duration::from_secs(60))?;
+    lock_file.sync_all()?;
+    File::create(dir.path().join("main.tf"))?.sync_all()?;
+
+    let output = common::render_module("terraform")
+        .arg("--path")
+        .arg(dir.path())
+        .use_config(toml::toml! {
+            [terraform]
+            check_lock_file = true
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Fixed(105).bold().paint("💠 default ⚠"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn folder_with_up_to_date_lock_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let main_tf = File::create(dir.path().join("main.tf"))?;
+    main_tf.set_modified(SystemTime::now() - Duration::from_secs(60))?;
+    main_tf.sync_all()?;
+    File::create(dir.path().join(".terraform.lock.hcl"))?.sync_all()?;
+
+    let output = common::render_module("terraform")
+        .arg("--path")
+        .arg(dir.path())
+        .use_config(toml::toml! {
+            [terraform]
+            check_lock_file = true
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Fixed(105).bold().paint("💠 default"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
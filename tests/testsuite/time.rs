@@ -56,3 +56,69 @@ fn config_check_prefix_and_suffix() -> io::Result<()> {
     assert!(actual.ends_with(&col_suffix));
     Ok(())
 }
+
+#[test]
+fn config_show_time_zone() -> io::Result<()> {
+    let output = common::render_module("time")
+        .env("TZ", "Asia/Tokyo")
+        .use_config(toml::toml! {
+            [time]
+            disabled = false
+            show_time_zone = true
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert!(actual.ends_with(" JST\u{1b}[0m "));
+    assert!(!actual.contains("(DST)"));
+    Ok(())
+}
+
+#[test]
+fn config_show_unknown_time_zone() -> io::Result<()> {
+    let output = common::render_module("time")
+        .env("TZ", "Mars/Olympus_Mons")
+        .use_config(toml::toml! {
+            [time]
+            disabled = false
+            show_time_zone = true
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert!(actual.starts_with("at "));
+    assert!(!actual.contains("Mars"));
+    Ok(())
+}
+
+#[test]
+fn config_time_zone_hidden_by_default() -> io::Result<()> {
+    let output = common::render_module("time")
+        .env("TZ", "Asia/Tokyo")
+        .use_config(toml::toml! {
+            [time]
+            disabled = false
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!actual.contains("JST"));
+    Ok(())
+}
+
+#[test]
+fn config_invalid_format() -> io::Result<()> {
+    let output = common::render_module("time")
+        .use_config(toml::toml! {
+            [time]
+            disabled = false
+            format = "%H:%"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert!(output.status.success());
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -1,11 +1,9 @@
 use ansi_term::Color;
 use std::io;
+use std::process::Command;
 
 use crate::common::{self, TestCommand};
 
-// TODO: Add tests for if root user (UID == 0)
-// Requires mocking
-
 #[test]
 fn no_env_variables() -> io::Result<()> {
     let output = common::render_module("username").output()?;
@@ -26,13 +24,17 @@ fn logname_equals_user() -> io::Result<()> {
 }
 
 #[test]
+#[cfg(not(windows))]
 fn ssh_wo_username() -> io::Result<()> {
-    // SSH connection w/o username
+    // SSH connection w/o username, which is looked up from the UID instead
     let output = common::render_module("username")
         .env("SSH_CONNECTION", "192.168.223.17 36673 192.168.223.229 22")
         .output()?;
     let actual = String::from_utf8(output.stdout).unwrap();
-    assert_eq!("", actual);
+
+    let id_output = Command::new("id").arg("-un").output()?;
+    let username = String::from_utf8(id_output.stdout).unwrap();
+    assert!(actual.contains(username.trim()));
     Ok(())
 }
 
@@ -77,3 +79,16 @@ fn show_always() -> io::Result<()> {
     assert_eq!(expected, actual);
     Ok(())
 }
+
+#[test]
+fn logname_used_without_user() -> io::Result<()> {
+    let output = common::render_module("username")
+        .env("LOGNAME", "astronaut")
+        .env("SSH_CONNECTION", "192.168.223.17 36673 192.168.223.229 22")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    // The style depends on whether the tests are run as root
+    assert!(actual.contains("astronaut"));
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use ansi_term::Color;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use crate::common;
+
+#[cfg(not(windows))]
+fn render_vcs_branch(dir: &Path) -> io::Result<String> {
+    let bin_dir = tempfile::tempdir()?;
+    common::create_stub_command(bin_dir.path(), "fossil", "echo 'feature-x'")?;
+    let path = common::create_stub_command(bin_dir.path(), "svn", "echo '^/branches/release-1.2'")?;
+
+    let output = common::render_module("vcs_branch")
+        .env("PATH", path)
+        .arg("--path")
+        .arg(dir)
+        .output()?;
+    Ok(String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn not_in_checkout() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("vcs_branch")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!("", actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn in_svn_checkout() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::create_dir_all(dir.path().join(".svn"))?;
+    fs::create_dir_all(dir.path().join("src/lib"))?;
+
+    let actual = render_vcs_branch(&dir.path().join("src/lib"))?;
+
+    let expected = format!("on {} ", Color::Purple.bold().paint("svn release-1.2"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn in_fossil_checkout() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join(".fslckout"))?.sync_all()?;
+
+    let actual = render_vcs_branch(dir.path())?;
+
+    let expected = format!("on {} ", Color::Purple.bold().paint("fossil feature-x"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn in_legacy_fossil_checkout() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("_FOSSIL_"))?.sync_all()?;
+
+    let actual = render_vcs_branch(dir.path())?;
+
+    let expected = format!("on {} ", Color::Purple.bold().paint("fossil feature-x"));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+#[cfg(not(windows))]
+fn git_repo_nested_in_svn_checkout() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::create_dir_all(dir.path().join(".svn"))?;
+    fs::create_dir_all(dir.path().join("vendor/lib/.git"))?;
+
+    let actual = render_vcs_branch(&dir.path().join("vendor/lib"))?;
+
+    assert_eq!("", actual);
+    dir.close()
+}
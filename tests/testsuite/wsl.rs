@@ -0,0 +1,55 @@
+use ansi_term::Color;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn not_in_wsl() -> io::Result<()> {
+    let output = common::render_module("wsl").output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn in_wsl() -> io::Result<()> {
+    let output = common::render_module("wsl")
+        .env("WSL_DISTRO_NAME", "Ubuntu-20.04")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("in {} ", Color::Blue.bold().paint("WSL Ubuntu-20.04"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn empty_distro_name() -> io::Result<()> {
+    let output = common::render_module("wsl")
+        .env("WSL_DISTRO_NAME", "")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn in_wsl_with_config() -> io::Result<()> {
+    let output = common::render_module("wsl")
+        .env("WSL_DISTRO_NAME", "Debian")
+        .use_config(toml::toml! {
+            [wsl]
+            symbol = "🐧 "
+            style = "bold green"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("in {} ", Color::Green.bold().paint("🐧 Debian"));
+    assert_eq!(expected, actual);
+    Ok(())
+}